@@ -0,0 +1,126 @@
+use super::traits::*;
+use super::binding::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use alloc::boxed::Box;
+use core::ops::{Sub};
+
+///
+/// A binding that ignores any new value that is within a tolerance of its current value
+///
+/// This is intended for floating-point values, where tiny changes caused by rounding errors or noisy inputs would
+/// otherwise cause a notification every time the binding is set. Small changes are not lost: the binding keeps its
+/// current value, so a series of small changes will update it once they add up to more than the tolerance.
+///
+#[derive(Clone)]
+pub struct ApproxBinding<Value> {
+    /// The binding where the value is stored
+    binding: Binding<Value>,
+
+    /// Values closer than this to the current value are treated as equal to it
+    epsilon: Value
+}
+
+impl<Value: 'static+Copy+PartialOrd+Sub<Output=Value>+Send+Sync> ApproxBinding<Value> {
+    ///
+    /// Creates a new binding that ignores changes smaller than `epsilon`
+    ///
+    pub fn new(initial_value: Value, epsilon: Value) -> ApproxBinding<Value> {
+        ApproxBinding {
+            binding: Binding::new(initial_value),
+            epsilon
+        }
+    }
+}
+
+impl<Value: 'static+Copy+PartialOrd+Sub<Output=Value>+Send+Sync> Changeable for ApproxBinding<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.binding.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.binding.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.binding.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.binding.binding_id()
+    }
+}
+
+impl<Value: 'static+Copy+PartialOrd+Sub<Output=Value>+Send+Sync> Bound<Value> for ApproxBinding<Value> {
+    fn get(&self) -> Value {
+        self.binding.get()
+    }
+}
+
+impl<Value: 'static+Copy+PartialOrd+Sub<Output=Value>+Send+Sync> MutableBound<Value> for ApproxBinding<Value> {
+    fn set(&self, new_value: Value) {
+        let epsilon = self.epsilon;
+
+        self.binding.with_mut(move |value| {
+            let difference = if new_value > *value { new_value - *value } else { *value - new_value };
+
+            // NaN is never within the tolerance, so it always replaces the current value
+            if difference <= epsilon {
+                false
+            } else {
+                *value = new_value;
+                true
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn ignores_small_changes() {
+        let position        = bind_approx(1.0, 0.01);
+        let changed         = bind(false);
+
+        let notify_changed  = changed.clone();
+        position.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        position.set(1.005);
+        assert!(!changed.get());
+        assert!(position.get() == 1.0);
+
+        position.set(0.995);
+        assert!(!changed.get());
+        assert!(position.get() == 1.0);
+    }
+
+    #[test]
+    fn notifies_for_large_changes() {
+        let position        = bind_approx(1.0f32, 0.01);
+        let changed         = bind(false);
+
+        let notify_changed  = changed.clone();
+        position.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        position.set(1.5);
+        assert!(changed.get());
+        assert!(position.get() == 1.5);
+    }
+
+    #[test]
+    fn small_changes_add_up() {
+        let position = bind_approx(0.0, 0.01);
+
+        for step in 1..=5 {
+            position.set(step as f64 * 0.004);
+        }
+
+        // Changes are measured from the value that was last stored, so the binding moves once they add up to more than the tolerance
+        assert!(position.get() == 3.0 * 0.004);
+    }
+}
@@ -1,11 +1,29 @@
 use super::traits::*;
 use super::releasable::*;
 use super::binding_context::*;
+use super::binding::*;
+use super::bindref::*;
+#[cfg(any(test, feature = "test-support"))]
+use super::flush_pending::*;
 
 use futures::prelude::*;
 use ::desync::*;
 
 use std::sync::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Poll};
+
+///
+/// The result of an update function passed to `bind_stream_with_final()`
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StreamUpdate<Value> {
+    /// Sets the binding to a new value and keeps processing updates from the stream
+    Keep(Value),
+
+    /// Sets the binding to a new value and stops reading from the stream
+    Final(Value)
+}
 
 ///
 /// Uses a stream to update a binding
@@ -15,15 +33,96 @@ where   S:          'static+Send+Stream+Unpin,
         Value:      'static+Send+Clone+PartialEq,
         UpdateFn:   'static+Send+FnMut(Value, S::Item) -> Value,
         S::Item:    Send {
+    let mut update = update;
+
+    bind_stream_with_final(stream, initial_value, move |value, next_item| StreamUpdate::Keep(update(value, next_item)))
+}
+
+///
+/// Uses a stream to update a binding, passing each item through a filter function before the update function
+///
+/// The filter can transform the items from the stream, or return `None` to skip an item so that the update function
+/// is only called for the items that are relevant to the binding.
+///
+pub fn bind_stream_filtered<S, Value, Item, FilterFn, UpdateFn>(stream: S, initial_value: Value, filter: FilterFn, update: UpdateFn) -> StreamBinding<Value>
+where   S:          'static+Send+Stream+Unpin,
+        Value:      'static+Send+Clone+PartialEq,
+        FilterFn:   'static+Send+FnMut(S::Item) -> Option<Item>,
+        UpdateFn:   'static+Send+FnMut(Value, Item) -> Value,
+        S::Item:    Send {
+    let mut filter = filter;
+    let mut update = update;
+
+    bind_stream_with_final(stream, initial_value, move |value, next_item| {
+        match filter(next_item) {
+            Some(item)  => StreamUpdate::Keep(update(value, item)),
+            None        => StreamUpdate::Keep(value)
+        }
+    })
+}
+
+///
+/// Uses a stream to update a binding, and also returns a binding to the number of items that have been read from the stream
+///
+/// The count goes up for every item that's processed, whether or not it changes the value of the binding, so it can be
+/// used to tell a stream that's still running but producing the same value apart from one that has stalled. Items are
+/// only read from the stream while the `StreamBinding` is kept alive.
+///
+pub fn bind_stream_counted<S, Value, UpdateFn>(stream: S, initial_value: Value, update: UpdateFn) -> (StreamBinding<Value>, BindRef<usize>)
+where   S:          'static+Send+Stream+Unpin,
+        Value:      'static+Send+Clone+PartialEq,
+        UpdateFn:   'static+Send+FnMut(Value, S::Item) -> Value,
+        S::Item:    Send {
+    let mut update  = update;
+    let count       = Binding::new(0);
+    let count_copy  = count.clone();
+
+    let binding     = bind_stream_with_final(stream, initial_value, move |value, next_item| {
+        count_copy.with_mut(|count| { *count += 1; true });
+
+        StreamUpdate::Keep(update(value, next_item))
+    });
+
+    (binding, BindRef::from(count))
+}
+
+///
+/// Uses a stream to update a binding, where the update function can indicate that the binding has reached its final value
+/// 
+/// Once the update function returns `StreamUpdate::Final`, the binding is set to the final value and the stream is
+/// dropped without reading any further items from it. This is useful for bindings that track a process that can finish, such as a state
+/// machine that reaches a terminal state.
+/// 
+pub fn bind_stream_with_final<S, Value, UpdateFn>(stream: S, initial_value: Value, update: UpdateFn) -> StreamBinding<Value>
+where   S:          'static+Send+Stream+Unpin,
+        Value:      'static+Send+Clone+PartialEq,
+        UpdateFn:   'static+Send+FnMut(Value, S::Item) -> StreamUpdate<Value>,
+        S::Item:    Send {
     // Create the content of the binding
-    let value       = Arc::new(Mutex::new(initial_value));
-    let core        = StreamBindingCore {
+    let value           = Arc::new(Mutex::new(initial_value));
+    let notifications   = Arc::new(Mutex::new(vec![]));
+    let core            = StreamBindingCore {
         value:          Arc::clone(&value),
-        notifications:  vec![]
+        notifications:  Arc::clone(&notifications)
     };
 
-    let core        = Arc::new(Desync::new(core));
-    let mut update  = update;
+    let core            = Arc::new(Desync::new(core));
+    let mut update      = update;
+    let is_final        = Arc::new(AtomicBool::new(false));
+
+    // The stream is released once the final value has been received (items are processed one at a time, so this is checked before the stream is read again)
+    let stream_is_final = Arc::clone(&is_final);
+    let mut stream      = Some(stream);
+    let stream          = stream::poll_fn(move |context| {
+        if stream_is_final.load(Ordering::Acquire) {
+            stream = None;
+        }
+
+        match stream.as_mut() {
+            Some(stream)    => stream.poll_next_unpin(context),
+            None            => Poll::Ready(None)
+        }
+    });
 
     // Send in the stream
     pipe_in(Arc::clone(&core), stream, 
@@ -32,7 +131,13 @@ where   S:          'static+Send+Stream+Unpin,
             let need_to_notify = {
                 // Update the value
                 let mut value = core.value.lock().unwrap();
-                let new_value = update((*value).clone(), next_item);
+                let new_value = match update((*value).clone(), next_item) {
+                    StreamUpdate::Keep(new_value)   => new_value,
+                    StreamUpdate::Final(new_value)  => {
+                        is_final.store(true, Ordering::Release);
+                        new_value
+                    }
+                };
 
                 if new_value != *value {
                     // Update the value in the core
@@ -47,16 +152,22 @@ where   S:          'static+Send+Stream+Unpin,
 
             // If the update changed the value, then call the notifications (with the lock released, in case any try to read the value)
             if need_to_notify {
-                core.notifications.retain(|notify| notify.is_in_use());
-                core.notifications.iter().for_each(|notify| { notify.mark_as_changed(); });
+                let to_notify = {
+                    let mut notifications = core.notifications.lock().unwrap();
+                    notifications.retain(|notify| notify.is_in_use());
+                    notifications.iter().map(|notify| notify.clone_for_inspection()).collect::<Vec<_>>()
+                };
+
+                to_notify.iter().for_each(|notify| { notify.mark_as_changed(); });
             }
 
             Box::pin(future::ready(()))
         });
     
     StreamBinding {
-        core:   core,
-        value:  value
+        core,
+        value,
+        notifications
     }
 }
 
@@ -65,11 +176,15 @@ where   S:          'static+Send+Stream+Unpin,
 /// 
 #[derive(Clone)]
 pub struct StreamBinding<Value: Send> {
-    /// The core of the binding (where updates are streamed and notifications sent)
+    /// The core of the binding (where updates are streamed and notifications sent: the stream is only followed while this is kept alive)
+    #[allow(dead_code)]
     core: Arc<Desync<StreamBindingCore<Value>>>,
 
     /// The current value of the binding
-    value: Arc<Mutex<Value>>
+    value: Arc<Mutex<Value>>,
+
+    /// The items that should be notified when this binding changes (shared with the core, so they can be added without waiting for the stream)
+    notifications: Arc<Mutex<Vec<ReleasableNotifiable>>>
 }
 
 ///
@@ -80,7 +195,7 @@ struct StreamBindingCore<Value: Send> {
     value: Arc<Mutex<Value>>,
 
     /// The items that should be notified when this binding changes
-    notifications: Vec<ReleasableNotifiable>
+    notifications: Arc<Mutex<Vec<ReleasableNotifiable>>>
 }
 
 impl<Value: 'static+Send+Clone> Bound<Value> for StreamBinding<Value> {
@@ -104,14 +219,23 @@ impl<Value: 'static+Send> Changeable for StreamBinding<Value> {
         let releasable = ReleasableNotifiable::new(what);
         let notifiable = releasable.clone_as_owned();
 
-        // Send to the core
-        self.core.desync(move |core| {
-            core.notifications.push(notifiable);
-        });
+        // Add immediately: if this waited for the core, an update that was already queued could change the value without notifying it
+        self.notifications.lock().unwrap().push(notifiable);
 
         // Return the releasable object
         Box::new(releasable)
     }
+
+    fn release_unused_notifications(&self) {
+        self.notifications.lock().unwrap().retain(|notify| notify.is_in_use());
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl<Value: 'static+Send> FlushPending for StreamBinding<Value> {
+    fn flush_pending(&self) {
+        self.core.sync(|_| { });
+    }
 }
 
 #[cfg(test)]
@@ -135,7 +259,7 @@ mod test {
         // Send the stream to a new binding
         let binding = bind_stream(stream, 0, |_old_value, new_value| new_value);
 
-        thread::sleep(Duration::from_millis(10));
+        flush_pending(&binding);
 
         // Binding should have the value of the last value in the stream
         assert!(binding.get() == 3);
@@ -150,12 +274,86 @@ mod test {
         // Send the stream to a new binding (with some processing)
         let binding = bind_stream(stream, 0, |_old_value, new_value| new_value + 42);
 
-        thread::sleep(Duration::from_millis(10));
+        flush_pending(&binding);
 
         // Binding should have the value of the last value in the stream
         assert!(binding.get() == 45);
     }
 
+    #[test]
+    pub fn filter_skips_items() {
+        // Stream with the values '1,2,3,4,5'
+        let stream  = vec![1, 2, 3, 4, 5];
+        let stream  = stream::iter(stream);
+
+        // Only add up the even values
+        let binding = bind_stream_filtered(stream, 0, 
+            |item| if item % 2 == 0 { Some(item) } else { None },
+            |total, item| total + item);
+
+        flush_pending(&binding);
+
+        assert!(binding.get() == 6);
+    }
+
+    #[test]
+    pub fn filter_transforms_items() {
+        let stream  = vec!["1", "two", "3"];
+        let stream  = stream::iter(stream);
+
+        // Skip any items that aren't numbers
+        let binding = bind_stream_filtered(stream, 0, |item: &str| item.parse::<i32>().ok(), |total, item| total + item);
+
+        flush_pending(&binding);
+
+        assert!(binding.get() == 4);
+    }
+
+    #[test]
+    pub fn ignore_updates_after_final_value() {
+        // Stream with the values '1,2,3,4'
+        let stream  = vec![1, 2, 3, 4];
+        let stream  = stream::iter(stream);
+
+        // Binding stops updating once it sees a '2'
+        let binding = bind_stream_with_final(stream, 0, |_old_value, new_value| {
+            if new_value == 2 {
+                StreamUpdate::Final(new_value)
+            } else {
+                StreamUpdate::Keep(new_value)
+            }
+        });
+
+        flush_pending(&binding);
+
+        // Binding should have the final value rather than the last value in the stream
+        assert!(binding.get() == 2);
+    }
+
+    #[test]
+    pub fn stream_is_released_after_final_value() {
+        let (sender, receiver)  = futures::channel::mpsc::unbounded();
+        let binding             = bind_stream_with_final(receiver, 0, |_old_value, new_value| StreamUpdate::Final(new_value));
+
+        sender.unbounded_send(1).unwrap();
+        flush_pending(&binding);
+
+        // The receiver is dropped as soon as the final value arrives, without waiting for another item
+        assert!(binding.get() == 1);
+        assert!(sender.is_closed());
+    }
+
+    #[test]
+    pub fn counts_items_that_do_not_change_value() {
+        let stream              = stream::iter(vec![1, 1, 2, 2, 2]);
+        let (binding, count)    = bind_stream_counted(stream, 0, |_old_value, new_value| new_value);
+
+        binding.flush_pending();
+
+        assert!(binding.get() == 2);
+        assert!(count.get() == 5);
+    }
+
     #[test]
     pub fn notifies_on_change() {
         // Create somewhere to send our notifications
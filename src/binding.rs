@@ -1,8 +1,15 @@
 use super::traits::*;
 use super::releasable::*;
 use super::binding_context::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
 
-use std::sync::*;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::boxed::Box;
+use core::mem;
+use core::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign};
 
 ///
 /// An internal representation of a bound value
@@ -11,6 +18,25 @@ struct BoundValue<Value> {
     /// The current value of this binding
     value: Value,
 
+    /// The generation of the value, which increases every time it's changed
+    generation: u64,
+
+    /// The ID of this binding in the binding graph
+    #[cfg(feature = "graph")]
+    id: BindingId,
+
+    /// True if this binding only notifies once between reads
+    deferred: bool,
+
+    /// For deferred bindings, true if the notifications have been sent and the value has not been read since
+    waiting_for_read: bool,
+
+    /// The number of `PausedNotifications` guards that currently exist for this binding
+    paused: usize,
+
+    /// The value when the notifications were paused, which is compared with the value when they're resumed
+    value_before_pause: Option<Value>,
+
     /// What to call when the value changes
     when_changed: Vec<ReleasableNotifiable>
 }
@@ -21,8 +47,15 @@ impl<Value: Clone+PartialEq> BoundValue<Value> {
     ///
     pub fn new(val: Value) -> BoundValue<Value> {
         BoundValue {
-            value:          val,
-            when_changed:   vec![]
+            value:                val,
+            generation:           0,
+            #[cfg(feature = "graph")]
+            id:                   BindingId::new(),
+            deferred:             false,
+            waiting_for_read:     false,
+            paused:               0,
+            value_before_pause:   None,
+            when_changed:         vec![]
         }
     }
 
@@ -33,6 +66,7 @@ impl<Value: Clone+PartialEq> BoundValue<Value> {
         let changed = self.value != new_value;
 
         self.value = new_value;
+        if changed { self.generation += 1; }
 
         changed
     }
@@ -40,11 +74,29 @@ impl<Value: Clone+PartialEq> BoundValue<Value> {
     ///
     /// Retrieves a copy of the list of notifiable items for this value
     ///
-    pub fn get_notifiable_items(&self) -> Vec<ReleasableNotifiable> {
-        self.when_changed
+    /// For deferred bindings, this is empty if the notifications were already sent and the value hasn't been read since
+    ///
+    pub fn get_notifiable_items(&mut self) -> Vec<ReleasableNotifiable> {
+        if self.paused > 0 {
+            // The notifications are sent when the last guard is dropped (if the value is different from when they were paused)
+            return vec![];
+        }
+
+        if self.deferred && self.waiting_for_read {
+            return vec![];
+        }
+
+        let notifiable = self.when_changed
             .iter()
             .map(|item| item.clone_for_inspection())
-            .collect()
+            .collect::<Vec<_>>();
+
+        // Deferred bindings wait for a read once something has been notified (anything that subscribes before then needs to be told about this change)
+        if self.deferred && !notifiable.is_empty() {
+            self.waiting_for_read = true;
+        }
+
+        notifiable
     }
 
     ///
@@ -57,10 +109,19 @@ impl<Value: Clone+PartialEq> BoundValue<Value> {
     ///
     /// Retrieves the value of this item
     ///
-    fn get(&self) -> Value {
+    fn get(&mut self) -> Value {
+        self.waiting_for_read = false;
         self.value.clone()
     }
 
+    ///
+    /// Retrieves the value of this item along with its generation
+    ///
+    fn get_versioned(&mut self) -> (Value, u64) {
+        self.waiting_for_read = false;
+        (self.value.clone(), self.generation)
+    }
+
     ///
     /// Retrieves a mutable reference to the value of this item
     ///
@@ -87,8 +148,8 @@ impl<Value: Default + Clone + PartialEq> Default for BoundValue<Value> {
     }
 }
 
-impl<Value: std::fmt::Debug> std::fmt::Debug for BoundValue<Value> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<Value: core::fmt::Debug> core::fmt::Debug for BoundValue<Value> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.value.fmt(f)
     }
 }
@@ -101,6 +162,15 @@ impl<Value: PartialEq> PartialEq for BoundValue<Value> {
 ///
 /// Represents a thread-safe, sharable binding
 ///
+/// A binding is `Send` and `Sync` whenever its value is `Send`, so clones of the
+/// same binding can be used from multiple threads.
+///
+/// Notifications are sent synchronously: by the time `set()` returns, everything
+/// that was subscribed to the binding has been notified of the change. Clones of
+/// a binding share the same list of subscribers, and they're always notified in
+/// the order they subscribed, whichever clone was used to subscribe or to set
+/// the value.
+///
 #[derive(Clone)]
 pub struct Binding<Value> {
     /// The value stored in this binding
@@ -113,8 +183,8 @@ impl<Value: Default + Clone + PartialEq> Default for Binding<Value> {
     }
 }
 
-impl<Value: std::fmt::Debug> std::fmt::Debug for Binding<Value> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<Value: core::fmt::Debug> core::fmt::Debug for Binding<Value> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.value.fmt(f)
     }
 }
@@ -131,12 +201,180 @@ impl<Value: Clone+PartialEq> Binding<Value> {
             value: Arc::new(Mutex::new(BoundValue::new(value)))
         }
     }
+
+    ///
+    /// Creates a binding that notifies once per read-gap
+    ///
+    /// After a deferred binding has notified that it has changed, further changes do not generate any more
+    /// notifications until its value has been read again. A burst of `set()` calls between two reads
+    /// therefore produces exactly one notification, and the next read returns the final value.
+    ///
+    pub fn new_deferred(value: Value) -> Binding<Value> {
+        let mut bound_value = BoundValue::new(value);
+        bound_value.deferred = true;
+
+        Binding {
+            value: Arc::new(Mutex::new(bound_value))
+        }
+    }
+
+    ///
+    /// Creates a new, independent binding that starts with the current value of this one
+    ///
+    /// Unlike `clone()`, which returns another reference to the same binding, the fork has its own value and its own
+    /// subscribers: setting either binding afterwards does not affect or notify the other. This is useful for editing a
+    /// copy of a value that might be copied back later. The fork is deferred if this binding is, and reading the value
+    /// here does not make a computed binding depend on this binding.
+    ///
+    pub fn fork(&self) -> Binding<Value> {
+        let (value, deferred) = {
+            let bound_value = self.value.lock().unwrap();
+            (bound_value.value.clone(), bound_value.deferred)
+        };
+
+        if deferred {
+            Binding::new_deferred(value)
+        } else {
+            Binding::new(value)
+        }
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Binding<Value> {
+    ///
+    /// Suspends the notifications for this binding until the returned guard is dropped
+    ///
+    /// While the guard exists, setting the binding updates its value without notifying anything. When the
+    /// guard is dropped, a single notification is sent if the value is different from when the notifications were paused. This is useful
+    /// when loading a lot of state into a binding that shouldn't cause a cascade of updates. If several guards
+    /// exist for the same binding, the notification is sent when the last one is dropped.
+    ///
+    pub fn pause_notifications(&self) -> PausedNotifications<Value> {
+        let mut cell = self.value.lock().unwrap();
+
+        if cell.paused == 0 {
+            cell.value_before_pause = Some(cell.value.clone());
+        }
+        cell.paused += 1;
+        drop(cell);
+
+        PausedNotifications {
+            binding: self.clone()
+        }
+    }
+
+    ///
+    /// Retrieves the generation of this binding
+    ///
+    /// The generation starts at 0 and increases every time the value of the binding changes. Code that caches
+    /// results derived from the binding can compare generations to check if the cache is still valid.
+    ///
+    pub fn generation(&self) -> u64 {
+        self.value.lock().unwrap().generation
+    }
+
+    ///
+    /// Retrieves the value of this binding along with the generation it belongs to
+    ///
+    /// The value and the generation are read together, so the generation always matches the value that's returned.
+    ///
+    pub fn get_versioned(&self) -> (Value, u64) {
+        BindingContext::add_dependency(self.clone());
+
+        self.value.lock().unwrap().get_versioned()
+    }
+
+    ///
+    /// Sets the value of this binding to `new_value` if its current value is `expected`, returning true if it was set
+    ///
+    /// The comparison and the update are made with the binding locked, so when several threads try to change the binding
+    /// from the same value, only one of them will succeed. For example, this can be used to make sure that only one
+    /// thread moves a state binding from `Idle` to `Running`.
+    ///
+    pub fn compare_and_set(&self, expected: Value, new_value: Value) -> bool {
+        let mut was_set = false;
+
+        self.with_mut(|value| {
+            if *value != expected {
+                return false;
+            }
+
+            // Only notify if the value actually changes
+            was_set = true;
+            if *value != new_value {
+                *value = new_value;
+                true
+            } else {
+                false
+            }
+        });
+
+        was_set
+    }
+
+    ///
+    /// Exchanges the values of two bindings
+    ///
+    /// Both bindings are locked while their values are exchanged, so no other thread can see one binding
+    /// updated without the other. Each binding notifies once if its value changed.
+    ///
+    pub fn swap(a: &Binding<Value>, b: &Binding<Value>) {
+        // Swapping a binding with itself leaves it unchanged (and would deadlock if we tried to lock it twice)
+        if Arc::ptr_eq(&a.value, &b.value) {
+            return;
+        }
+
+        // The bindings are always locked in address order, so two threads swapping the same bindings can't deadlock
+        let (first, second) = if (Arc::as_ptr(&a.value) as usize) < (Arc::as_ptr(&b.value) as usize) { (a, b) } else { (b, a) };
+
+        let notifications = {
+            let mut first   = first.value.lock().unwrap();
+            let mut second  = second.value.lock().unwrap();
+
+            if first.value == second.value {
+                vec![]
+            } else {
+                mem::swap(&mut first.value, &mut second.value);
+                first.generation    += 1;
+                second.generation   += 1;
+
+                let mut notifications = first.get_notifiable_items();
+                notifications.extend(second.get_notifiable_items());
+                notifications
+            }
+        };
+
+        // Call the notifications outside of the locks
+        let mut needs_filtering = false;
+
+        for to_notify in notifications {
+            needs_filtering = !to_notify.mark_as_changed() || needs_filtering;
+        }
+
+        if needs_filtering {
+            first.value.lock().unwrap().filter_unused_notifications();
+            second.value.lock().unwrap().filter_unused_notifications();
+        }
+    }
 }
 
 impl<Value: 'static+Clone+PartialEq+Send> Changeable for Binding<Value> {
     fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
         self.value.lock().unwrap().when_changed(what)
     }
+
+    fn release_unused_notifications(&self) {
+        self.value.lock().unwrap().filter_unused_notifications();
+    }
+
+    fn identity(&self) -> Option<usize> {
+        Some(Arc::as_ptr(&self.value) as *const () as usize)
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        Some(self.value.lock().unwrap().id)
+    }
 }
 
 impl<Value: 'static+Clone+PartialEq+Send> Bound<Value> for Binding<Value> {
@@ -180,7 +418,10 @@ impl<Value: 'static + Clone + PartialEq + Send> WithBound<Value> for Binding<Val
     where
         F: FnOnce(&Value) -> T,
     {
-        f(&self.value.lock().unwrap().value)
+        let mut cell = self.value.lock().unwrap();
+        cell.waiting_for_read = false;
+
+        f(&cell.value)
     }
     fn with_mut<F>(&self, f: F)
     where
@@ -191,6 +432,7 @@ impl<Value: 'static + Clone + PartialEq + Send> WithBound<Value> for Binding<Val
             let changed = f(v.get_mut());
 
             if changed {
+                v.generation += 1;
                 v.get_notifiable_items()
             } else {
                 vec![]
@@ -212,6 +454,51 @@ impl<Value: 'static + Clone + PartialEq + Send> WithBound<Value> for Binding<Val
 
 }
 
+impl<Value: 'static+Clone+PartialEq+Send> Binding<Value> {
+    ///
+    /// Replaces the value of this binding with the result of a function, with the binding locked so no other update can be lost
+    ///
+    fn update_with(&self, update: impl FnOnce(Value) -> Value) {
+        self.with_mut(move |value| {
+            let new_value = update(value.clone());
+
+            if *value != new_value {
+                *value = new_value;
+                true
+            } else {
+                false
+            }
+        });
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send+Add<Output=Value>> AddAssign<Value> for Binding<Value> {
+    ///
+    /// Adds to the value of this binding (as a single update, so this is safe to use from multiple threads)
+    ///
+    fn add_assign(&mut self, rhs: Value) {
+        self.update_with(move |value| value + rhs);
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send+Sub<Output=Value>> SubAssign<Value> for Binding<Value> {
+    ///
+    /// Subtracts from the value of this binding (as a single update, so this is safe to use from multiple threads)
+    ///
+    fn sub_assign(&mut self, rhs: Value) {
+        self.update_with(move |value| value - rhs);
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send+Mul<Output=Value>> MulAssign<Value> for Binding<Value> {
+    ///
+    /// Multiplies the value of this binding (as a single update, so this is safe to use from multiple threads)
+    ///
+    fn mul_assign(&mut self, rhs: Value) {
+        self.update_with(move |value| value * rhs);
+    }
+}
+
 impl<Value: 'static+Clone+PartialEq+Send> From<Value> for Binding<Value> {
     #[inline]
     fn from(val: Value) -> Binding<Value> {
@@ -232,3 +519,47 @@ impl<'a, Value: 'static+Clone+PartialEq+Send> From<&'a Value> for Binding<Value>
         Binding::new(val.clone())
     }
 }
+
+///
+/// Guard returned by `Binding::pause_notifications()`: the binding sends any notifications that were suppressed when this is dropped
+///
+#[must_use = "notifications are resumed as soon as the guard is dropped"]
+pub struct PausedNotifications<Value: 'static+Clone+PartialEq+Send> {
+    /// The binding whose notifications are paused
+    binding: Binding<Value>
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Drop for PausedNotifications<Value> {
+    fn drop(&mut self) {
+        // Resume the notifications, sending a single notification if the value changed while they were paused
+        let notifications = {
+            let mut cell = self.binding.value.lock().unwrap();
+            cell.paused -= 1;
+
+            if cell.paused == 0 {
+                // Setting the value and then restoring it doesn't send a notification
+                let value_before_pause = cell.value_before_pause.take();
+
+                if value_before_pause.as_ref() != Some(&cell.value) {
+                    cell.get_notifiable_items()
+                } else {
+                    vec![]
+                }
+            } else {
+                vec![]
+            }
+        };
+
+        // Call the notifications outside of the lock
+        let mut needs_filtering = false;
+
+        for to_notify in notifications {
+            needs_filtering = !to_notify.mark_as_changed() || needs_filtering;
+        }
+
+        if needs_filtering {
+            let mut cell = self.binding.value.lock().unwrap();
+            cell.filter_unused_notifications();
+        }
+    }
+}
@@ -1,14 +1,126 @@
 use super::traits::*;
 use super::notify_fn::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
 
-use std::rc::*;
-use std::sync::*;
-use std::cell::*;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeSet};
+#[cfg(any(feature = "std", not(feature = "spin")))]
+use alloc::rc::*;
+#[cfg(any(feature = "std", not(feature = "spin")))]
+use core::cell::*;
+#[cfg(all(feature = "spin", not(feature = "std")))]
+use core::ops::{DerefMut};
 
+#[cfg(any(feature = "std", not(feature = "spin")))]
 thread_local! {
     static CURRENT_CONTEXT: RefCell<Option<BindingContext>> = RefCell::new(None);
 }
 
+///
+/// Without the standard library there are no thread-local variables, so there's a single binding context shared by
+/// every thread, protected by a spin lock. Computed bindings should only be evaluated from a single thread in this
+/// configuration, as a binding read on one thread will be added to a context that was started on another.
+///
+#[cfg(all(feature = "spin", not(feature = "std")))]
+static CURRENT_CONTEXT: spin::Mutex<Option<BindingContext>> = spin::Mutex::new(None);
+
+///
+/// Returns a copy of the binding context for the current thread
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+fn current_context() -> Option<BindingContext> {
+    CURRENT_CONTEXT.with(|current_context| current_context.borrow().as_ref().cloned())
+}
+
+///
+/// Replaces the binding context for the current thread, returning the previous context
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+fn replace_current_context(new_context: Option<BindingContext>) -> Option<BindingContext> {
+    CURRENT_CONTEXT.with(|current_context| current_context.replace(new_context))
+}
+
+///
+/// Returns a copy of the shared binding context
+///
+#[cfg(all(feature = "spin", not(feature = "std")))]
+fn current_context() -> Option<BindingContext> {
+    CURRENT_CONTEXT.lock().as_ref().cloned()
+}
+
+///
+/// Replaces the shared binding context, returning the previous context
+///
+#[cfg(all(feature = "spin", not(feature = "std")))]
+fn replace_current_context(new_context: Option<BindingContext>) -> Option<BindingContext> {
+    core::mem::replace(&mut *CURRENT_CONTEXT.lock(), new_context)
+}
+
+///
+/// Restores a binding context when dropped
+///
+/// Without the standard library, the binding context is not discarded along with a thread that panics, so this makes sure
+/// that a panic while binding does not leave the context in place for the code that runs afterwards.
+///
+struct RestoreContext(Option<Option<BindingContext>>);
+
+impl Drop for RestoreContext {
+    fn drop(&mut self) {
+        if let Some(previous_context) = self.0.take() {
+            replace_current_context(previous_context);
+        }
+    }
+}
+
+///
+/// The lists of dependencies are only used by the thread that created them with the standard library, but without it they
+/// can be used by any thread, as the binding context is shared between threads
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+type DependencyList<TCollection> = Rc<RefCell<TCollection>>;
+
+#[cfg(all(feature = "spin", not(feature = "std")))]
+type DependencyList<TCollection> = Arc<Mutex<TCollection>>;
+
+///
+/// A binding that a context depends on
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+type Dependency = Box<dyn Changeable>;
+
+#[cfg(all(feature = "spin", not(feature = "std")))]
+type Dependency = Box<dyn Changeable+Send>;
+
+///
+/// Creates a new, empty, list of dependencies
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+fn new_dependency_list<TCollection: Default>() -> DependencyList<TCollection> {
+    Rc::new(RefCell::new(TCollection::default()))
+}
+
+#[cfg(all(feature = "spin", not(feature = "std")))]
+fn new_dependency_list<TCollection: Default>() -> DependencyList<TCollection> {
+    Arc::new(Mutex::new(TCollection::default()))
+}
+
+///
+/// Borrows the contents of a list of dependencies
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+fn borrow_dependency_list<TCollection>(list: &DependencyList<TCollection>) -> RefMut<'_, TCollection> {
+    list.borrow_mut()
+}
+
+#[cfg(all(feature = "spin", not(feature = "std")))]
+fn borrow_dependency_list<TCollection>(list: &DependencyList<TCollection>) -> impl '_+DerefMut<Target=TCollection> {
+    list.lock().unwrap()
+}
+
 ///
 /// Represents the dependencies of a binding context
 ///
@@ -18,10 +130,13 @@ pub struct BindingDependencies {
     recently_changed: Arc<Mutex<bool>>,
 
     /// The when_changed monitors for the recently_changed flag
-    recent_change_monitors: Rc<RefCell<Vec<Box<dyn Releasable>>>>,
+    recent_change_monitors: DependencyList<Vec<Box<dyn Releasable>>>,
 
     /// The list of changables that are dependent on this context
-    dependencies: Rc<RefCell<Vec<Box<dyn Changeable>>>>
+    dependencies: DependencyList<Vec<Dependency>>,
+
+    /// The identities of the dependencies that have been added, so each one is only subscribed to once
+    dependency_identities: DependencyList<BTreeSet<usize>>
 }
 
 impl BindingDependencies {
@@ -31,22 +146,64 @@ impl BindingDependencies {
     pub fn new() -> BindingDependencies {
         BindingDependencies {
             recently_changed:       Arc::new(Mutex::new(false)),
-            recent_change_monitors: Rc::new(RefCell::new(vec![])),
-            dependencies:           Rc::new(RefCell::new(vec![]))
+            recent_change_monitors: new_dependency_list(),
+            dependencies:           new_dependency_list(),
+            dependency_identities:  new_dependency_list()
         }
     }
 
     ///
     /// Adds a new dependency to this object
     ///
+    #[cfg(any(feature = "std", not(feature = "spin")))]
     pub fn add_dependency<TChangeable: Changeable+'static>(&mut self, dependency: TChangeable) {
+        self.add_boxed_dependency(Box::new(dependency))
+    }
+
+    ///
+    /// Adds a new dependency to this object
+    ///
+    /// Without the standard library, the binding context is shared between threads, so its dependencies must be `Send`
+    ///
+    #[cfg(all(feature = "spin", not(feature = "std")))]
+    pub fn add_dependency<TChangeable: Changeable+Send+'static>(&mut self, dependency: TChangeable) {
+        self.add_boxed_dependency(Box::new(dependency))
+    }
+
+    ///
+    /// Adds a dependency that has been boxed to this object
+    ///
+    fn add_boxed_dependency(&mut self, dependency: Dependency) {
+        // Bindings that are read more than once only need to be added once
+        if let Some(identity) = dependency.identity() {
+            if !borrow_dependency_list(&self.dependency_identities).insert(identity) {
+                return;
+            }
+        }
+
         // Set the recently changed flag so that we can tell if the dependencies are already out of date before when_changed is called
         let recently_changed            = Arc::clone(&self.recently_changed);
-        let mut recent_change_monitors  = self.recent_change_monitors.borrow_mut();
-        recent_change_monitors.push(dependency.when_changed(notify(move || { *recently_changed.lock().unwrap() = true; })));
+        let recent_change_monitor       = dependency.when_changed(notify(move || { *recently_changed.lock().unwrap() = true; }));
+        borrow_dependency_list(&self.recent_change_monitors).push(recent_change_monitor);
 
         // Add this dependency to the list
-        self.dependencies.borrow_mut().push(Box::new(dependency))
+        borrow_dependency_list(&self.dependencies).push(dependency)
+    }
+
+    ///
+    /// Returns the IDs of the dependencies in this object, in the order they were added
+    ///
+    #[cfg(feature = "graph")]
+    pub fn dependency_ids(&self) -> Vec<BindingId> {
+        let mut ids = vec![];
+
+        for id in borrow_dependency_list(&self.dependencies).iter().filter_map(|dependency| dependency.binding_id()) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        ids
     }
 
     ///
@@ -57,7 +214,7 @@ impl BindingDependencies {
         let mut to_release = vec![];
 
         // Register with all of the dependencies
-        for dep in self.dependencies.borrow_mut().iter_mut() {
+        for dep in borrow_dependency_list(&self.dependencies).iter_mut() {
             to_release.push(dep.when_changed(Arc::clone(&what)));
         }
 
@@ -107,19 +264,14 @@ impl BindingContext {
     /// Gets the active binding context
     ///
     pub fn current() -> Option<BindingContext> {
-        CURRENT_CONTEXT.with(|current_context| {
-            current_context
-                .borrow()
-                .as_ref()
-                .cloned()
-        })
+        current_context()
     }
 
     ///
     /// Panics if we're trying to create a binding, with a particular message
     /// 
     pub fn panic_if_in_binding_context(msg: &str) {
-        if CURRENT_CONTEXT.with(|context| context.borrow().is_some()) {
+        if current_context().is_some() {
             panic!("Not possible when binding: {}", msg);
         }
     }
@@ -139,15 +291,13 @@ impl BindingContext {
             _nested:        previous_context.clone().map(Box::new)
         };
 
-        // Make the current context the same as the new context
-        CURRENT_CONTEXT.with(|current_context| *current_context.borrow_mut() = Some(new_context));
+        // Make the current context the same as the new context (resetting to the previous context afterwards, even if the action panics)
+        replace_current_context(Some(new_context));
+        let _restore = RestoreContext(Some(previous_context));
 
         // Perform the requested action with this context
         let result = to_do();
 
-        // Reset to the previous context
-        CURRENT_CONTEXT.with(|current_context| *current_context.borrow_mut() = previous_context);
-
         (result, dependencies)
     }
 
@@ -160,22 +310,29 @@ impl BindingContext {
         // Remember the previous context
         let previous_context = Self::current();
 
-        // Unset the context
-        CURRENT_CONTEXT.with(|current_context| *current_context.borrow_mut() = None);
+        // Unset the context (resetting to the previous context afterwards, even if the action panics)
+        replace_current_context(None);
+        let _restore = RestoreContext(Some(previous_context));
 
         // Perform the operations without a binding context
-        let result = to_do();
-
-        // Reset to the previous context
-        CURRENT_CONTEXT.with(|current_context| *current_context.borrow_mut() = previous_context);
-
-        result
+        to_do()
     }
 
     ///
     /// Adds a dependency to the current context (if one is found)
     /// 
+    #[cfg(any(feature = "std", not(feature = "spin")))]
     pub fn add_dependency<TChangeable: Changeable+'static>(dependency: TChangeable) {
         Self::current().map(|mut ctx| ctx.dependencies.add_dependency(dependency));
     }
+
+    ///
+    /// Adds a dependency to the current context (if one is found)
+    ///
+    /// Without the standard library, the binding context is shared between threads, so its dependencies must be `Send`
+    /// 
+    #[cfg(all(feature = "spin", not(feature = "std")))]
+    pub fn add_dependency<TChangeable: Changeable+Send+'static>(dependency: TChangeable) {
+        Self::current().map(|mut ctx| ctx.dependencies.add_dependency(dependency));
+    }
 }
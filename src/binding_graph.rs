@@ -0,0 +1,233 @@
+//!
+//! Support for capturing the graph of dependencies between bindings, for diagnostic purposes
+//!
+//! This is only available with the `graph` feature. Every binding is assigned a `BindingId`, and computed bindings
+//! record the IDs of the bindings they read each time they are calculated. `BindingGraph::capture()` can then be used
+//! to read the dependencies of every computed binding that currently exists.
+//!
+
+use std::fmt::Write;
+use std::sync::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::{HashSet};
+
+///
+/// A unique identifier for a binding
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BindingId(u64);
+
+impl BindingId {
+    ///
+    /// Assigns a new binding ID
+    ///
+    pub (crate) fn new() -> BindingId {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        BindingId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    ///
+    /// Returns the number used to represent this ID
+    ///
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+///
+/// The kinds of binding that can appear in a binding graph
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BindingKind {
+    /// A binding that other bindings depend on, but which does not have dependencies of its own (such as a `Binding`)
+    Source,
+
+    /// A computed binding
+    Computed,
+}
+
+///
+/// Describes a single binding in a binding graph
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct BindingGraphNode {
+    /// The ID of this binding
+    pub id: BindingId,
+
+    /// What kind of binding this is
+    pub kind: BindingKind,
+
+    /// The name of the type of value stored in this binding, if known
+    pub value_type: Option<&'static str>,
+
+    /// The bindings that this binding read the last time it was calculated
+    pub dependencies: Vec<BindingId>,
+}
+
+///
+/// Trait implemented by the bindings that are stored in the graph registry
+///
+pub (crate) trait GraphNode : Send+Sync {
+    ///
+    /// Describes this binding
+    ///
+    fn graph_node(&self) -> BindingGraphNode;
+}
+
+/// The bindings that can appear in a binding graph
+static REGISTRY: Mutex<Vec<Weak<dyn GraphNode>>> = Mutex::new(Vec::new());
+
+///
+/// Adds a binding to the registry used to capture binding graphs
+///
+pub (crate) fn register_graph_node(node: Weak<dyn GraphNode>) {
+    let mut registry = REGISTRY.lock().unwrap();
+
+    // Tidy up any bindings that have been dropped before the registry needs to grow
+    if registry.len() == registry.capacity() {
+        registry.retain(|node| node.strong_count() > 0);
+    }
+
+    registry.push(node);
+}
+
+///
+/// A snapshot of the dependencies between the bindings in a program
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct BindingGraph {
+    /// The bindings in this graph
+    pub nodes: Vec<BindingGraphNode>,
+}
+
+impl BindingGraph {
+    ///
+    /// Captures the current dependencies of every computed binding, along with the bindings they depend on
+    ///
+    /// Computed bindings only know their dependencies once they've been calculated, so a binding that has never been
+    /// read will appear without any dependencies. This must not be called from within the calculation of a computed
+    /// binding.
+    ///
+    pub fn capture() -> BindingGraph {
+        // Read the bindings that are still alive from the registry
+        let live_nodes = {
+            let mut registry = REGISTRY.lock().unwrap();
+            registry.retain(|node| node.strong_count() > 0);
+
+            registry.iter()
+                .filter_map(|node| node.upgrade())
+                .collect::<Vec<_>>()
+        };
+
+        // Describe each binding outside of the registry lock
+        let mut nodes   = live_nodes.iter().map(|node| node.graph_node()).collect::<Vec<_>>();
+        nodes.sort_by_key(|node| node.id);
+
+        // Add nodes for any dependencies that are not themselves in the registry
+        let known       = nodes.iter().map(|node| node.id).collect::<HashSet<_>>();
+        let mut sources = nodes.iter()
+            .flat_map(|node| node.dependencies.iter().copied())
+            .filter(|id| !known.contains(id))
+            .collect::<Vec<_>>();
+        sources.sort();
+        sources.dedup();
+
+        nodes.extend(sources.into_iter().map(|id| BindingGraphNode { id, kind: BindingKind::Source, value_type: None, dependencies: vec![] }));
+
+        BindingGraph { nodes }
+    }
+
+    ///
+    /// Returns the edges in this graph, as pairs of (dependency, dependent binding)
+    ///
+    pub fn edges(&self) -> impl '_+Iterator<Item=(BindingId, BindingId)> {
+        self.nodes.iter()
+            .flat_map(|node| node.dependencies.iter().map(move |dependency| (*dependency, node.id)))
+    }
+
+    ///
+    /// Formats this graph in the DOT language used by graphviz
+    ///
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+
+        writeln!(dot, "digraph bindings {{").unwrap();
+
+        for node in self.nodes.iter() {
+            let shape       = match node.kind {
+                BindingKind::Source     => "box",
+                BindingKind::Computed   => "ellipse",
+            };
+            let value_type  = node.value_type.unwrap_or("");
+
+            writeln!(dot, "    b{} [label=\"{}: {}\", shape={}];", node.id.0, node.id.0, value_type.replace('"', "\\\""), shape).unwrap();
+        }
+
+        for (dependency, dependent) in self.edges() {
+            writeln!(dot, "    b{} -> b{};", dependency.0, dependent.0).unwrap();
+        }
+
+        writeln!(dot, "}}").unwrap();
+
+        dot
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn capture_computed_dependencies() {
+        let a       = bind(1);
+        let b       = bind(2);
+
+        let a_copy  = a.clone();
+        let b_copy  = b.clone();
+        let sum     = computed(move || a_copy.get() + b_copy.get());
+
+        let sum_copy    = sum.clone();
+        let doubled     = computed(move || sum_copy.get() * 2);
+
+        assert!(doubled.get() == 6);
+
+        let a_id        = a.binding_id().unwrap();
+        let b_id        = b.binding_id().unwrap();
+        let sum_id      = sum.binding_id().unwrap();
+        let doubled_id  = doubled.binding_id().unwrap();
+
+        let graph       = BindingGraph::capture();
+        let sum_node    = graph.nodes.iter().find(|node| node.id == sum_id).unwrap();
+        let double_node = graph.nodes.iter().find(|node| node.id == doubled_id).unwrap();
+        let a_node      = graph.nodes.iter().find(|node| node.id == a_id).unwrap();
+
+        assert!(sum_node.kind == BindingKind::Computed);
+        assert!(sum_node.value_type == Some("i32"));
+        assert!(sum_node.dependencies == vec![a_id, b_id]);
+        assert!(double_node.dependencies == vec![sum_id]);
+        assert!(a_node.kind == BindingKind::Source);
+
+        let edges = graph.edges().collect::<Vec<_>>();
+        assert!(edges.contains(&(a_id, sum_id)));
+        assert!(edges.contains(&(sum_id, doubled_id)));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains(&format!("b{} -> b{};", sum_id.as_u64(), doubled_id.as_u64())));
+    }
+
+    #[test]
+    fn dropped_bindings_leave_the_graph() {
+        let a       = bind(1);
+        let a_copy  = a.clone();
+        let plus_one = computed(move || a_copy.get() + 1);
+        plus_one.get();
+
+        let id      = plus_one.binding_id().unwrap();
+        assert!(BindingGraph::capture().nodes.iter().any(|node| node.id == id));
+
+        drop(plus_one);
+        assert!(!BindingGraph::capture().nodes.iter().any(|node| node.id == id));
+    }
+}
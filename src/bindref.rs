@@ -1,10 +1,15 @@
 use super::traits::*;
 use super::binding::*;
 use super::computed::*;
+use super::binding_context::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
 #[cfg(feature = "stream")]
 use super::bind_stream::*;
 
-use std::sync::*;
+use alloc::boxed::Box;
+use core::ops::{Add, Sub, Mul};
 
 ///
 /// A `BindRef` references another binding without needing to know precisely
@@ -14,6 +19,9 @@ use std::sync::*;
 /// 
 /// Cloning a `BindRef` will create another reference to the same binding.
 /// 
+/// `BindRef` is always `Send` and `Sync`, as the `Bound` trait requires these of
+/// every binding it can refer to, so it can be shared freely between threads.
+/// 
 pub struct BindRef<Target> {
     reference: Arc<dyn Bound<Target>>
 }
@@ -30,6 +38,22 @@ impl<Value> Changeable for BindRef<Value> {
     fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
         self.reference.when_changed(what)
     }
+
+    #[inline]
+    fn release_unused_notifications(&self) {
+        self.reference.release_unused_notifications()
+    }
+
+    #[inline]
+    fn identity(&self) -> Option<usize> {
+        self.reference.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    #[inline]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.reference.binding_id()
+    }
 }
 
 impl<Value> Clone for BindRef<Value> {
@@ -62,6 +86,46 @@ impl<Value> BindRef<Value> {
     }
 }
 
+impl<Value: 'static> BindRef<Value> {
+    ///
+    /// Creates a new BindRef to a binding that is created by a factory function the first time it's used
+    ///
+    /// The factory is called when the value is first read or something first subscribes to it, which makes it
+    /// possible to reference a binding that doesn't exist yet: for example, when two parts of an application need
+    /// to refer to each other's bindings while they are being constructed. The factory is called exactly once,
+    /// even if the binding is first used from several threads at once, and must not use the binding it's creating.
+    ///
+    pub fn lazy<TFactoryFn>(factory: TFactoryFn) -> BindRef<Value>
+    where TFactoryFn: 'static+Send+FnOnce() -> BindRef<Value> {
+        let lazy = LazyBinding {
+            state: Mutex::new(LazyState {
+                factory: Some(Box::new(factory)),
+                binding: None,
+            })
+        };
+
+        BindRef {
+            reference: Arc::new(lazy)
+        }
+    }
+}
+
+impl<Value: 'static+Clone+Send> BindRef<Value> {
+    ///
+    /// Creates a new BindRef that computes its value using a function
+    ///
+    /// This is the same as `BindRef::from(computed(calculate_value))`: any bindings accessed by the function are
+    /// tracked as dependencies, and the value is recalculated when they change.
+    ///
+    #[inline]
+    pub fn from_fn<TFn>(calculate_value: TFn) -> BindRef<Value>
+    where TFn: 'static+Send+Sync+Fn() -> Value {
+        BindRef {
+            reference: Arc::new(ComputedBinding::new(calculate_value))
+        }
+    }
+}
+
 impl<'a, Value> From<&'a BindRef<Value>> for BindRef<Value> {
     #[inline]
     fn from(val: &'a BindRef<Value>) -> Self {
@@ -125,10 +189,120 @@ impl<Value: 'static+Clone+Send+PartialEq> From<StreamBinding<Value>> for BindRef
     }
 }
 
+///
+/// The state of a binding created by `BindRef::lazy()`
+///
+struct LazyState<Value> {
+    /// The function that creates the binding, if it hasn't been called yet
+    factory: Option<Box<dyn Send+FnOnce() -> BindRef<Value>>>,
+
+    /// The binding, once it has been created
+    binding: Option<BindRef<Value>>,
+}
+
+///
+/// A binding that calls a factory function to create the binding it refers to the first time it's used
+///
+struct LazyBinding<Value> {
+    state: Mutex<LazyState<Value>>,
+}
+
+impl<Value> LazyBinding<Value> {
+    ///
+    /// Returns the binding, calling the factory function to create it if it hasn't been created yet
+    ///
+    fn binding(&self) -> BindRef<Value> {
+        // The state stays locked while the factory runs, so a concurrent caller waits for the binding rather than creating another
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(binding) = &state.binding {
+            return binding.clone();
+        }
+
+        // Bindings created by the factory are not dependencies of any computed binding that is reading this one
+        let factory = state.factory.take().expect("Lazy binding factory should only be called once");
+        let binding = BindingContext::out_of_context(factory);
+
+        state.binding = Some(binding.clone());
+        binding
+    }
+}
+
+impl<Value> Changeable for LazyBinding<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.binding().when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        // Nothing can have subscribed if the binding hasn't been created yet
+        let binding = self.state.lock().unwrap().binding.clone();
+
+        if let Some(binding) = binding {
+            binding.release_unused_notifications();
+        }
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        let binding = self.state.lock().unwrap().binding.clone();
+
+        binding.and_then(|binding| binding.binding_id())
+    }
+}
+
+impl<Value> Bound<Value> for LazyBinding<Value> {
+    fn get(&self) -> Value {
+        self.binding().get()
+    }
+}
+
+///
+/// Implements an arithmetic operator for `BindRef`, producing a computed binding that applies the operator
+/// to the current values of both operands.
+///
+/// Operators are implemented for both owned and borrowed `BindRef`s: the owned form consumes its operands,
+/// and the borrowed form clones them (which is cheap, as only the reference is copied). Use `&a + &b` to
+/// keep using the operands after building the expression.
+///
+macro_rules! bind_ref_operator {
+    ($op_trait: ident, $op_fn: ident) => {
+        impl<Value> $op_trait<BindRef<Value>> for BindRef<Value>
+        where
+            Value:          'static + $op_trait<Value>,
+            Value::Output:  'static + Clone + Send,
+        {
+            type Output = BindRef<Value::Output>;
+
+            fn $op_fn(self, rhs: BindRef<Value>) -> BindRef<Value::Output> {
+                BindRef::from_fn(move || self.get().$op_fn(rhs.get()))
+            }
+        }
+
+        impl<'a, Value> $op_trait<&'a BindRef<Value>> for &'a BindRef<Value>
+        where
+            Value:          'static + $op_trait<Value>,
+            Value::Output:  'static + Clone + Send,
+        {
+            type Output = BindRef<Value::Output>;
+
+            #[inline]
+            fn $op_fn(self, rhs: &'a BindRef<Value>) -> BindRef<Value::Output> {
+                self.clone().$op_fn(rhs.clone())
+            }
+        }
+    }
+}
+
+bind_ref_operator!(Add, add);
+bind_ref_operator!(Sub, sub);
+bind_ref_operator!(Mul, mul);
+
 #[cfg(test)]
 mod test {
     use super::super::*;
 
+    use alloc::vec::Vec;
+
     #[test]
     fn bindref_matches_core_value() {
         let bind        = bind(1);
@@ -155,6 +329,82 @@ mod test {
         assert!(bind_ref.get() == 1);
     }
 
+    #[test]
+    fn bind_ref_from_fn() {
+        let bind        = bind(1);
+        let bind_copy   = bind.clone();
+        let bind_ref    = BindRef::from_fn(move || bind_copy.get() + 1);
+
+        assert!(bind_ref.get() == 2);
+
+        bind.set(2);
+
+        assert!(bind_ref.get() == 3);
+    }
+
+    #[test]
+    fn lazy_bind_ref_created_on_first_use() {
+        let created     = bind(false);
+        let value       = bind(1);
+
+        let factory_created = created.clone();
+        let factory_value   = value.clone();
+        let lazy            = BindRef::lazy(move || {
+            factory_created.set(true);
+            BindRef::new(&factory_value)
+        });
+
+        assert!(!created.get());
+
+        assert!(lazy.get() == 1);
+        assert!(created.get());
+
+        value.set(2);
+        assert!(lazy.get() == 2);
+    }
+
+    #[test]
+    fn lazy_bind_ref_notifies_changes() {
+        let value       = bind(1);
+        let value_copy  = value.clone();
+        let lazy        = BindRef::lazy(move || BindRef::new(&value_copy));
+        let doubled     = computed(move || lazy.get() * 2);
+        let changed     = bind(false);
+
+        let notify_changed = changed.clone();
+        assert!(doubled.get() == 2);
+        doubled.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        value.set(2);
+        assert!(changed.get());
+        assert!(doubled.get() == 4);
+    }
+
+    #[test]
+    fn lazy_factory_runs_once() {
+        let calls       = bind(0);
+        let calls_copy  = calls.clone();
+        let lazy        = BindRef::lazy(move || {
+            calls_copy.set(calls_copy.get() + 1);
+            BindRef::from(bind(1))
+        });
+
+        let threads = (0..4).map(|_| {
+            let lazy = lazy.clone();
+            std::thread::spawn(move || lazy.get())
+        }).collect::<Vec<_>>();
+
+        threads.into_iter().for_each(|thread| { assert!(thread.join().unwrap() == 1); });
+        assert!(calls.get() == 1);
+    }
+
+    #[test]
+    fn bind_ref_type_name() {
+        let bind_ref    = BindRef::from(bind(1u32));
+
+        assert!(bind_ref.type_name() == "u32");
+    }
+
     #[test]
     fn bindref_matches_core_value_when_created_from_ref() {
         let bind        = bind(1);
@@ -166,4 +416,51 @@ mod test {
 
         assert!(bind_ref.get() == 2);
     }
+
+    #[test]
+    fn add_bind_refs() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let sum     = BindRef::new(&a) + BindRef::new(&b);
+
+        assert!(sum.get() == 3);
+
+        a.set(3);
+        assert!(sum.get() == 5);
+
+        b.set(4);
+        assert!(sum.get() == 7);
+    }
+
+    #[test]
+    fn borrowed_operators_keep_operands() {
+        let a       = BindRef::from(bind(6));
+        let b       = BindRef::from(bind(2));
+
+        let sum     = &a + &b;
+        let diff    = &a - &b;
+        let product = &a * &b;
+
+        assert!(sum.get() == 8);
+        assert!(diff.get() == 4);
+        assert!(product.get() == 12);
+        assert!(a.get() == 6);
+    }
+
+    #[test]
+    fn operator_binding_notifies_on_change() {
+        let a           = bind(2);
+        let b           = bind(3);
+        let product     = BindRef::new(&a) * BindRef::new(&b);
+        let changed     = bind(false);
+
+        let notify_changed = changed.clone();
+        product.get();
+        product.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        assert!(!changed.get());
+        b.set(4);
+        assert!(changed.get());
+        assert!(product.get() == 8);
+    }
 }
@@ -0,0 +1,242 @@
+use super::traits::*;
+use super::bindref::*;
+use super::notify_fn::*;
+use super::releasable::*;
+use super::binding_context::*;
+
+use std::mem;
+use std::sync::*;
+
+///
+/// The shared state of a changed flag
+///
+struct ChangedFlagCore {
+    /// True if one of the bindings has changed since the flag was last read
+    changed: bool,
+
+    /// What to call when the flag is set
+    when_changed: Vec<ReleasableNotifiable>
+}
+
+impl ChangedFlagCore {
+    ///
+    /// Sets the flag, notifying anything that's listening if it was previously unset
+    ///
+    fn set_changed(core: &Mutex<ChangedFlagCore>) {
+        let notifications = {
+            let mut core = core.lock().unwrap();
+
+            if !core.changed {
+                core.changed = true;
+                core.when_changed.retain(|releasable| releasable.is_in_use());
+                core.when_changed.iter().map(|item| item.clone_for_inspection()).collect()
+            } else {
+                vec![]
+            }
+        };
+
+        // Call the notifications outside of the lock
+        for to_notify in notifications {
+            to_notify.mark_as_changed();
+        }
+    }
+}
+
+///
+/// A binding that can be monitored by a changed flag, whatever the type of its value
+///
+pub trait ChangedFlagSource : Changeable+Send+Sync {
+    ///
+    /// Reads the binding so that it will notify again the next time it changes (computed bindings only notify after being read)
+    ///
+    fn read_to_rearm(&self);
+}
+
+impl<Value> ChangedFlagSource for BindRef<Value> {
+    fn read_to_rearm(&self) {
+        self.get();
+    }
+}
+
+///
+/// A binding that is set to true when any of a group of bindings change, and is reset to false when it is read
+///
+/// This works like a latch: it's intended for situations like a render loop, which can read the flag once per frame
+/// to decide whether or not anything needs to be redrawn.
+///
+pub struct ChangedFlag {
+    /// The state of this flag
+    core: Arc<Mutex<ChangedFlagCore>>,
+
+    /// The bindings that are being monitored
+    bindings: Arc<Vec<Box<dyn ChangedFlagSource>>>,
+
+    /// The notifications for the monitored bindings (released when the last copy of the flag is dropped)
+    _monitors: Arc<Mutex<Vec<Box<dyn Releasable>>>>
+}
+
+impl ChangedFlag {
+    ///
+    /// Creates a new changed flag that monitors the specified bindings
+    ///
+    pub fn new(bindings: Vec<Box<dyn ChangedFlagSource>>) -> ChangedFlag {
+        let core = ChangedFlagCore {
+            changed:        false,
+            when_changed:   vec![]
+        };
+        let core = Arc::new(Mutex::new(core));
+
+        // Set the flag whenever any of the bindings change
+        let monitors = bindings.iter()
+            .map(|binding| {
+                let core = Arc::downgrade(&core);
+
+                binding.when_changed(notify(move || {
+                    if let Some(core) = core.upgrade() {
+                        ChangedFlagCore::set_changed(&core);
+                    }
+                }))
+            })
+            .collect::<Vec<_>>();
+
+        ChangedFlag {
+            core,
+            bindings:   Arc::new(bindings),
+            _monitors:  Arc::new(Mutex::new(monitors))
+        }
+    }
+}
+
+impl Clone for ChangedFlag {
+    fn clone(&self) -> Self {
+        ChangedFlag {
+            core:       Arc::clone(&self.core),
+            bindings:   Arc::clone(&self.bindings),
+            _monitors:  Arc::clone(&self._monitors)
+        }
+    }
+}
+
+impl Changeable for ChangedFlag {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        let releasable = ReleasableNotifiable::new(what);
+
+        let mut core = self.core.lock().unwrap();
+        core.when_changed.push(releasable.clone_as_owned());
+        core.when_changed.retain(|releasable| releasable.is_in_use());
+
+        Box::new(releasable)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.core.lock().unwrap().when_changed.retain(|releasable| releasable.is_in_use());
+    }
+}
+
+impl Bound<bool> for ChangedFlag {
+    ///
+    /// Returns whether or not any of the bindings have changed since the flag was last read, and resets the flag
+    ///
+    fn get(&self) -> bool {
+        BindingContext::add_dependency(self.clone());
+
+        // Read the bindings so that they will notify again when they are next changed (computed bindings only notify after being read)
+        BindingContext::out_of_context(|| {
+            self.bindings.iter().for_each(|binding| binding.read_to_rearm());
+        });
+
+        // Read and reset the flag
+        let mut core = self.core.lock().unwrap();
+        mem::take(&mut core.changed)
+    }
+}
+
+///
+/// Creates a binding that becomes true when any of the specified bindings change, and is reset to false when read
+///
+/// The bindings can have different types of value, for example to make a 'dirty' flag for all of the bindings in a model:
+/// `any_changed_flag(vec![Box::new(BindRef::new(&name)), Box::new(BindRef::new(&enabled))])`.
+///
+pub fn any_changed_flag(bindings: Vec<Box<dyn ChangedFlagSource>>) -> BindRef<bool> {
+    BindRef::from_arc(Arc::new(ChangedFlag::new(bindings)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn flag_is_initially_unset() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let flag    = any_changed_flag(vec![Box::new(BindRef::new(&a)), Box::new(BindRef::new(&b))]);
+
+        assert!(!flag.get());
+    }
+
+    #[test]
+    fn flag_is_set_on_change_and_reset_on_read() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let flag    = any_changed_flag(vec![Box::new(BindRef::new(&a)), Box::new(BindRef::new(&b))]);
+
+        a.set(3);
+        assert!(flag.get());
+        assert!(!flag.get());
+
+        b.set(4);
+        b.set(5);
+        assert!(flag.get());
+        assert!(!flag.get());
+    }
+
+    #[test]
+    fn flag_monitors_computed_bindings() {
+        let a           = bind(1);
+        let a_copy      = a.clone();
+        let doubled_a   = computed(move || a_copy.get() * 2);
+        let flag        = any_changed_flag(vec![Box::new(BindRef::from(doubled_a))]);
+
+        assert!(!flag.get());
+
+        a.set(2);
+        assert!(flag.get());
+        assert!(!flag.get());
+
+        a.set(3);
+        assert!(flag.get());
+    }
+
+    #[test]
+    fn flag_notifies_when_set() {
+        let a               = bind(1);
+        let flag            = any_changed_flag(vec![Box::new(BindRef::new(&a))]);
+        let notified        = bind(false);
+
+        let notify_flag     = notified.clone();
+        flag.when_changed(notify(move || notify_flag.set(true))).keep_alive();
+
+        a.set(2);
+        assert!(notified.get());
+    }
+
+    #[test]
+    fn flag_monitors_bindings_of_different_types() {
+        let enabled     = bind(false);
+        let name        = bind("Alice".to_string());
+        let items       = bind(vec![1, 2, 3]);
+        let flag        = any_changed_flag(vec![Box::new(BindRef::new(&enabled)), Box::new(BindRef::new(&name)), Box::new(BindRef::new(&items))]);
+
+        assert!(!flag.get());
+
+        name.set("Bob".to_string());
+        assert!(flag.get());
+        assert!(!flag.get());
+
+        items.set(vec![4]);
+        enabled.set(true);
+        assert!(flag.get());
+        assert!(!flag.get());
+    }
+}
@@ -0,0 +1,114 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::sync::*;
+
+///
+/// Returns the indices of the elements that are different between two versions of a list
+///
+/// Elements are compared by position, so if the lists have different lengths, every index past the end of the shorter
+/// list is included.
+///
+fn find_changed_indices<Value: PartialEq>(old_values: &[Value], new_values: &[Value]) -> Vec<usize> {
+    let max_len = old_values.len().max(new_values.len());
+
+    (0..max_len)
+        .filter(|idx| old_values.get(*idx) != new_values.get(*idx))
+        .collect()
+}
+
+///
+/// Creates a binding to the indices of the elements that were changed by the most recent change to a list binding
+///
+/// The old and new values of the list are compared element by element, so inserting or removing an element will mark
+/// every element after it as changed. This is useful for working out which items to redraw when a list changes. The
+/// binding starts as an empty list, and notifies every time the source list changes, even if the same indices changed
+/// as the last time.
+///
+pub fn changed_indices<Value>(binding: BindRef<Vec<Value>>) -> BindRef<Vec<usize>>
+where
+Value: 'static+Clone+Send+PartialEq {
+    // The generation makes every change distinct, so changing the same elements twice still notifies
+    let previous_values = Mutex::new(binding.get());
+    let last_change     = Binding::new((0u64, vec![]));
+
+    // Compare with the previous value whenever the binding changes
+    let change_copy     = last_change.clone();
+    bind_to_source(binding, move |new_values: Vec<Value>| {
+        let changed         = {
+            let mut previous_values = previous_values.lock().unwrap();
+            let changed             = find_changed_indices(&previous_values, &new_values);
+
+            *previous_values = new_values;
+            changed
+        };
+
+        // Update the change binding outside of the lock (if nothing changed, the previous change is left in place)
+        if !changed.is_empty() {
+            change_copy.with_mut(|(generation, indices)| {
+                *generation += 1;
+                *indices     = changed;
+                true
+            });
+        }
+    }, move || last_change.get().1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn no_changes_initially() {
+        let list    = bind(vec![1, 2, 3]);
+        let changed = changed_indices(BindRef::new(&list));
+
+        assert!(changed.get().is_empty());
+    }
+
+    #[test]
+    fn finds_changed_elements() {
+        let list    = bind(vec![1, 2, 3]);
+        let changed = changed_indices(BindRef::new(&list));
+
+        list.set(vec![1, 5, 3]);
+        assert!(changed.get() == vec![1]);
+
+        list.set(vec![4, 5, 6]);
+        assert!(changed.get() == vec![0, 2]);
+    }
+
+    #[test]
+    fn length_changes_are_included() {
+        let list    = bind(vec![1, 2, 3]);
+        let changed = changed_indices(BindRef::new(&list));
+
+        list.set(vec![1, 2, 3, 4, 5]);
+        assert!(changed.get() == vec![3, 4]);
+
+        list.set(vec![1]);
+        assert!(changed.get() == vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn notifies_when_same_element_changes_twice() {
+        let list            = bind(vec![1, 2, 3]);
+        let changed         = changed_indices(BindRef::new(&list));
+        let notified        = bind(0);
+
+        let notify_count    = notified.clone();
+        assert!(changed.get().is_empty());
+        changed.when_changed(notify(move || notify_count.set(notify_count.get() + 1))).keep_alive();
+
+        list.set(vec![1, 4, 3]);
+        assert!(changed.get() == vec![1]);
+
+        list.set(vec![1, 5, 3]);
+        assert!(changed.get() == vec![1]);
+
+        assert!(notified.get() == 2);
+    }
+}
@@ -0,0 +1,72 @@
+use super::traits::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::mem;
+
+///
+/// Creates a binding to the most recent change made to another binding, as a pair of the old and new values
+///
+/// The binding is `None` until the source binding changes for the first time, and is then set to `Some((old_value, new_value))`
+/// every time the source binding changes. This makes it possible to follow the changes to a binding through the binding
+/// interface itself, which is useful for things like audit logs or debugging.
+///
+pub fn changes_binding<Value>(binding: BindRef<Value>) -> BindRef<Option<(Value, Value)>>
+where 
+Value: 'static+Clone+Send+PartialEq {
+    // The previous value is updated every time the source binding changes, and the change is recorded with it
+    let previous_value = binding.get();
+
+    bind_to_source_with_state(binding, previous_value, None, |previous_value, new_value: Value| {
+        if *previous_value == new_value {
+            // The value has not actually changed
+            None
+        } else {
+            let old_value = mem::replace(previous_value, new_value.clone());
+            Some(Some((old_value, new_value)))
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn no_change_initially() {
+        let a       = bind(5);
+        let changes = changes_binding(BindRef::new(&a));
+
+        assert!(changes.get().is_none());
+    }
+
+    #[test]
+    fn records_old_and_new_values() {
+        let a       = bind(5);
+        let changes = changes_binding(BindRef::new(&a));
+
+        a.set(8);
+        assert!(changes.get() == Some((5, 8)));
+
+        a.set(6);
+        assert!(changes.get() == Some((8, 6)));
+
+        a.set(6);
+        assert!(changes.get() == Some((8, 6)));
+    }
+
+    #[test]
+    fn follows_computed_bindings() {
+        let a       = bind(1);
+        let a_copy  = a.clone();
+        let doubled = computed(move || a_copy.get() * 2);
+        let changes = changes_binding(BindRef::from(doubled));
+
+        a.set(2);
+        assert!(changes.get() == Some((2, 4)));
+
+        a.set(3);
+        assert!(changes.get() == Some((4, 6)));
+    }
+}
@@ -1,9 +1,14 @@
 use super::traits::*;
 use super::releasable::*;
 use super::binding_context::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
 
-use std::sync::*;
-use std::mem;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::boxed::Box;
+use core::mem;
 
 ///
 /// Represents a computed value
@@ -31,7 +36,21 @@ where TFn: 'static+Fn() -> Value {
     existing_notification: Option<Box<dyn Releasable>>,
 
     /// What to call when the value changes
-    when_changed: Vec<ReleasableNotifiable>
+    when_changed: Vec<ReleasableNotifiable>,
+
+    /// If true, the dependencies captured by the first calculation are kept for the lifetime of the binding
+    static_dependencies: bool,
+
+    /// For bindings that only notify when their value is different, compares a new value to the cached value
+    is_same: Option<fn(&Value, &Value) -> bool>,
+
+    /// The ID of this binding in the binding graph
+    #[cfg(feature = "graph")]
+    id: BindingId,
+
+    /// The IDs of the bindings that were read by the most recent calculation that updated the dependencies
+    #[cfg(feature = "graph")]
+    dependency_ids: Vec<BindingId>
 }
 
 impl<Value: 'static+Clone, TFn> ComputedBindingCore<Value, TFn>
@@ -44,7 +63,13 @@ where TFn: 'static+Fn() -> Value {
             calculate_value:        calculate_value,
             latest_value:           Unknown,
             existing_notification:  None,
-            when_changed:           vec![]
+            when_changed:           vec![],
+            static_dependencies:    false,
+            is_same:                None,
+            #[cfg(feature = "graph")]
+            id:                     BindingId::new(),
+            #[cfg(feature = "graph")]
+            dependency_ids:         vec![]
         }
     }
 
@@ -135,10 +160,7 @@ where TFn: 'static+Send+Sync+Fn() -> Value {
         // it creates one rather than returning an existing one.
         BindingContext::panic_if_in_binding_context("Cannot create computed bindings in a computed value calculation function (you should evaluate the value directly rather than create bindings)");
 
-        // Create the binding
-        ComputedBinding {
-            core: Arc::new(Mutex::new(ComputedBindingCore::new(calculate_value)))
-        }
+        Self::new_in_context(calculate_value)
     }
 
     ///
@@ -153,9 +175,47 @@ where TFn: 'static+Send+Sync+Fn() -> Value {
     /// 
     pub fn new_in_context(calculate_value: TFn) -> ComputedBinding<Value, TFn> {
         // Create the binding
-        ComputedBinding {
-            core: Arc::new(Mutex::new(ComputedBindingCore::new(calculate_value)))
+        let core = Arc::new(Mutex::new(ComputedBindingCore::new(calculate_value)));
+
+        #[cfg(feature = "graph")]
+        {
+            let graph_node: Weak<dyn GraphNode> = Arc::downgrade(&core) as Weak<Mutex<ComputedBindingCore<Value, TFn>>>;
+            register_graph_node(graph_node);
         }
+
+        ComputedBinding { core }
+    }
+
+    ///
+    /// Creates a new computable binding that only tracks the dependencies read during its first calculation
+    ///
+    /// Later calculations don't change the set of dependencies, so this binding will always be notified
+    /// by the bindings that were read the first time, even if a different branch is taken later on. Note
+    /// that a computed binding that is a dependency will only generate further notifications if it's
+    /// read again by the calculation function.
+    ///
+    pub fn new_with_static_dependencies(calculate_value: TFn) -> ComputedBinding<Value, TFn> {
+        let binding = Self::new(calculate_value);
+        binding.core.lock().unwrap().static_dependencies = true;
+
+        binding
+    }
+
+    ///
+    /// Creates a new computable binding that only notifies when its value is different
+    ///
+    /// When one of the dependencies changes and something is waiting to be notified about this binding, the value is
+    /// recalculated immediately and compared with the cached value, and the notification is only sent if they're not
+    /// equal. This makes `follow()` and `when_changed()` skip recalculations that produce the same value, for example
+    /// when following the length of a rope that's being edited. The value is still calculated lazily if nothing is
+    /// waiting for notifications.
+    ///
+    pub fn new_distinct(calculate_value: TFn) -> ComputedBinding<Value, TFn>
+    where Value: PartialEq {
+        let binding = Self::new(calculate_value);
+        binding.core.lock().unwrap().is_same = Some(<Value as PartialEq>::eq);
+
+        binding
     }
 
     ///
@@ -163,33 +223,52 @@ where TFn: 'static+Send+Sync+Fn() -> Value {
     ///
     fn mark_changed(&self, force_notify: bool) {
         // We do the notifications and releasing while the lock is not retained
-        let (notifiable, releasable) = {
+        let (mut notifiable, releasable, previous_value) = {
             // Get the core
             let mut core = self.core.lock().unwrap();
 
+            core.filter_unused_notifications();
+
+            // Bindings that only notify when their value is different need the previous value to compare against (this is only needed if there's something to notify)
+            let previous_value = match (&core.latest_value, core.is_same) {
+                (Cached(value), Some(is_same)) if !force_notify && !core.when_changed.is_empty()    => Some((value.clone(), is_same)),
+                _                                                                                   => None
+            };
+
             // Mark it as changed
             let actually_changed = core.mark_changed() || force_notify;
 
-            core.filter_unused_notifications();
-
             // Get the items that need changing (once we've notified our dependencies that we're changed, we don't need to notify them again until we get recalculated)
-            let notifiable = if actually_changed {
+            let notifiable = if actually_changed && previous_value.is_none() {
                 core.get_notifiable_items()
             } else {
                 vec![]
             };
 
-            // Extract the releasable so we can release it after the lock has gone
+            // Extract the releasable so we can release it after the lock has gone (static dependencies keep their notification)
             let mut releasable: Option<Box<dyn Releasable>> = None;
-            mem::swap(&mut releasable, &mut core.existing_notification);
+            if !core.static_dependencies {
+                mem::swap(&mut releasable, &mut core.existing_notification);
+            }
 
             // These values are needed outside of the lock
-            (notifiable, releasable)
+            (notifiable, releasable, previous_value)
         };
 
         // Don't want any more notifications from this source
         releasable.map(|mut releasable| releasable.done());
 
+        if let Some((previous_value, is_same)) = previous_value {
+            // Recalculate the value straight away, and only notify if it's different
+            let new_value   = BindingContext::out_of_context(|| self.get());
+            let core        = self.core.lock().unwrap();
+
+            // The value is left uncached if the dependencies changed during the calculation, in which case the notification has already been sent
+            if matches!(core.latest_value, Cached(_)) && !is_same(&previous_value, &new_value) {
+                notifiable = core.get_notifiable_items();
+            }
+        }
+
         // Notify anything that needs to be notified that this has changed
         for to_notify in notifiable {
             to_notify.mark_as_changed();
@@ -258,6 +337,34 @@ where TFn: 'static+Send+Sync+Fn() -> Value {
 
         Box::new(releasable)
     }
+
+    fn release_unused_notifications(&self) {
+        self.core.lock().unwrap().filter_unused_notifications();
+    }
+
+    fn identity(&self) -> Option<usize> {
+        Some(Arc::as_ptr(&self.core) as *const () as usize)
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        Some(self.core.lock().unwrap().id)
+    }
+}
+
+#[cfg(feature = "graph")]
+impl<Value: 'static+Clone+Send, TFn> GraphNode for Mutex<ComputedBindingCore<Value, TFn>>
+where TFn: 'static+Send+Sync+Fn() -> Value {
+    fn graph_node(&self) -> BindingGraphNode {
+        let core = self.lock().unwrap();
+
+        BindingGraphNode {
+            id:             core.id,
+            kind:           BindingKind::Computed,
+            value_type:     Some(core::any::type_name::<Value>()),
+            dependencies:   core.dependency_ids.clone()
+        }
+    }
 }
 
 impl<Value: 'static+Clone+Send, TFn> Bound<Value> for ComputedBinding<Value, TFn>
@@ -277,6 +384,11 @@ where TFn: 'static+Send+Sync+Fn() -> Value {
 
             if let Cached(value) = core.get() {
                 // The value already exists in this item
+                result = value;
+            } else if core.static_dependencies && core.existing_notification.is_some() {
+                // The dependencies were fixed by an earlier calculation, so the new ones can be ignored
+                let (value, _dependencies) = core.recalculate();
+
                 result = value;
             } else {
                 // TODO: really want to recalculate without locking the core - can do this by moving the function out and doing the recalculation here
@@ -295,6 +407,11 @@ where TFn: 'static+Send+Sync+Fn() -> Value {
                 // Need to re-calculate the core
                 let (value, mut dependencies) = core.recalculate();
 
+                #[cfg(feature = "graph")]
+                {
+                    core.dependency_ids = dependencies.dependency_ids();
+                }
+
                 // If any of the dependencies change, mark this item as changed too
                 notify_immediately = self.monitor_changes(&mut core, &mut dependencies);
 
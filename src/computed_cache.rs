@@ -0,0 +1,194 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+use super::binding_context::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap};
+use std::hash::{Hash};
+use std::sync::*;
+
+///
+/// The cached computed bindings, indexed by the type of their key and value, then by the key itself
+///
+static CACHE: Mutex<Option<HashMap<TypeId, Box<dyn Any+Send>>>> = Mutex::new(None);
+
+///
+/// A computed binding that's stored in the cache, which removes itself from the cache when the last reference is dropped
+///
+struct CachedComputed<Key, Value>
+where
+Key:    'static+Hash+Eq+Send+Sync,
+Value:  'static+Clone+Send {
+    /// The key that this binding is stored under
+    key: Key,
+
+    /// The shared computed binding
+    binding: BindRef<Value>,
+}
+
+///
+/// Runs a function on the cache for a particular type of key and value
+///
+fn with_cache<Key, Value, TResult>(action: impl FnOnce(&mut HashMap<Key, Weak<CachedComputed<Key, Value>>>) -> TResult) -> TResult
+where
+Key:    'static+Hash+Eq+Send+Sync,
+Value:  'static+Clone+Send {
+    let mut cache   = CACHE.lock().unwrap();
+    let cache       = cache.get_or_insert_with(HashMap::new);
+    let typed_cache = cache.entry(TypeId::of::<(Key, Value)>())
+        .or_insert_with(|| Box::new(HashMap::<Key, Weak<CachedComputed<Key, Value>>>::new()))
+        .downcast_mut::<HashMap<Key, Weak<CachedComputed<Key, Value>>>>()
+        .unwrap();
+
+    action(typed_cache)
+}
+
+impl<Key, Value> Drop for CachedComputed<Key, Value>
+where
+Key:    'static+Hash+Eq+Send+Sync,
+Value:  'static+Clone+Send {
+    fn drop(&mut self) {
+        // The entry is only removed if it hasn't been replaced by a new binding with the same key (the binding itself is dropped after the cache is unlocked)
+        with_cache::<Key, Value, _>(|cache| {
+            if cache.get(&self.key).map(|entry| entry.strong_count() == 0).unwrap_or(false) {
+                cache.remove(&self.key);
+            }
+        });
+    }
+}
+
+impl<Key, Value> Changeable for CachedComputed<Key, Value>
+where
+Key:    'static+Hash+Eq+Send+Sync,
+Value:  'static+Clone+Send {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.binding.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.binding.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.binding.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.binding.binding_id()
+    }
+}
+
+impl<Key, Value> Bound<Value> for CachedComputed<Key, Value>
+where
+Key:    'static+Hash+Eq+Send+Sync,
+Value:  'static+Clone+Send {
+    fn get(&self) -> Value {
+        self.binding.get()
+    }
+}
+
+///
+/// Creates a computed binding that is shared with any other computed binding created with the same key
+///
+/// If a binding created with `key` is still in use, this returns a reference to it and `calculate_value` is
+/// not used, so two parts of an application that derive the same value only need to compute it once. The
+/// key should identify both the calculation and its inputs. Bindings are removed from the cache when the
+/// last reference to them is dropped, so a later call with the same key will create a new binding.
+///
+pub fn computed_cached<Key, Value, TFn>(key: Key, calculate_value: TFn) -> BindRef<Value>
+where
+Key:    'static+Hash+Eq+Clone+Send+Sync,
+Value:  'static+Clone+Send,
+TFn:    'static+Send+Sync+Fn() -> Value {
+    // Check this before locking the cache so that it isn't poisoned by the panic
+    BindingContext::panic_if_in_binding_context("Cannot create computed bindings in a computed value calculation function (you should evaluate the value directly rather than create bindings)");
+
+    with_cache(move |cache| {
+        if let Some(existing) = cache.get(&key).and_then(|entry| entry.upgrade()) {
+            // Share the existing binding
+            BindRef::from_arc(existing)
+        } else {
+            // Create a new binding and store it in the cache
+            let binding = Arc::new(CachedComputed {
+                key:        key.clone(),
+                binding:    BindRef::from_arc(Arc::new(ComputedBinding::new(calculate_value))),
+            });
+
+            cache.insert(key, Arc::downgrade(&binding));
+            BindRef::from_arc(binding)
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn same_key_shares_calculation() {
+        let input           = bind(2);
+        let calculations    = Arc::new(AtomicUsize::new(0));
+
+        let create_doubled = || {
+            let input           = input.clone();
+            let calculations    = Arc::clone(&calculations);
+
+            computed_cached(("doubled", 1), move || {
+                calculations.fetch_add(1, Ordering::Relaxed);
+                input.get() * 2
+            })
+        };
+
+        let doubled_a = create_doubled();
+        let doubled_b = create_doubled();
+
+        assert!(doubled_a.get() == 4);
+        assert!(doubled_b.get() == 4);
+        assert!(calculations.load(Ordering::Relaxed) == 1);
+
+        input.set(3);
+
+        assert!(doubled_a.get() == 6);
+        assert!(doubled_b.get() == 6);
+        assert!(calculations.load(Ordering::Relaxed) == 2);
+    }
+
+    #[test]
+    fn different_keys_are_not_shared() {
+        let input = bind(2);
+
+        let input_copy  = input.clone();
+        let doubled     = computed_cached(("scaled", 2), move || input_copy.get() * 2);
+        let input_copy  = input.clone();
+        let tripled     = computed_cached(("scaled", 3), move || input_copy.get() * 3);
+
+        assert!(doubled.get() == 4);
+        assert!(tripled.get() == 6);
+    }
+
+    #[test]
+    fn entry_removed_when_dropped() {
+        let first = computed_cached(("dropped", 1), || 1);
+        assert!(first.get() == 1);
+
+        // While the first binding is in use, the calculation for a binding with the same key is ignored
+        let second = computed_cached(("dropped", 1), || 2);
+        assert!(second.get() == 1);
+
+        // Once every reference is dropped, the entry is removed and a new binding is created
+        drop(first);
+        drop(second);
+
+        assert!(with_cache::<(&str, i32), i32, _>(|cache| !cache.contains_key(&("dropped", 1))));
+
+        let third = computed_cached(("dropped", 1), || 3);
+        assert!(third.get() == 3);
+    }
+}
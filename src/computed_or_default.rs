@@ -0,0 +1,111 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+
+use std::sync::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+///
+/// Creates a computed binding that returns the result of `calculate_value` wrapped in `Some`, or `None` if it panics
+///
+fn computed_catching_panics<Value, TFn>(calculate_value: TFn) -> BindRef<Option<Value>>
+where
+Value:  'static+Clone+Send,
+TFn:    'static+Send+Sync+Fn() -> Value {
+    BindRef::from_arc(Arc::new(ComputedBinding::new(move || catch_unwind(AssertUnwindSafe(&calculate_value)).ok())))
+}
+
+///
+/// Creates a computed binding that returns a default value if the calculation panics
+///
+/// The calculation is tried again when any of the bindings it read before it panicked change, so the binding recovers once
+/// the problem has been fixed. The panic is still reported by the panic hook in the usual way.
+///
+pub fn computed_or_default<Value, TFn>(default_value: Value, calculate_value: TFn) -> BindRef<Value>
+where
+Value:  'static+Clone+Send+Sync,
+TFn:    'static+Send+Sync+Fn() -> Value {
+    let result = computed_catching_panics(calculate_value);
+
+    BindRef::from_arc(Arc::new(ComputedBinding::new(move || result.get().unwrap_or_else(|| default_value.clone()))))
+}
+
+///
+/// Creates a computed binding that returns a default value if the calculation panics, along with a binding that
+/// is true when the last calculation panicked
+///
+/// This is useful for showing an error instead of the default value. Both bindings are updated from the same
+/// calculation, so reading either of them recalculates the value if it's out of date.
+///
+pub fn computed_or_default_with_status<Value, TFn>(default_value: Value, calculate_value: TFn) -> (BindRef<Value>, BindRef<bool>)
+where
+Value:  'static+Clone+Send+Sync,
+TFn:    'static+Send+Sync+Fn() -> Value {
+    let result          = computed_catching_panics(calculate_value);
+
+    let value_result    = result.clone();
+    let value           = BindRef::from_arc(Arc::new(ComputedBinding::new(move || value_result.get().unwrap_or_else(|| default_value.clone()))));
+    let panicked        = BindRef::from(ComputedBinding::new(move || result.get().is_none()));
+
+    (value, panicked)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn returns_calculated_value() {
+        let value = computed_or_default(0, || 42);
+
+        assert!(value.get() == 42);
+    }
+
+    #[test]
+    fn returns_default_after_panic() {
+        let divisor         = bind(0);
+        let divisor_copy    = divisor.clone();
+        let value           = computed_or_default(-1, move || 12 / divisor_copy.get());
+
+        assert!(value.get() == -1);
+
+        // Recovers when the binding that caused the panic changes
+        divisor.set(4);
+        assert!(value.get() == 3);
+    }
+
+    #[test]
+    fn status_indicates_panic() {
+        let divisor             = bind(0);
+        let divisor_copy        = divisor.clone();
+        let (value, panicked)   = computed_or_default_with_status(-1, move || 12 / divisor_copy.get());
+
+        assert!(panicked.get());
+        assert!(value.get() == -1);
+
+        divisor.set(3);
+        assert!(!panicked.get());
+        assert!(value.get() == 4);
+
+        divisor.set(0);
+        assert!(value.get() == -1);
+        assert!(panicked.get());
+    }
+
+    #[test]
+    fn status_notifies_when_calculation_panics() {
+        let divisor             = bind(1);
+        let divisor_copy        = divisor.clone();
+        let (_value, panicked)  = computed_or_default_with_status(-1, move || 12 / divisor_copy.get());
+        let changed             = bind(false);
+
+        let notify_changed      = changed.clone();
+        assert!(!panicked.get());
+        panicked.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        divisor.set(0);
+        assert!(changed.get());
+        assert!(panicked.get());
+    }
+}
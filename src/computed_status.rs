@@ -0,0 +1,111 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::computed::*;
+use super::notify_fn::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use alloc::boxed::Box;
+
+///
+/// A computed binding that clears a 'stale' flag whenever its value is read
+///
+struct ComputedWithStatus<Value> {
+    /// The computed value
+    value: BindRef<Value>,
+
+    /// Set to true when a dependency of the value changes, and false when the value is read
+    stale: Binding<bool>,
+
+    /// Sets the stale flag when the computed value changes (released when this binding is dropped)
+    _monitor: Mutex<Box<dyn Releasable>>,
+}
+
+impl<Value: 'static+Clone+Send> Changeable for ComputedWithStatus<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.value.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.value.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.value.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.value.binding_id()
+    }
+}
+
+impl<Value: 'static+Clone+Send> Bound<Value> for ComputedWithStatus<Value> {
+    fn get(&self) -> Value {
+        // The flag is cleared before reading the value, so a change made while the value is being calculated will set it again
+        self.stale.set(false);
+
+        self.value.get()
+    }
+}
+
+///
+/// Creates a computed binding along with a binding that indicates whether or not it's waiting to be recalculated
+///
+/// Computed bindings are lazy, so when one of their dependencies changes, they're not recalculated until their value is
+/// next read. The status binding is true from when a dependency changes until the value binding is next read, which
+/// can be used to show that a value is out of date while it's being updated. As the value hasn't been calculated yet,
+/// the status binding is also true before the value binding is first read.
+///
+pub fn computed_with_status<Value, TFn>(calculate_value: TFn) -> (BindRef<Value>, BindRef<bool>)
+where
+Value:  'static+Clone+Send,
+TFn:    'static+Send+Sync+Fn() -> Value {
+    let value       = BindRef::from_arc(Arc::new(ComputedBinding::new(calculate_value)));
+    let stale       = Binding::new(true);
+
+    // The computed binding notifies when a dependency changes after it has been read
+    let stale_copy  = stale.clone();
+    let monitor     = value.when_changed(notify(move || stale_copy.set(true)));
+
+    let value       = ComputedWithStatus {
+        value,
+        stale:      stale.clone(),
+        _monitor:   Mutex::new(monitor),
+    };
+
+    (BindRef::from_arc(Arc::new(value)), BindRef::from(stale))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn stale_until_first_read() {
+        let (value, stale) = computed_with_status(|| 1);
+
+        assert!(stale.get());
+        assert!(value.get() == 1);
+        assert!(!stale.get());
+    }
+
+    #[test]
+    fn stale_after_dependency_changes() {
+        let input           = bind(1);
+        let computed_input  = input.clone();
+        let (value, stale)  = computed_with_status(move || computed_input.get() * 2);
+
+        assert!(value.get() == 2);
+        assert!(!stale.get());
+
+        input.set(2);
+        assert!(stale.get());
+
+        assert!(value.get() == 4);
+        assert!(!stale.get());
+    }
+}
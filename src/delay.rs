@@ -0,0 +1,93 @@
+use super::traits::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::collections::{VecDeque};
+
+///
+/// Creates a binding that trails behind another binding by a fixed number of changes
+///
+/// The delayed binding has the value the source binding had `num_changes` changes ago. It keeps the initial value of the
+/// source binding until the source has changed more than `num_changes` times.
+///
+pub fn delay_by<Value>(binding: BindRef<Value>, num_changes: usize) -> BindRef<Value>
+where Value: 'static+Clone+Send+PartialEq {
+    // The history holds the most recent values of the source binding, with the delayed value at the front
+    let initial_value   = binding.get();
+    let mut history     = VecDeque::with_capacity(num_changes+1);
+    history.push_back(initial_value.clone());
+
+    // Add new values to the history whenever the source binding changes
+    bind_to_source_with_state(binding, history, initial_value, move |history, new_value: Value| {
+        if history.back() == Some(&new_value) {
+            // The value has not actually changed
+            None
+        } else {
+            // Add the new value, and move the delayed value along once the history is full
+            history.push_back(new_value);
+            if history.len() > num_changes+1 {
+                history.pop_front();
+            }
+
+            if history.len() == num_changes+1 {
+                history.front().cloned()
+            } else {
+                None
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn delay_by_two_changes() {
+        let a       = bind(0);
+        let delayed = delay_by(BindRef::new(&a), 2);
+
+        assert!(delayed.get() == 0);
+
+        a.set(1);
+        assert!(delayed.get() == 0);
+
+        a.set(2);
+        assert!(delayed.get() == 0);
+
+        a.set(3);
+        assert!(delayed.get() == 1);
+
+        a.set(4);
+        assert!(delayed.get() == 2);
+    }
+
+    #[test]
+    fn delay_by_zero_changes_follows_source() {
+        let a       = bind(0);
+        let delayed = delay_by(BindRef::new(&a), 0);
+
+        a.set(1);
+        assert!(delayed.get() == 1);
+
+        a.set(2);
+        assert!(delayed.get() == 2);
+    }
+
+    #[test]
+    fn delay_ignores_notifications_without_changes() {
+        let a       = bind(1);
+        let a_copy  = a.clone();
+        let is_odd  = computed(move || a_copy.get() % 2);
+        let delayed = delay_by(BindRef::from(is_odd), 1);
+
+        a.set(3);
+        a.set(2);
+        assert!(delayed.get() == 1);
+
+        a.set(4);
+        a.set(5);
+        assert!(delayed.get() == 0);
+    }
+}
@@ -0,0 +1,76 @@
+use super::traits::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::mem;
+use std::ops::{Sub};
+
+///
+/// Creates a binding to the difference between the two most recent values of another binding
+///
+/// The difference is calculated as `new_value - old_value` whenever the source binding changes. The delta binding starts
+/// out with the default value of the output type (ie, 0 for numeric types) as there is no previous value to compare to.
+///
+pub fn delta_binding<Value>(binding: BindRef<Value>) -> BindRef<Value::Output>
+where 
+Value:          'static+Clone+Send+PartialEq+Sub,
+Value::Output:  'static+Clone+Send+PartialEq+Default {
+    // The previous value is updated every time the source binding changes, and the delta is calculated from it
+    let previous_value = binding.get();
+
+    bind_to_source_with_state(binding, previous_value, Value::Output::default(), |previous_value, new_value: Value| {
+        if *previous_value == new_value {
+            // The value has not actually changed
+            None
+        } else {
+            let old_value = mem::replace(previous_value, new_value.clone());
+            Some(new_value - old_value)
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn delta_starts_at_zero() {
+        let a       = bind(5);
+        let delta   = delta_binding(BindRef::new(&a));
+
+        assert!(delta.get() == 0);
+    }
+
+    #[test]
+    fn delta_follows_changes() {
+        let a       = bind(5);
+        let delta   = delta_binding(BindRef::new(&a));
+
+        a.set(8);
+        assert!(delta.get() == 3);
+
+        a.set(6);
+        assert!(delta.get() == -2);
+
+        a.set(6);
+        assert!(delta.get() == -2);
+
+        a.set(10);
+        assert!(delta.get() == 4);
+    }
+
+    #[test]
+    fn delta_of_computed_binding() {
+        let a       = bind(1.0);
+        let a_copy  = a.clone();
+        let doubled = computed(move || a_copy.get() * 2.0);
+        let delta   = delta_binding(BindRef::from(doubled));
+
+        a.set(2.0);
+        assert!(delta.get() == 2.0);
+
+        a.set(1.5);
+        assert!(delta.get() == -1.0);
+    }
+}
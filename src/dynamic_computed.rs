@@ -0,0 +1,224 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::computed::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use std::sync::*;
+
+///
+/// Identifies an input that was added to a `DynamicComputed` binding
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DynamicInputId(u64);
+
+///
+/// The inputs to a dynamic computed binding
+///
+struct DynamicInputs<Input> {
+    /// The ID to assign to the next input
+    next_id: u64,
+
+    /// The current inputs, in the order they were added
+    inputs: Vec<(DynamicInputId, BindRef<Input>)>,
+}
+
+///
+/// A computed binding whose value is calculated from a set of input bindings that can change after it's created
+///
+/// The value is recalculated whenever any of the inputs changes, or when an input is added or removed. This is useful
+/// where the number of values that contribute to a result isn't known in advance, such as a spreadsheet cell that
+/// totals a range that the user can resize.
+///
+#[derive(Clone)]
+pub struct DynamicComputed<Input, Value> {
+    /// The inputs to the calculation
+    inputs: Arc<Mutex<DynamicInputs<Input>>>,
+
+    /// Changed whenever an input is added or removed
+    inputs_changed: Binding<u64>,
+
+    /// The computed value
+    value: BindRef<Value>,
+}
+
+impl<Input, Value> DynamicComputed<Input, Value>
+where
+Input:  'static+Clone+Send,
+Value:  'static+Clone+Send {
+    ///
+    /// Creates a new dynamic computed binding with no inputs, which calculates its value from the values of its inputs using an aggregate function
+    ///
+    pub fn new<TFn>(aggregate: TFn) -> DynamicComputed<Input, Value>
+    where TFn: 'static+Send+Sync+Fn(&[Input]) -> Value {
+        let inputs          = Arc::new(Mutex::new(DynamicInputs { next_id: 0, inputs: vec![] }));
+        let inputs_changed  = Binding::new(0);
+
+        let calc_inputs     = Arc::clone(&inputs);
+        let calc_changed    = inputs_changed.clone();
+        let value           = ComputedBinding::new(move || {
+            // Reading the counter means the value is recalculated when the inputs are added or removed
+            calc_changed.get();
+
+            // Read the inputs outside of the lock, so they are all dependencies of the computed value
+            let inputs = calc_inputs.lock().unwrap().inputs.iter().map(|(_, input)| input.clone()).collect::<Vec<_>>();
+            let values = inputs.iter().map(|input| input.get()).collect::<Vec<_>>();
+
+            aggregate(&values)
+        });
+
+        DynamicComputed {
+            inputs,
+            inputs_changed,
+            value: BindRef::from_arc(Arc::new(value)),
+        }
+    }
+
+    ///
+    /// Adds a new input to this binding, returning an ID that can be used to remove it again
+    ///
+    pub fn add_input<TBinding: 'static+Bound<Input>>(&self, input: TBinding) -> DynamicInputId {
+        let id = {
+            let mut inputs  = self.inputs.lock().unwrap();
+            let id          = DynamicInputId(inputs.next_id);
+
+            inputs.next_id += 1;
+            inputs.inputs.push((id, BindRef::from_arc(Arc::new(input))));
+
+            id
+        };
+
+        self.inputs_changed.with_mut(|count| { *count += 1; true });
+
+        id
+    }
+
+    ///
+    /// Removes an input from this binding, returning false if it was already removed
+    ///
+    pub fn remove_input(&self, id: DynamicInputId) -> bool {
+        let removed = {
+            let mut inputs  = self.inputs.lock().unwrap();
+            let old_len     = inputs.inputs.len();
+
+            inputs.inputs.retain(|(input_id, _)| *input_id != id);
+            inputs.inputs.len() != old_len
+        };
+
+        if removed {
+            self.inputs_changed.with_mut(|count| { *count += 1; true });
+        }
+
+        removed
+    }
+
+    ///
+    /// Returns the number of inputs that this binding is currently using
+    ///
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.lock().unwrap().inputs.len()
+    }
+}
+
+impl<Input, Value> Changeable for DynamicComputed<Input, Value>
+where
+Input:  'static+Clone+Send,
+Value:  'static+Clone+Send {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.value.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.value.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.value.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.value.binding_id()
+    }
+}
+
+impl<Input, Value> Bound<Value> for DynamicComputed<Input, Value>
+where
+Input:  'static+Clone+Send,
+Value:  'static+Clone+Send {
+    fn get(&self) -> Value {
+        self.value.get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn aggregates_inputs() {
+        let total   = DynamicComputed::new(|values: &[i32]| values.iter().sum::<i32>());
+        let a       = bind(1);
+        let b       = bind(2);
+
+        assert!(total.get() == 0);
+
+        total.add_input(a.clone());
+        total.add_input(b.clone());
+
+        assert!(total.get() == 3);
+        assert!(total.num_inputs() == 2);
+    }
+
+    #[test]
+    fn follows_changes_to_inputs() {
+        let total   = DynamicComputed::new(|values: &[i32]| values.iter().sum::<i32>());
+        let a       = bind(1);
+        let b       = bind(2);
+
+        total.add_input(a.clone());
+        let b_id    = total.add_input(b.clone());
+        assert!(total.get() == 3);
+
+        a.set(10);
+        assert!(total.get() == 12);
+
+        // Removed inputs no longer contribute to the value
+        assert!(total.remove_input(b_id));
+        assert!(!total.remove_input(b_id));
+        assert!(total.get() == 10);
+
+        b.set(20);
+        assert!(total.get() == 10);
+    }
+
+    #[test]
+    fn notifies_when_inputs_change() {
+        let total   = DynamicComputed::new(|values: &[i32]| values.iter().sum::<i32>());
+        let a       = bind(1);
+        let changed = bind(false);
+
+        let notify_changed = changed.clone();
+        total.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+        assert!(total.get() == 0);
+
+        let a_id = total.add_input(a.clone());
+        assert!(changed.get());
+        assert!(total.get() == 1);
+
+        changed.set(false);
+        a.set(2);
+        assert!(changed.get());
+        assert!(total.get() == 2);
+
+        // Inputs that have been removed don't cause a notification when they change
+        total.remove_input(a_id);
+        assert!(total.get() == 0);
+
+        changed.set(false);
+        a.set(3);
+        assert!(!changed.get());
+    }
+}
@@ -0,0 +1,136 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::computed::*;
+
+///
+/// A binding with a working value that can be edited, and a committed value that only changes when the edits are committed
+///
+/// This is useful for forms where the user can cancel their edits: the form edits the `working()` binding, and `commit()`
+/// or `rollback()` is called when the user accepts or cancels their changes. Clones of an editable binding share the same
+/// values.
+///
+#[derive(Clone)]
+pub struct EditableBinding<Value> {
+    /// The value that's being edited
+    working: Binding<Value>,
+
+    /// The value as it was when the edits were last committed
+    committed: Binding<Value>,
+
+    /// True if the working value is different from the committed value
+    is_dirty: BindRef<bool>,
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> EditableBinding<Value> {
+    ///
+    /// Creates a new editable binding, where both the working and the committed values start as `initial_value`
+    ///
+    pub fn new(initial_value: Value) -> EditableBinding<Value> {
+        let working         = Binding::new(initial_value.clone());
+        let committed       = Binding::new(initial_value);
+
+        let working_copy    = working.clone();
+        let committed_copy  = committed.clone();
+        let is_dirty        = BindRef::from(ComputedBinding::new(move || working_copy.get() != committed_copy.get()));
+
+        EditableBinding {
+            working,
+            committed,
+            is_dirty
+        }
+    }
+
+    ///
+    /// Returns the binding containing the value that's being edited
+    ///
+    pub fn working(&self) -> Binding<Value> {
+        self.working.clone()
+    }
+
+    ///
+    /// Returns a binding to the value as it was when the edits were last committed
+    ///
+    pub fn committed(&self) -> BindRef<Value> {
+        BindRef::new(&self.committed)
+    }
+
+    ///
+    /// Returns a binding that is true when the working value is different from the committed value
+    ///
+    pub fn is_dirty(&self) -> BindRef<bool> {
+        self.is_dirty.clone()
+    }
+
+    ///
+    /// Copies the working value to the committed value
+    ///
+    pub fn commit(&self) {
+        self.committed.set(self.working.get());
+    }
+
+    ///
+    /// Discards any edits by copying the committed value back to the working value
+    ///
+    pub fn rollback(&self) {
+        self.working.set(self.committed.get());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    use alloc::string::ToString;
+
+    #[test]
+    fn commit_edits() {
+        let name = editable_binding("Alice".to_string());
+
+        name.working().set("Bob".to_string());
+        assert!(name.committed().get() == "Alice");
+        assert!(name.is_dirty().get());
+
+        name.commit();
+        assert!(name.committed().get() == "Bob");
+        assert!(!name.is_dirty().get());
+    }
+
+    #[test]
+    fn rollback_edits() {
+        let name = editable_binding("Alice".to_string());
+
+        name.working().set("Bob".to_string());
+        name.rollback();
+
+        assert!(name.working().get() == "Alice");
+        assert!(name.committed().get() == "Alice");
+        assert!(!name.is_dirty().get());
+    }
+
+    #[test]
+    fn editing_back_to_committed_value_is_not_dirty() {
+        let count       = editable_binding(1);
+        let is_dirty    = count.is_dirty();
+
+        count.working().set(2);
+        assert!(is_dirty.get());
+
+        count.working().set(1);
+        assert!(!is_dirty.get());
+    }
+
+    #[test]
+    fn dirty_flag_notifies_when_edited() {
+        let count           = editable_binding(1);
+        let is_dirty        = count.is_dirty();
+        let changed         = bind(false);
+
+        let notify_changed  = changed.clone();
+        assert!(!is_dirty.get());
+        is_dirty.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        count.working().set(2);
+        assert!(changed.get());
+    }
+}
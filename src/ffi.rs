@@ -0,0 +1,107 @@
+use super::traits::*;
+
+use std::sync::*;
+use std::os::raw::{c_void};
+
+///
+/// Notifiable that calls a C function with a user data pointer
+///
+struct RawNotifiable {
+    /// The function to call when the binding changes
+    callback: extern "C" fn(*mut c_void),
+
+    /// The user data to pass to the callback
+    user_data: *mut c_void,
+}
+
+// The caller of `when_changed_raw()` guarantees that the callback can be called from any thread with the user data
+unsafe impl Send for RawNotifiable { }
+unsafe impl Sync for RawNotifiable { }
+
+impl Notifiable for RawNotifiable {
+    fn mark_as_changed(&self) {
+        (self.callback)(self.user_data)
+    }
+}
+
+///
+/// Opaque handle returned by `when_changed_raw()`, which can be released by calling `flo_binding_release_notification()`
+///
+pub struct RawReleasable {
+    /// The notification that's released along with this handle
+    releasable: Box<dyn Releasable>,
+}
+
+///
+/// Calls a C function whenever a binding changes
+///
+/// This is a Rust-side helper for building an FFI layer: it's generic over the binding type, so it can't be called from
+/// C directly. A library that exposes its bindings to C should wrap it in its own `extern "C"` functions for the concrete
+/// binding types it uses, and pass the handle this returns back to C.
+///
+/// The returned handle must be passed to `flo_binding_release_notification()` to stop the notifications. As with
+/// `when_changed()`, the notification is only fired if the binding has been read since the last time it changed.
+///
+/// # Safety
+///
+/// The callback may be called from any thread, and is called with `user_data`, which must remain valid until the handle
+/// is released. A notification that's in progress on another thread may still call the callback while the handle is
+/// being released.
+///
+pub unsafe fn when_changed_raw<TBinding: Changeable>(binding: &TBinding, callback: extern "C" fn(*mut c_void), user_data: *mut c_void) -> *mut RawReleasable {
+    let notifiable  = Arc::new(RawNotifiable { callback, user_data });
+    let releasable  = binding.when_changed(notifiable);
+
+    Box::into_raw(Box::new(RawReleasable { releasable }))
+}
+
+///
+/// Stops the notifications for a handle returned by `when_changed_raw()` and frees the handle
+///
+/// # Safety
+///
+/// The handle must have been returned by `when_changed_raw()` and must not be used again after this call. Passing a null
+/// handle does nothing.
+///
+#[no_mangle]
+pub unsafe extern "C" fn flo_binding_release_notification(handle: *mut RawReleasable) {
+    if !handle.is_null() {
+        let mut handle = Box::from_raw(handle);
+        handle.releasable.done();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    extern "C" fn count_changes(user_data: *mut c_void) {
+        let count = unsafe { &*(user_data as *const AtomicUsize) };
+        count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn notify_raw_callback() {
+        let binding = bind(1);
+        let count   = AtomicUsize::new(0);
+
+        let handle  = unsafe { when_changed_raw(&binding, count_changes, &count as *const AtomicUsize as *mut c_void) };
+
+        binding.set(2);
+        assert!(count.load(Ordering::SeqCst) == 1);
+
+        binding.get();
+        binding.set(3);
+        assert!(count.load(Ordering::SeqCst) == 2);
+
+        // No more notifications once the handle is released
+        unsafe { flo_binding_release_notification(handle); }
+
+        binding.get();
+        binding.set(4);
+        assert!(count.load(Ordering::SeqCst) == 2);
+    }
+}
@@ -0,0 +1,23 @@
+///
+/// Trait implemented by bindings that deliver their updates and notifications asynchronously
+///
+/// Bindings such as `StreamBinding` and the rope bindings process their updates in a background queue. This trait
+/// makes it possible for tests to wait for this queue to drain instead of waiting for a fixed amount of time.
+///
+pub trait FlushPending {
+    ///
+    /// Blocks until any updates and notifications that have been queued for this binding have been processed
+    ///
+    fn flush_pending(&self);
+}
+
+///
+/// Blocks until any updates and notifications that have been queued for a binding have been processed
+///
+/// This is only available when the `test-support` feature is turned on. Note that this only waits for work that is
+/// already queued: for bindings that follow a stream, an item that the stream has not yet produced will not be
+/// waited for.
+///
+pub fn flush_pending<TBinding: FlushPending>(binding: &TBinding) {
+    binding.flush_pending()
+}
@@ -0,0 +1,70 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+
+use alloc::vec::Vec;
+
+///
+/// Creates a binding that folds the values of a list of bindings into a single value
+///
+/// The bindings are read in order, starting from `initial`, and the result is recalculated whenever any of them
+/// change. This is useful for aggregating a varying number of bindings, such as summing a list of numbers. If the
+/// list is empty, the binding always has the initial value.
+///
+pub fn fold_bindings<Value, Result, FoldFn>(bindings: Vec<BindRef<Value>>, initial: Result, fold_fn: FoldFn) -> BindRef<Result>
+where
+Value:  'static+Clone+Send,
+Result: 'static+Clone+Send+Sync+PartialEq,
+FoldFn: 'static+Send+Sync+Fn(Result, Value) -> Result {
+    let folded = ComputedBinding::new(move || {
+        // Every binding is read on every evaluation, so they're all dependencies of the folded binding
+        bindings.iter()
+            .fold(initial.clone(), |result, binding| fold_fn(result, binding.get()))
+    });
+
+    BindRef::from(folded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn sums_bindings() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let c       = bind(3);
+        let sum     = fold_bindings(vec![BindRef::new(&a), BindRef::new(&b), BindRef::new(&c)], 0, |sum, value| sum + value);
+
+        assert!(sum.get() == 6);
+
+        b.set(5);
+        assert!(sum.get() == 9);
+    }
+
+    #[test]
+    fn empty_list_has_initial_value() {
+        let sum     = fold_bindings(Vec::<BindRef<i32>>::new(), 42, |sum, value| sum + value);
+
+        assert!(sum.get() == 42);
+    }
+
+    #[test]
+    fn notifies_when_any_binding_changes() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let sum     = fold_bindings(vec![BindRef::new(&a), BindRef::new(&b)], 0, |sum, value| sum + value);
+        let changed = bind(false);
+
+        let notify_changed = changed.clone();
+        sum.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+        assert!(sum.get() == 3);
+
+        b.set(4);
+        assert!(changed.get());
+        assert!(sum.get() == 5);
+    }
+}
@@ -7,6 +7,7 @@ use futures::task::{Poll};
 
 use std::pin::{Pin};
 use std::sync::*;
+use std::collections::{VecDeque};
 use std::marker::PhantomData;
 
 ///
@@ -21,18 +22,12 @@ enum FollowState {
 ///
 /// Core data structures for a follow stream
 /// 
-struct FollowCore<TValue, Binding: Bound<TValue>> {
+struct FollowCore {
     /// Changed if the binding value has changed, or Unchanged if it is not changed
     state: FollowState,
 
     /// What to notify when this item is changed
     notify: Option<task::Waker>,
-
-    /// The binding that this is following
-    binding: Arc<Binding>,
-
-    /// Value is stored in the binding
-    value: PhantomData<TValue>
 }
 
 ///
@@ -43,11 +38,29 @@ where
     TValue:     Send,
     Binding:    Bound<TValue>,
 {
-    /// The core of this future
-    core: Arc<Mutex<FollowCore<TValue, Binding>>>,
+    /// The core of this future (this doesn't own the binding, so the notification never holds the last reference to it)
+    core: Arc<Mutex<FollowCore>>,
+
+    /// The binding that this is following
+    binding: Arc<Binding>,
 
     /// Lifetime of the watcher
-    _watcher: Box<dyn Releasable>,
+    watcher: Box<dyn Releasable>,
+
+    /// Value is stored in the binding
+    value: PhantomData<fn() -> TValue>
+}
+
+impl<TValue, Binding> Drop for FollowStream<TValue, Binding>
+where 
+    TValue:     Send,
+    Binding:    Bound<TValue>,
+{
+    fn drop(&mut self) {
+        // Release the watcher and remove it from the binding immediately, rather than waiting for the binding to tidy it up
+        self.watcher.done();
+        self.binding.release_unused_notifications();
+    }
 }
 
 impl<TValue, Binding> Stream for FollowStream<TValue, Binding>
@@ -58,37 +71,129 @@ where
     type Item   = TValue;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Option<Self::Item>> {
-        // If the core is in a 'changed' state, return the binding so we can fetch it
+        // If the core is in a 'changed' state, fetch the value of the binding
         // Want to fetch the binding value outside of the lock as it can potentially change during calculation
-        let binding = {
+        let changed = {
             let mut core = self.core.lock().unwrap();
 
             match core.state {
                 FollowState::Unchanged => {
                     // Wake this future when changed
                     core.notify = Some(cx.waker().clone());
-                    None
+                    false
                 },
 
                 FollowState::Changed => {
                     // Value has changed since we were last notified: return the changed value
                     core.state = FollowState::Unchanged;
-                    Some(Arc::clone(&core.binding))
+                    true
                 }
             }
         };
 
-        if let Some(binding) = binding {
-            Poll::Ready(Some(binding.get()))
+        if changed {
+            Poll::Ready(Some(self.binding.get()))
         } else {
             Poll::Pending
         }
     }
 }
 
+///
+/// Core data structures for a buffered follow stream
+///
+struct FollowBufferedCore<TValue> {
+    /// The values that have been read from the binding but not yet returned by the stream
+    buffer: VecDeque<TValue>,
+
+    /// The maximum number of values that can be stored in the buffer
+    capacity: usize,
+
+    /// The last value that was added to the buffer
+    last_value: Option<TValue>,
+
+    /// The number of values that have been discarded because the buffer was full
+    dropped: usize,
+
+    /// What to notify when a new value is added to the buffer
+    notify: Option<task::Waker>,
+}
+
+impl<TValue: Clone+PartialEq> FollowBufferedCore<TValue> {
+    ///
+    /// Adds a new value to the buffer, discarding the oldest value if the buffer is full
+    ///
+    fn push(&mut self, value: TValue) {
+        // Values that are the same as the last value are not buffered
+        if self.last_value.as_ref() == Some(&value) {
+            return;
+        }
+
+        // Make space by dropping the oldest value
+        if self.buffer.len() >= self.capacity {
+            self.buffer.pop_front();
+            self.dropped += 1;
+        }
+
+        self.last_value = Some(value.clone());
+        self.buffer.push_back(value);
+    }
+}
+
+///
+/// Stream that follows the values of a binding, buffering up to a fixed number of distinct values
+///
+pub struct FollowBufferedStream<TValue> {
+    /// The core of this stream
+    core: Arc<Mutex<FollowBufferedCore<TValue>>>,
+
+    /// The binding that this is following
+    binding: Arc<dyn Bound<TValue>>,
+
+    /// Lifetime of the watcher
+    watcher: Box<dyn Releasable>,
+}
+
+impl<TValue> Drop for FollowBufferedStream<TValue> {
+    fn drop(&mut self) {
+        // Release the watcher and remove it from the binding immediately
+        self.watcher.done();
+        self.binding.release_unused_notifications();
+    }
+}
+
+impl<TValue> FollowBufferedStream<TValue> {
+    ///
+    /// Returns the number of values that have been discarded from this stream because its buffer was full
+    ///
+    pub fn dropped_count(&self) -> usize {
+        self.core.lock().unwrap().dropped
+    }
+}
+
+impl<TValue> Stream for FollowBufferedStream<TValue> {
+    type Item   = TValue;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Option<Self::Item>> {
+        let mut core = self.core.lock().unwrap();
+
+        if let Some(value) = core.buffer.pop_front() {
+            Poll::Ready(Some(value))
+        } else {
+            // Wake this future when a new value is buffered
+            core.notify = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
 ///
 /// Creates a stream from a binding
 /// 
+/// The stream returns a value whenever the binding notifies that it has changed. For computed bindings, this
+/// will happen whenever any of the dependencies change, even if the computed value turns out to be the same:
+/// computed bindings created with `computed_distinct()` only notify when their value is different.
+/// 
 pub fn follow<TValue, Binding>(binding: Binding) -> FollowStream<TValue, Binding>
 where
     TValue:     'static + Send,
@@ -98,33 +203,160 @@ where
     let core = FollowCore {
         state:      FollowState::Changed,
         notify:     None,
-        binding:    Arc::new(binding),
-        value:      PhantomData
     };
 
     // Notify whenever the binding changes
+    let binding     = Arc::new(binding);
     let core        = Arc::new(Mutex::new(core));
     let weak_core   = Arc::downgrade(&core);
-    let watcher     = {
-        let core = core.lock().unwrap();
+    let watcher     = binding.when_changed(notify(move || {
+        if let Some(core) = weak_core.upgrade() {
+            let task = {
+                let mut core = core.lock().unwrap();
+
+                core.state = FollowState::Changed;
+                core.notify.take()
+            };
+            task.map(|task| task.wake());
+        }
+    }));
+
+    // Create the stream
+    FollowStream {
+        core:       core,
+        binding,
+        watcher,
+        value:      PhantomData
+    }
+}
 
-        core.binding.when_changed(notify(move || {
-            if let Some(core) = weak_core.upgrade() {
-                let task = {
-                    let mut core = core.lock().unwrap();
+///
+/// The state shared between a `wait_until()` future and the notification that checks the value of the binding
+///
+struct WaitUntilCore<TValue> {
+    /// The value that the future is waiting for
+    target: TValue,
 
-                    core.state = FollowState::Changed;
+    /// Set to true once the binding has had the target value
+    reached: bool,
+
+    /// What to notify when the binding reaches the target value
+    notify: Option<task::Waker>,
+}
+
+///
+/// Returns a future that completes the first time a binding has the specified value
+///
+/// The future completes as soon as it's polled if the binding already has the target value. This is useful for waiting
+/// for something to reach a particular state, for example waiting for a connection status binding to become ready. The
+/// binding is read every time it notifies a change, so a value that's replaced before the future is polled is still seen,
+/// as long as the binding still has that value when its notification is sent.
+///
+pub fn wait_until<TValue, Binding>(binding: Binding, target: TValue) -> impl Future<Output=()>+Send
+where
+    TValue:     'static + Send + PartialEq,
+    Binding:    'static + Bound<TValue>,
+{
+    let binding     = Arc::new(binding);
+    let core        = Arc::new(Mutex::new(WaitUntilCore {
+        target,
+        reached:    false,
+        notify:     None,
+    }));
+
+    // Check the value every time the binding changes, starting immediately so that no changes are missed before the future is first polled
+    let weak_core       = Arc::downgrade(&core);
+    let weak_binding    = Arc::downgrade(&binding);
+    let mut watcher     = binding.when_changed(notify(move || {
+        if let (Some(core), Some(binding)) = (weak_core.upgrade(), weak_binding.upgrade()) {
+            // Read the value outside of the lock, as it may take a while to calculate
+            let value   = binding.get();
+            let task    = {
+                let mut core = core.lock().unwrap();
+
+                if value == core.target {
+                    core.reached = true;
                     core.notify.take()
-                };
-                task.map(|task| task.wake());
-            }
-        }))
+                } else {
+                    None
+                }
+            };
+            if let Some(task) = task { task.wake() }
+        }
+    }));
+
+    // The binding might already have the target value
+    let value = binding.get();
+    {
+        let mut core = core.lock().unwrap();
+        if value == core.target {
+            core.reached = true;
+        }
+    }
+
+    future::poll_fn(move |context| {
+        // The binding is kept alive until the target value is reached
+        let _binding = &binding;
+        let mut core = core.lock().unwrap();
+
+        if core.reached {
+            watcher.done();
+            Poll::Ready(())
+        } else {
+            core.notify = Some(context.waker().clone());
+            Poll::Pending
+        }
+    })
+}
+
+///
+/// Creates a stream from a binding that buffers up to `capacity` distinct values
+/// 
+/// Unlike `follow()`, which only ever returns the latest value of the binding, this reads the binding every time
+/// it changes and stores the value until the stream is polled. Values that are the same as the last buffered value
+/// are skipped. When the buffer is full, the oldest value is discarded to make space for the new one: the number of
+/// values discarded this way can be retrieved by calling `dropped_count()` on the stream.
+/// 
+pub fn follow_buffered<TValue, Binding>(binding: Binding, capacity: usize) -> FollowBufferedStream<TValue>
+where
+    TValue:     'static + Send + Clone + PartialEq,
+    Binding:    'static + Bound<TValue>,
+{
+    assert!(capacity > 0, "follow_buffered() needs a capacity of at least 1");
+
+    // The initial value is the first value in the buffer
+    let binding     = Arc::new(binding);
+    let mut core    = FollowBufferedCore {
+        buffer:     VecDeque::with_capacity(capacity),
+        capacity,
+        last_value: None,
+        dropped:    0,
+        notify:     None,
     };
+    core.push(binding.get());
+
+    // Read the value from the binding whenever it changes
+    let core            = Arc::new(Mutex::new(core));
+    let weak_core       = Arc::downgrade(&core);
+    let weak_binding    = Arc::downgrade(&binding);
+    let watcher         = binding.when_changed(notify(move || {
+        if let (Some(core), Some(binding)) = (weak_core.upgrade(), weak_binding.upgrade()) {
+            // Read the value outside of the lock, as it may take a while to calculate
+            let value   = binding.get();
+            let task    = {
+                let mut core = core.lock().unwrap();
+
+                core.push(value);
+                core.notify.take()
+            };
+            if let Some(task) = task { task.wake() }
+        }
+    }));
 
-    // Create the stream
-    FollowStream {
-        core:       core,
-        _watcher:   watcher
+    FollowBufferedStream {
+        core,
+        binding,
+        watcher
     }
 }
 
@@ -159,6 +391,26 @@ mod test {
         });
     }
 
+    #[test]
+    fn follow_computed_distinct_skips_unchanged_values() {
+        let binding     = bind(1);
+        let bind_ref    = BindRef::from(binding.clone());
+        let is_odd      = computed_distinct(move || bind_ref.get() % 2);
+        let waker       = Arc::new(NotifyNothing);
+        let waker       = waker_ref(&waker);
+        let mut context = Context::from_waker(&waker);
+        let mut stream  = follow(is_odd);
+
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(1)));
+
+        // Computed binding is recalculated, but the value is the same
+        binding.set(3);
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Pending);
+
+        binding.set(4);
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(0)));
+    }
+
     #[test]
     fn follow_stream_updates() {
         let binding     = bind(1);
@@ -240,6 +492,30 @@ mod test {
         assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(2)));
     }
 
+    #[test]
+    fn follow_buffered_drops_oldest_values() {
+        let binding     = bind(1);
+        let bind_ref    = BindRef::from(binding.clone());
+        let waker       = Arc::new(NotifyNothing);
+        let waker       = waker_ref(&waker);
+        let mut context = Context::from_waker(&waker);
+        let mut stream  = follow_buffered(bind_ref, 2);
+
+        // Setting the same value again is not buffered
+        binding.set(2);
+        binding.set(2);
+        binding.set(3);
+        binding.set(4);
+
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(3)));
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(4)));
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Pending);
+        assert!(stream.dropped_count() == 2);
+
+        binding.set(5);
+        assert!(stream.poll_next_unpin(&mut context) == Poll::Ready(Some(5)));
+    }
+
     #[test]
     fn will_wake_when_binding_is_updated() {
         let binding     = bind(1);
@@ -256,4 +532,88 @@ mod test {
             assert!(stream.next().await == Some(2));
         })
     }
+
+    ///
+    /// Binding that keeps track of the notifications that are added to it
+    ///
+    struct TrackNotifications {
+        binding:        Binding<i32>,
+        notifications:  Arc<Mutex<Vec<Weak<dyn Notifiable>>>>,
+    }
+
+    impl Changeable for TrackNotifications {
+        fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+            self.notifications.lock().unwrap().push(Arc::downgrade(&what));
+            self.binding.when_changed(what)
+        }
+
+        fn release_unused_notifications(&self) {
+            self.binding.release_unused_notifications();
+        }
+    }
+
+    impl Bound<i32> for TrackNotifications {
+        fn get(&self) -> i32 {
+            self.binding.get()
+        }
+    }
+
+    #[test]
+    fn dropping_follow_stream_releases_notification() {
+        let notifications   = Arc::new(Mutex::new(vec![]));
+        let stream          = follow(TrackNotifications { binding: bind(1), notifications: Arc::clone(&notifications) });
+
+        assert!(notifications.lock().unwrap().len() == 1);
+        assert!(notifications.lock().unwrap().iter().all(|notification| notification.upgrade().is_some()));
+
+        drop(stream);
+        assert!(notifications.lock().unwrap().iter().all(|notification| notification.upgrade().is_none()));
+    }
+
+    #[test]
+    fn dropping_buffered_stream_releases_notification() {
+        let notifications   = Arc::new(Mutex::new(vec![]));
+        let stream          = follow_buffered(TrackNotifications { binding: bind(1), notifications: Arc::clone(&notifications) }, 2);
+
+        assert!(notifications.lock().unwrap().len() == 1);
+        assert!(notifications.lock().unwrap().iter().all(|notification| notification.upgrade().is_some()));
+
+        drop(stream);
+        assert!(notifications.lock().unwrap().iter().all(|notification| notification.upgrade().is_none()));
+    }
+
+    #[test]
+    fn wait_until_already_satisfied() {
+        let binding = bind(1);
+
+        executor::block_on(wait_until(binding, 1));
+    }
+
+    #[test]
+    fn wait_until_value_is_set() {
+        let binding = bind(0);
+        let waiting = wait_until(binding.clone(), 3);
+
+        let setter  = binding.clone();
+        thread::spawn(move || {
+            for value in 1..=3 {
+                thread::sleep(Duration::from_millis(5));
+                setter.set(value);
+            }
+        });
+
+        executor::block_on(waiting);
+        assert!(binding.get() == 3);
+    }
+
+    #[test]
+    fn wait_until_sees_values_that_are_replaced_before_polling() {
+        let binding = bind(0);
+        let waiting = wait_until(binding.clone(), 3);
+
+        binding.set(3);
+        binding.set(4);
+
+        executor::block_on(waiting);
+    }
 }
@@ -0,0 +1,62 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::hash::{Hash};
+use std::collections::{HashMap};
+
+///
+/// Creates a binding to the value of a single key in a binding to a `HashMap`
+///
+/// The binding has the value `None` if the key is not present in the map. It only notifies when the value for this key
+/// changes, so changes to other keys in the map will not cause anything depending on it to be updated.
+///
+pub fn key_binding<Key, Value>(map: BindRef<HashMap<Key, Value>>, key: Key) -> BindRef<Option<Value>>
+where 
+Key:    'static+Clone+Send+Sync+Eq+Hash,
+Value:  'static+Clone+Send+PartialEq {
+    let value           = Binding::new(map.get().get(&key).cloned());
+
+    // Update the value whenever the map changes. The value binding will only notify if the value for this key has changed.
+    let value_copy      = value.clone();
+    bind_to_source(map, move |new_map: HashMap<Key, Value>| value_copy.set(new_map.get(&key).cloned()), move || value.get())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn read_key_from_map() {
+        let map     = bind(HashMap::new());
+        let one     = key_binding(BindRef::new(&map), 1);
+
+        assert!(one.get().is_none());
+
+        map.set(vec![(1, "one")].into_iter().collect());
+        assert!(one.get() == Some("one"));
+
+        map.set(HashMap::new());
+        assert!(one.get().is_none());
+    }
+
+    #[test]
+    fn changes_to_other_keys_do_not_notify() {
+        let map     = bind(vec![(1, "one"), (2, "two")].into_iter().collect::<HashMap<_, _>>());
+        let one     = key_binding(BindRef::new(&map), 1);
+        let changed = bind(false);
+
+        let notify_changed = changed.clone();
+        one.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+        assert!(one.get() == Some("one"));
+
+        map.with_mut(|map| { map.insert(2, "deux"); true });
+        assert!(!changed.get());
+
+        map.with_mut(|map| { map.insert(1, "un"); true });
+        assert!(changed.get());
+        assert!(one.get() == Some("un"));
+    }
+}
@@ -0,0 +1,136 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+///
+/// Trait implemented by collections that have a length
+///
+pub trait HasLen {
+    ///
+    /// Returns the number of items in this collection
+    ///
+    fn len(&self) -> usize;
+
+    ///
+    /// Returns true if this collection has no items
+    ///
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> HasLen for Vec<T> {
+    fn len(&self) -> usize { Vec::len(self) }
+}
+
+impl<T> HasLen for VecDeque<T> {
+    fn len(&self) -> usize { VecDeque::len(self) }
+}
+
+impl HasLen for String {
+    fn len(&self) -> usize { String::len(self) }
+}
+
+impl<K, V> HasLen for BTreeMap<K, V> {
+    fn len(&self) -> usize { BTreeMap::len(self) }
+}
+
+impl<T> HasLen for BTreeSet<T> {
+    fn len(&self) -> usize { BTreeSet::len(self) }
+}
+
+#[cfg(any(feature = "std", not(feature = "spin")))]
+impl<K, V, S> HasLen for std::collections::HashMap<K, V, S> {
+    fn len(&self) -> usize { std::collections::HashMap::len(self) }
+}
+
+#[cfg(any(feature = "std", not(feature = "spin")))]
+impl<T, S> HasLen for std::collections::HashSet<T, S> {
+    fn len(&self) -> usize { std::collections::HashSet::len(self) }
+}
+
+///
+/// Extension methods for bindings to collections
+///
+pub trait BoundLenExt<Value> {
+    ///
+    /// Returns a binding to the length of the collection in this binding
+    ///
+    /// Unlike a computed binding that reads the length, this only notifies when the length changes, so changes that
+    /// replace the items in the collection without adding or removing any don't cause anything that depends on the
+    /// length to be recalculated.
+    ///
+    fn len_binding(&self) -> BindRef<usize>;
+}
+
+impl<Value, TBound> BoundLenExt<Value> for TBound
+where
+Value:  'static+Clone+Send+HasLen,
+TBound: 'static+Clone+Bound<Value> {
+    fn len_binding(&self) -> BindRef<usize> {
+        let len             = Binding::new(self.get().len());
+
+        // Update the length whenever the source changes. Setting a binding to its current value doesn't notify, so nothing
+        // happens if the length is unchanged.
+        let len_copy        = len.clone();
+        bind_to_source(self.clone(), move |new_value: Value| len_copy.set(new_value.len()), move || len.get())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn follows_length() {
+        let list    = bind(vec![1, 2, 3]);
+        let len     = list.len_binding();
+
+        assert!(len.get() == 3);
+
+        list.set(vec![1]);
+        assert!(len.get() == 1);
+    }
+
+    #[test]
+    fn only_notifies_when_length_changes() {
+        let list    = bind(vec![1, 2, 3]);
+        let len     = list.len_binding();
+        let changed = bind(false);
+
+        let notify_changed = changed.clone();
+        len.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+        assert!(len.get() == 3);
+
+        list.set(vec![4, 5, 6]);
+        assert!(!changed.get());
+
+        list.set(vec![4, 5, 6, 7]);
+        assert!(changed.get());
+        assert!(len.get() == 4);
+    }
+
+    #[test]
+    fn follows_computed_bindings() {
+        let text        = bind(String::from("abc"));
+        let text_copy   = text.clone();
+        let doubled     = computed(move || text_copy.get().repeat(2));
+        let len         = doubled.len_binding();
+
+        assert!(len.get() == 6);
+
+        text.set(String::from("abcd"));
+        assert!(len.get() == 8);
+
+        text.set(String::from("ab"));
+        assert!(len.get() == 4);
+    }
+}
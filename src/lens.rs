@@ -0,0 +1,167 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::binding_context::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use alloc::boxed::Box;
+
+///
+/// Function that writes a new value for a field into a value
+///
+type SetFieldFn<Value, Field> = dyn Fn(&mut Value, Field)+Send+Sync;
+
+///
+/// A binding that reads and writes a single field of the value stored in another binding
+///
+/// Reading the lens reads the field from the source binding, and setting it updates just that field in the source
+/// binding, leaving the rest of the value as it was. As for a computed binding, the lens notifies whenever the source
+/// binding changes.
+///
+#[derive(Clone)]
+pub struct LensBinding<Value, Field> {
+    /// The binding that contains the field
+    source: Binding<Value>,
+
+    /// The value of the field, computed from the source binding
+    field: BindRef<Field>,
+
+    /// Reads the field from the value
+    get_field: Arc<dyn Fn(&Value) -> Field+Send+Sync>,
+
+    /// Updates the field in the value
+    set_field: Arc<SetFieldFn<Value, Field>>
+}
+
+impl<Value, Field> LensBinding<Value, Field>
+where
+    Value: 'static+Clone+PartialEq+Send,
+    Field: 'static+Clone+PartialEq+Send,
+{
+    ///
+    /// Creates a new lens that uses `get_field` to read a field from the source binding and `set_field` to write it back
+    ///
+    pub fn new<GetFn, SetFn>(source: &Binding<Value>, get_field: GetFn, set_field: SetFn) -> LensBinding<Value, Field>
+    where
+        GetFn: 'static+Send+Sync+Fn(&Value) -> Field,
+        SetFn: 'static+Send+Sync+Fn(&mut Value, Field),
+    {
+        let get_field = Arc::new(get_field);
+
+        let field_source    = source.clone();
+        let field_get       = Arc::clone(&get_field);
+        let field           = BindRef::from_fn(move || {
+            // with_ref() doesn't track dependencies, so add the source binding as a dependency before reading the field
+            BindingContext::add_dependency(field_source.clone());
+            field_source.with_ref(|value| field_get(value))
+        });
+
+        LensBinding {
+            source:     source.clone(),
+            field,
+            get_field,
+            set_field:  Arc::new(set_field)
+        }
+    }
+}
+
+impl<Value, Field> Changeable for LensBinding<Value, Field> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.field.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.field.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.field.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.field.binding_id()
+    }
+}
+
+impl<Value, Field> Bound<Field> for LensBinding<Value, Field>
+where
+    Value: 'static+Clone+PartialEq+Send,
+    Field: 'static+Clone+PartialEq+Send,
+{
+    fn get(&self) -> Field {
+        self.field.get()
+    }
+}
+
+impl<Value, Field> MutableBound<Field> for LensBinding<Value, Field>
+where
+    Value: 'static+Clone+PartialEq+Send,
+    Field: 'static+Clone+PartialEq+Send,
+{
+    fn set(&self, new_value: Field) {
+        // Update the field with the source binding locked, so other fields changed at the same time aren't overwritten
+        self.source.with_mut(|value| {
+            if (self.get_field)(value) == new_value {
+                false
+            } else {
+                (self.set_field)(value, new_value);
+                true
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[derive(Clone, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[test]
+    fn read_field_through_lens() {
+        let point   = bind(Point { x: 1, y: 2 });
+        let x       = lens(&point, |point| point.x, |point, x| point.x = x);
+
+        assert!(x.get() == 1);
+
+        point.set(Point { x: 3, y: 4 });
+        assert!(x.get() == 3);
+    }
+
+    #[test]
+    fn write_field_through_lens() {
+        let point   = bind(Point { x: 1, y: 2 });
+        let x       = lens(&point, |point| point.x, |point, x| point.x = x);
+        let y       = lens(&point, |point| point.y, |point, y| point.y = y);
+
+        x.set(5);
+        y.set(6);
+
+        assert!(point.get() == Point { x: 5, y: 6 });
+        assert!(x.get() == 5);
+        assert!(y.get() == 6);
+    }
+
+    #[test]
+    fn setting_same_value_does_not_notify_source() {
+        let point   = bind(Point { x: 1, y: 2 });
+        let x       = lens(&point, |point| point.x, |point, x| point.x = x);
+        let changed = bind(false);
+
+        let notify_changed = changed.clone();
+        point.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        x.set(1);
+        assert!(!changed.get());
+
+        x.set(2);
+        assert!(changed.get());
+    }
+}
@@ -44,6 +44,7 @@
 //! is the most flexible.
 //! 
 //! ```
+//! # #[cfg(feature = "stream")] {
 //! # use flo_binding::*;
 //! # use futures::prelude::*;
 //! # use futures::executor;
@@ -69,12 +70,14 @@
 //!     event_lifetime.done();
 //!     binding.set(5);                                 // Prints nothing as the 'when_changed' event has been deregistered
 //! # });
+//! # }
 //! ```
 //! 
 //! The inverse of `follow()` is `bind_stream()`, which creates a bindings that is kept
 //! up to date with whatever the last value received from a stream is:
 //! 
 //! ```
+//! # #[cfg(feature = "stream")] {
 //! # use flo_binding::*;
 //!     let binding             = bind(1);
 //!     let binding_from_stream = bind_stream(follow(binding.clone()), 1, |_old_value, new_value| new_value);
@@ -84,6 +87,7 @@
 //! 
 //!     binding.set(2);
 //!     let value = binding_from_stream.get();      // == 2
+//! # }
 //! ```
 //! 
 //! A stream binding like this is read-only, but is a good way to convert any stream of
@@ -109,6 +113,10 @@
 //!     let value = one_more.get();     // == 4 (3 + 1)
 //! ```
 //! 
+//! The `computed!` macro can be used to clone the bindings that are captured by a computed
+//! binding, so the example above can also be written as 
+//! `computed!([binding] => binding.get() + 1)`.
+//! 
 //! For collections of data, `flo_binding` uses the concept of a 'rope binding'. The 
 //! general rope data type is provided by the [`flo_rope`](https://crates.io/crates/flo_rope)
 //! crate. These bindings send differences rather than their full state when streaming and
@@ -119,6 +127,7 @@
 //! information.
 //! 
 //! ```
+//! # #[cfg(feature = "rope")] {
 //! # use flo_binding::*;
 //! # use futures::prelude::*;
 //! # use futures::executor;
@@ -134,12 +143,42 @@
 //!     let rope_len        = rope_copy.len();                                  // == 4
 //!     let rope_content    = rope_copy.read_cells(0..4).collect::<Vec<_>>();   // == vec![1, 2, 3, 4]
 //! # });
+//! # }
 //! ```
 //! 
 //! The `flo_rope` library provides some extra functionality - for example, a way to create the
 //! `RopeAction`s for a rope by using a diff algorithm on a changing sequence instead of just
 //! reporting the changes as they arrive.
 //! 
+//! ## Using without the standard library
+//! 
+//! The basic bindings (`bind()`, `computed()` and `when_changed()`) can be used without the
+//! standard library by turning off the default features and turning on the `spin` feature (the
+//! standard library is still used if `spin` is not turned on, or if `std` is turned on as well). In
+//! this configuration, a spin lock is used in place of `std::sync::Mutex` and there is a single
+//! binding context rather than one per thread, so computed bindings should only be evaluated
+//! from a single thread. The `stream` and `rope` features and the binding combinators such as
+//! `merge()` require the `std` feature.
+//! 
+//! ## Inspecting the binding graph
+//! 
+//! With the `graph` feature, `binding_graph::BindingGraph::capture()` returns the dependencies
+//! of every computed binding that currently exists, which can be written out in the DOT format
+//! for visualization. Every binding has an ID, which can be read with `binding_id()`.
+//! 
+//! ## Sending ropes to other processes
+//! 
+//! The `serde` feature adds `SerializedRopeAction`, a serializable copy of the actions that describe
+//! the changes to a rope. These can be sent over a transport and turned back into a rope with
+//! `RopeBinding::from_serialized_stream()`.
+//! 
+//! ## Using from other languages
+//! 
+//! The `ffi` feature adds `when_changed_raw()`, which calls a C function pointer when a binding
+//! changes. This is called from Rust, by the `extern "C"` functions of a library that exposes its
+//! bindings to C, and returns a handle that can be released from C by calling
+//! `flo_binding_release_notification()`.
+//! 
 //! ## Companion libraries
 //! 
 //! Aside from `flo_rope`, the [`desync`](https://crates.io/crates/desync) crate provides a 
@@ -155,32 +194,128 @@
 //!
 
 #![warn(bare_trait_objects)]
+#![cfg_attr(all(feature = "spin", not(feature = "std")), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(test, feature = "spin", not(feature = "std")))]
+extern crate std;
 
+#[macro_use]
+mod macros;
+mod sync;
 mod traits;
 pub mod binding_context;
+#[cfg(feature = "graph")]
+pub mod binding_graph;
 mod binding;
+mod validated_binding;
+mod validation;
+mod recorded_binding;
+mod editable_binding;
+mod try_map_binding;
+mod memoized_map_binding;
+mod sorted_binding;
+mod computed_status;
+mod select_bool;
+mod len_binding;
+mod project_binding;
+mod approx_binding;
+mod lens;
 mod computed;
+mod fold_bindings;
 mod bindref;
 mod notify_fn;
+mod source_monitor;
+mod when_changed_while;
 mod releasable;
 #[cfg(feature = "stream")]
 mod follow;
 #[cfg(feature = "stream")]
 mod bind_stream;
+#[cfg(feature = "stream")]
+mod when_stable;
+#[cfg(any(test, feature = "test-support"))]
+mod flush_pending;
 #[cfg(feature = "rope")]
 mod rope_binding;
+#[cfg(feature = "ffi")]
+mod ffi;
 
 pub use self::traits::*;
 pub use self::binding::*;
+pub use self::validated_binding::*;
+pub use self::validation::*;
+pub use self::recorded_binding::*;
+pub use self::editable_binding::*;
+pub use self::try_map_binding::*;
+pub use self::memoized_map_binding::*;
+pub use self::sorted_binding::*;
+pub use self::computed_status::*;
+pub use self::select_bool::*;
+pub use self::len_binding::*;
+pub use self::project_binding::*;
+pub use self::approx_binding::*;
+pub use self::lens::*;
 pub use self::computed::*;
+pub use self::fold_bindings::*;
 pub use self::bindref::*;
 pub use self::notify_fn::*;
+pub use self::when_changed_while::*;
 #[cfg(feature = "stream")]
 pub use self::follow::*;
 #[cfg(feature = "stream")]
 pub use self::bind_stream::*;
+#[cfg(feature = "stream")]
+pub use self::when_stable::*;
+#[cfg(any(test, feature = "test-support"))]
+pub use self::flush_pending::*;
 #[cfg(feature = "rope")]
 pub use self::rope_binding::*;
+#[cfg(feature = "ffi")]
+pub use self::ffi::*;
+
+///
+/// Declares modules that need the standard library, along with their re-exports
+///
+/// These modules are left out when the crate is built with `spin` in place of the standard library.
+///
+macro_rules! std_modules {
+    ($($module:ident),* $(,)?) => {
+        $(
+            #[cfg(any(feature = "std", not(feature = "spin")))]
+            mod $module;
+            #[cfg(any(feature = "std", not(feature = "spin")))]
+            pub use self::$module::*;
+        )*
+    };
+}
+
+#[cfg(any(feature = "std", not(feature = "spin")))]
+mod timer;
+
+std_modules! {
+    computed_cache,
+    computed_or_default,
+    merge,
+    dynamic_computed,
+    shared_binding,
+    delay,
+    delta,
+    changes_binding,
+    changed_indices,
+    moving_average,
+    seen_extreme_binding,
+    key_binding,
+    changed_flag,
+    multi_watcher,
+    transition,
+    notify_debounced,
+    rate_binding,
+    time_since_change,
+}
+
+use core::ops::{RangeInclusive, Sub};
 
 ///
 /// Creates a simple bound value with the specified initial value
@@ -189,6 +324,105 @@ pub fn bind<Value: Clone+PartialEq>(val: Value) -> Binding<Value> {
     Binding::new(val)
 }
 
+///
+/// Creates a simple bound value initialised with the default value for its type
+///
+pub fn bind_default<Value: Default+Clone+PartialEq>() -> Binding<Value> {
+    Binding::new(Value::default())
+}
+
+///
+/// Creates a bound value that notifies once per read-gap
+///
+/// Once the binding has notified that it has changed, no further notifications are sent until its value is
+/// read again, so any number of `set()` calls between two reads generate a single notification.
+///
+pub fn bind_deferred<Value: Clone+PartialEq>(val: Value) -> Binding<Value> {
+    Binding::new_deferred(val)
+}
+
+///
+/// Creates a bound value that passes every new value through a validation function before storing it
+///
+/// The validation function returns the value that should actually be stored: for example, it might clamp
+/// the value to a range or round it to a particular precision.
+///
+pub fn bind_validated<Value, TFn>(val: Value, validate: TFn) -> ValidatedBinding<Value>
+where Value: 'static+Clone+PartialEq+Send, TFn: 'static+Send+Sync+Fn(Value) -> Value {
+    ValidatedBinding::new(val, validate)
+}
+
+///
+/// Creates a binding that reads and writes a single field of the value stored in another binding
+///
+/// `get_field` reads the field from the value of the source binding, and `set_field` writes a new value for the field
+/// back into it. Setting the returned binding updates the field in the source binding in a single operation.
+///
+pub fn lens<Value, Field, GetFn, SetFn>(source: &Binding<Value>, get_field: GetFn, set_field: SetFn) -> LensBinding<Value, Field>
+where
+    Value: 'static+Clone+PartialEq+Send,
+    Field: 'static+Clone+PartialEq+Send,
+    GetFn: 'static+Send+Sync+Fn(&Value) -> Field,
+    SetFn: 'static+Send+Sync+Fn(&mut Value, Field),
+{
+    LensBinding::new(source, get_field, set_field)
+}
+
+///
+/// Creates a bound value that remembers up to `max_history` of the values it has been set to
+///
+/// The past values can be read with `history()`, and `restore()` sets the binding back to one of them, which makes
+/// it simple to implement undo for a value.
+///
+pub fn bind_recorded<Value>(val: Value, max_history: usize) -> RecordedBinding<Value>
+where Value: 'static+Clone+PartialEq+Send {
+    RecordedBinding::new(val, max_history)
+}
+
+///
+/// Creates a binding with a working value that can be edited and a committed value that is only updated by `commit()`
+///
+/// `rollback()` discards the edits by copying the committed value back to the working value, and `is_dirty()` returns a
+/// binding that is true while there are edits that haven't been committed.
+///
+pub fn editable_binding<Value>(initial_value: Value) -> EditableBinding<Value>
+where Value: 'static+Clone+PartialEq+Send {
+    EditableBinding::new(initial_value)
+}
+
+///
+/// Creates a binding backed by a value that is shared with code that doesn't use bindings
+///
+/// Setting the binding updates the shared value and notifies anything that depends on it. If the shared value is changed
+/// directly, call `notify_changed()` on the binding to announce the change.
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+pub fn bind_shared<Value>(value: std::sync::Arc<std::sync::Mutex<Value>>) -> SharedBinding<Value>
+where Value: 'static+Clone+PartialEq+Send {
+    SharedBinding::new(value)
+}
+
+///
+/// Creates a bound value that treats any new value within `epsilon` of its current value as unchanged
+///
+/// This is useful for floating-point values from sources such as sensors or animations, where changes too small
+/// to matter would otherwise cause a notification and recalculate everything that depends on the binding.
+///
+pub fn bind_approx<Value>(val: Value, epsilon: Value) -> ApproxBinding<Value>
+where Value: 'static+Copy+PartialOrd+Sub<Output=Value>+Send+Sync {
+    ApproxBinding::new(val, epsilon)
+}
+
+///
+/// Creates a bound value that is always clamped to the specified range
+///
+pub fn bind_clamped<Value>(val: Value, range: RangeInclusive<Value>) -> ValidatedBinding<Value>
+where Value: 'static+Clone+Ord+Send+Sync {
+    let (min, max) = range.into_inner();
+
+    ValidatedBinding::new(val, move |val| val.clamp(min.clone(), max.clone()))
+}
+
 ///
 /// Creates a computed value that tracks bindings accessed during the function call and marks itself as changed when any of these dependencies also change
 ///
@@ -197,15 +431,51 @@ where Value: Clone+Send, TFn: 'static+Send+Sync+Fn() -> Value {
     ComputedBinding::new(calculate_value)
 }
 
+///
+/// Creates a computed value that tracks only the bindings accessed during the first call to its function
+///
+/// This is useful when the function has data-dependent control flow but always needs to be notified about
+/// the same set of bindings: a `computed()` binding would stop tracking a binding if a branch that reads it
+/// is not taken.
+///
+pub fn computed_static_deps<Value, TFn>(calculate_value: TFn) -> ComputedBinding<Value, TFn>
+where Value: Clone+Send, TFn: 'static+Send+Sync+Fn() -> Value {
+    ComputedBinding::new_with_static_dependencies(calculate_value)
+}
+
+///
+/// Creates a computed value that only notifies when its value is different from the last value it calculated
+///
+/// A `computed()` binding notifies whenever any of its dependencies change, even if the new value turns out to be
+/// the same. This recalculates the value when a dependency changes (provided something is waiting for notifications)
+/// so the notification can be skipped when the value is unchanged.
+///
+pub fn computed_distinct<Value, TFn>(calculate_value: TFn) -> ComputedBinding<Value, TFn>
+where Value: Clone+Send+PartialEq, TFn: 'static+Send+Sync+Fn() -> Value {
+    ComputedBinding::new_distinct(calculate_value)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use super::binding_context::*;
 
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use alloc::boxed::Box;
     use std::thread;
     use std::sync::*;
     use std::time::Duration;
 
+    fn assert_send_sync<T: Send+Sync>() { }
+
+    #[test]
+    fn bindings_are_send_and_sync() {
+        assert_send_sync::<Binding<i32>>();
+        assert_send_sync::<BindRef<i32>>();
+        assert_send_sync::<ComputedBinding<i32, fn() -> i32>>();
+    }
+
     #[test]
     fn can_create_binding() {
         let bound = bind(1);
@@ -220,6 +490,401 @@ mod test {
         assert!(bound.get() == 2);
     }
 
+    #[test]
+    fn generation_increases_on_change() {
+        let bound = bind(1);
+        assert!(bound.get_versioned() == (1, 0));
+
+        bound.set(2);
+        assert!(bound.get_versioned() == (2, 1));
+
+        // Setting the same value doesn't start a new generation
+        bound.set(2);
+        assert!(bound.generation() == 1);
+
+        bound.with_mut(|val| { *val = 3; true });
+        assert!(bound.get_versioned() == (3, 2));
+    }
+
+    #[test]
+    fn swap_bindings() {
+        let a           = bind(1);
+        let b           = bind(2);
+        let changed_a   = bind(0);
+        let changed_b   = bind(0);
+
+        let notify_a    = changed_a.clone();
+        let notify_b    = changed_b.clone();
+        a.when_changed(notify(move || notify_a.set(notify_a.get()+1))).keep_alive();
+        b.when_changed(notify(move || notify_b.set(notify_b.get()+1))).keep_alive();
+
+        Binding::swap(&a, &b);
+
+        assert!(a.get() == 2);
+        assert!(b.get() == 1);
+        assert!(changed_a.get() == 1);
+        assert!(changed_b.get() == 1);
+
+        // Swapping a binding with itself does nothing
+        Binding::swap(&a, &a.clone());
+        assert!(a.get() == 2);
+        assert!(changed_a.get() == 1);
+    }
+
+    #[test]
+    fn swap_bindings_from_several_threads() {
+        let a = bind(1);
+        let b = bind(2);
+
+        let threads = (0..4).map(|idx| {
+            let (a, b) = (a.clone(), b.clone());
+
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    // Swap in both orders to check that the locks are always taken in a consistent order
+                    if idx % 2 == 0 { Binding::swap(&a, &b) } else { Binding::swap(&b, &a) }
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        threads.into_iter().for_each(|thread| thread.join().unwrap());
+
+        // 4000 swaps leaves the values where they started
+        assert!(a.get() == 1);
+        assert!(b.get() == 2);
+    }
+
+    #[test]
+    fn forked_binding_is_independent() {
+        let original    = bind(1);
+        let fork        = original.fork();
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        original.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        assert!(fork.get() == 1);
+
+        // Changing the fork leaves the original alone
+        fork.set(2);
+        assert!(original.get() == 1);
+        assert!(changed.get() == 0);
+
+        // Changing the original leaves the fork alone
+        original.set(3);
+        assert!(fork.get() == 2);
+        assert!(changed.get() == 1);
+    }
+
+    #[test]
+    fn computed_does_not_depend_on_forked_binding() {
+        let original        = bind(1);
+        let original_copy   = original.clone();
+        let fork            = computed(move || original_copy.fork().get() * 2);
+
+        assert!(fork.get() == 2);
+
+        // The computed binding read a fork, not the original, so it isn't updated
+        original.set(2);
+        assert!(fork.get() == 2);
+    }
+
+    #[test]
+    fn default_binding_has_default_value() {
+        let bound = bind_default::<Vec<i32>>();
+
+        assert!(bound.get().is_empty());
+
+        bound.set(vec![1]);
+        assert!(bound.get() == vec![1]);
+    }
+
+    #[test]
+    fn arithmetic_assignment_updates_binding() {
+        let mut counter = bind(1);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        counter.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        counter += 2;
+        assert!(counter.get() == 3);
+
+        counter *= 4;
+        assert!(counter.get() == 12);
+
+        counter -= 5;
+        assert!(counter.get() == 7);
+        assert!(changed.get() == 3);
+
+        // Operations that leave the value unchanged don't notify
+        counter += 0;
+        assert!(changed.get() == 3);
+    }
+
+    #[test]
+    fn add_assign_from_many_threads() {
+        let counter = bind(0);
+
+        let threads = (0..4).map(|_| {
+            let mut counter = counter.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    counter += 1;
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        threads.into_iter().for_each(|thread| thread.join().unwrap());
+        assert!(counter.get() == 4000);
+    }
+
+    #[test]
+    fn compare_and_set_only_sets_expected_value() {
+        let state   = bind("Idle");
+        let changed = bind(0);
+
+        let notify_changed = changed.clone();
+        state.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        assert!(state.compare_and_set("Idle", "Running"));
+        assert!(state.get() == "Running");
+
+        assert!(!state.compare_and_set("Idle", "Stopped"));
+        assert!(state.get() == "Running");
+        assert!(changed.get() == 1);
+    }
+
+    #[test]
+    fn compare_and_set_from_many_threads() {
+        let state       = bind(0);
+        let successes   = Arc::new(Mutex::new(0));
+
+        // Every thread tries to move the state from 0 to its own ID, but only one should succeed
+        let threads = (1..=8).map(|id| {
+            let state       = state.clone();
+            let successes   = Arc::clone(&successes);
+
+            thread::spawn(move || {
+                if state.compare_and_set(0, id) {
+                    *successes.lock().unwrap() += 1;
+                }
+            })
+        }).collect::<Vec<_>>();
+
+        threads.into_iter().for_each(|thread| thread.join().unwrap());
+
+        assert!(*successes.lock().unwrap() == 1);
+        assert!(state.get() != 0);
+    }
+
+    #[test]
+    fn set_notifies_before_returning() {
+        let bound       = bind(1);
+        let notified    = Arc::new(Mutex::new(false));
+
+        let notified_copy = Arc::clone(&notified);
+        bound.when_changed(notify(move || *notified_copy.lock().unwrap() = true)).keep_alive();
+
+        bound.set(2);
+        assert!(*notified.lock().unwrap());
+    }
+
+    #[test]
+    fn notifications_are_in_subscription_order_across_clones() {
+        let bound       = bind(1);
+        let clones      = [bound.clone(), bound.clone(), bound.clone()];
+        let order       = Arc::new(Mutex::new(vec![]));
+
+        // Subscribe alternately through the different clones
+        let mut lifetimes = (0..6).map(|idx| {
+            let order = Arc::clone(&order);
+            clones[idx % 3].when_changed(notify(move || order.lock().unwrap().push(idx)))
+        }).collect::<Vec<_>>();
+
+        clones[1].set(2);
+        assert!(*order.lock().unwrap() == vec![0, 1, 2, 3, 4, 5]);
+
+        // Releasing a subscriber doesn't change the order of the others
+        lifetimes[2].done();
+        order.lock().unwrap().clear();
+
+        bound.set(3);
+        assert!(*order.lock().unwrap() == vec![0, 1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn can_subscribe_through_reference() {
+        fn count_changes(item: impl Changeable, count: Binding<i32>) -> Box<dyn Releasable> {
+            item.when_changed(notify(move || count.set(count.get()+1)))
+        }
+
+        let bound       = bind(1);
+        let changed     = bind(0);
+
+        let mut lifetime = count_changes(&bound, changed.clone());
+        lifetime.keep_alive();
+
+        bound.set(2);
+        assert!(changed.get() == 1);
+        assert!(bound.get() == 2);
+    }
+
+    #[test]
+    fn deferred_binding_notifies_once_per_read() {
+        let bound       = bind_deferred(1);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        // Many sets without a read only notify once
+        for value in 2..10 {
+            bound.set(value);
+        }
+        assert!(changed.get() == 1);
+
+        // Reading the value picks up the final value and re-arms the notification
+        assert!(bound.get() == 9);
+
+        bound.set(10);
+        bound.set(11);
+        assert!(changed.get() == 2);
+        assert!(bound.get() == 11);
+    }
+
+    #[test]
+    fn deferred_binding_notifies_subscriber_added_after_unobserved_change() {
+        let bound       = bind_deferred(1);
+        let changed     = bind(0);
+
+        // Nothing is notified about this change, so it shouldn't stop the next change from notifying
+        bound.set(2);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        bound.set(3);
+        assert!(changed.get() == 1);
+    }
+
+    #[test]
+    fn deferred_binding_ignores_unchanged_values() {
+        let bound       = bind_deferred(1);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        // Setting the same value doesn't use up the notification
+        bound.set(1);
+        assert!(changed.get() == 0);
+
+        bound.set(2);
+        bound.set(3);
+        assert!(changed.get() == 1);
+    }
+
+    #[test]
+    fn deferred_binding_notifies_computed_once_per_read() {
+        let bound       = bind_deferred(1);
+        let bound_copy  = bound.clone();
+        let doubled     = computed(move || bound_copy.get() * 2);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        doubled.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+        assert!(doubled.get() == 2);
+
+        for value in 2..10 {
+            bound.set(value);
+        }
+        assert!(changed.get() == 1);
+
+        // The computed binding reads the deferred binding, which re-arms it
+        assert!(doubled.get() == 18);
+
+        bound.set(10);
+        bound.set(11);
+        assert!(changed.get() == 2);
+        assert!(doubled.get() == 22);
+    }
+
+    #[test]
+    fn paused_binding_notifies_once_when_resumed() {
+        let bound       = bind(1);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        let paused = bound.pause_notifications();
+        for value in 2..10 {
+            bound.set(value);
+        }
+
+        // The value is updated but nothing is notified until the guard is dropped
+        assert!(bound.get() == 9);
+        assert!(changed.get() == 0);
+
+        drop(paused);
+        assert!(changed.get() == 1);
+
+        // Notifications work as normal afterwards
+        bound.set(10);
+        assert!(changed.get() == 2);
+    }
+
+    #[test]
+    fn paused_binding_without_changes_does_not_notify() {
+        let bound       = bind(1);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        let paused = bound.pause_notifications();
+        bound.set(1);
+        drop(paused);
+
+        assert!(changed.get() == 0);
+    }
+
+    #[test]
+    fn paused_binding_restored_to_original_value_does_not_notify() {
+        let bound       = bind(1);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        let paused = bound.pause_notifications();
+        bound.set(2);
+        bound.set(1);
+        drop(paused);
+
+        assert!(changed.get() == 0);
+    }
+
+    #[test]
+    fn nested_pauses_notify_when_last_guard_dropped() {
+        let bound       = bind(1);
+        let changed     = bind(0);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(notify_changed.get()+1))).keep_alive();
+
+        let outer = bound.pause_notifications();
+        let inner = bound.pause_notifications();
+        bound.set(2);
+
+        drop(inner);
+        assert!(changed.get() == 0);
+
+        drop(outer);
+        assert!(changed.get() == 1);
+    }
+
     #[test]
     fn notified_on_change() {
         let bound       = bind(1);
@@ -354,6 +1019,86 @@ mod test {
         assert!(changed.get() == true);
     }
 
+    #[test]
+    fn binding_context_subscribes_to_each_binding_once() {
+        struct CountSubscriptions(Arc<Mutex<usize>>);
+
+        impl Changeable for CountSubscriptions {
+            fn when_changed(&self, _what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+                *self.0.lock().unwrap() += 1;
+                Box::new(vec![])
+            }
+
+            fn identity(&self) -> Option<usize> {
+                Some(Arc::as_ptr(&self.0) as usize)
+            }
+        }
+
+        let subscriptions   = Arc::new(Mutex::new(0));
+        let (_, context)    = BindingContext::bind(|| {
+            for _ in 0..3 {
+                BindingContext::add_dependency(CountSubscriptions(Arc::clone(&subscriptions)));
+            }
+        });
+
+        // One subscription is made when the dependency is added, and one when something subscribes to the context
+        assert!(*subscriptions.lock().unwrap() == 1);
+
+        let _lifetime = context.when_changed(notify(|| { }));
+        assert!(*subscriptions.lock().unwrap() == 2);
+    }
+
+    #[test]
+    fn computed_reading_binding_repeatedly_notifies_once() {
+        let bound       = bind(1);
+        let bound_copy  = bound.clone();
+        let sum         = computed(move || bound_copy.get() + bound_copy.get() + bound_copy.get());
+        let changes     = bind(0);
+
+        let notify_changes = changes.clone();
+        assert!(sum.get() == 3);
+        sum.when_changed(notify(move || notify_changes.set(notify_changes.get()+1))).keep_alive();
+
+        bound.set(2);
+        assert!(changes.get() == 1);
+        assert!(sum.get() == 6);
+    }
+
+    #[test]
+    fn binding_context_subscribes_to_wrapped_bindings_once() {
+        let approx          = bind_approx(1.0, 0.01);
+        let clamped         = bind_clamped(1, 0..=10);
+        let (_, context)    = BindingContext::bind(|| {
+            for _ in 0..2 {
+                BindingContext::add_dependency(approx.clone());
+                BindingContext::add_dependency(clamped.clone());
+            }
+        });
+
+        let notifications   = Arc::new(Mutex::new(0));
+        let count           = Arc::clone(&notifications);
+        let _lifetime       = context.when_changed(notify(move || *count.lock().unwrap() += 1));
+
+        approx.set(2.0);
+        assert!(*notifications.lock().unwrap() == 1);
+
+        clamped.set(5);
+        assert!(*notifications.lock().unwrap() == 2);
+    }
+
+    #[test]
+    fn can_compute_value_with_macro() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let sum     = computed!([a, b] => a.get() + b.get());
+
+        assert!(sum.get() == 3);
+
+        a.set(3);
+        b.set(4);
+        assert!(sum.get() == 7);
+    }
+
     #[test]
     fn can_compute_value() {
         let bound           = bind(1);
@@ -605,6 +1350,83 @@ mod test {
         assert!(computed.get() == 5);
     }
 
+    #[test]
+    fn computed_distinct_only_notifies_for_new_values() {
+        let value           = bind(1);
+
+        let computed_value  = value.clone();
+        let is_odd          = computed_distinct(move || computed_value.get() % 2);
+
+        let changed         = bind(0);
+        let notify_changed  = changed.clone();
+        is_odd.when_changed(notify(move || notify_changed.set(notify_changed.get() + 1))).keep_alive();
+
+        assert!(is_odd.get() == 1);
+
+        // The value is recalculated, but it's the same so there's no notification
+        value.set(3);
+        assert!(changed.get() == 0);
+        assert!(is_odd.get() == 1);
+
+        value.set(4);
+        assert!(changed.get() == 1);
+        assert!(is_odd.get() == 0);
+
+        // Still notifies for changes after a change that was suppressed
+        value.set(6);
+        value.set(7);
+        assert!(changed.get() == 2);
+        assert!(is_odd.get() == 1);
+    }
+
+    #[test]
+    fn computed_static_deps_keeps_first_dependencies() {
+        let switch          = bind(false);
+        let val1            = bind(1);
+        let val2            = bind(2);
+
+        let computed_switch = switch.clone();
+        let computed_val1   = val1.clone();
+        let computed_val2   = val2.clone();
+        let computed        = computed_static_deps(move || {
+            // Only switch and val1 are read during the first evaluation
+            if computed_switch.get() {
+                computed_val2.get() + 1
+            } else {
+                computed_val1.get() + 1
+            }
+        });
+
+        let changed         = bind(false);
+        let notify_changed  = changed.clone();
+        computed.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        assert!(computed.get() == 2);
+        assert!(!changed.get());
+
+        // Flicking the switch uses val2
+        switch.set(true);
+        assert!(changed.get());
+        assert!(computed.get() == 3);
+
+        // val2 was not read the first time, so it isn't tracked
+        changed.set(false);
+        val2.set(4);
+        assert!(!changed.get());
+        assert!(computed.get() == 3);
+
+        // val1 is still tracked even though it's no longer read
+        val1.set(5);
+        assert!(changed.get());
+        assert!(computed.get() == 5);
+
+        // The dependencies remain in place after several recalculations
+        changed.set(false);
+        switch.set(false);
+        assert!(changed.get());
+        assert!(computed.get() == 6);
+    }
+
     #[test]
     fn change_during_computation_recomputes() {
         // Create a computed binding that delays for a bit while reading
@@ -0,0 +1,56 @@
+///
+/// Creates a computed binding that captures clones of a list of bindings
+///
+/// This saves having to clone each binding before moving it into the function that calculates the value. The
+/// bindings are cloned, so the originals can still be used after the computed binding is created:
+///
+/// ```
+/// # use flo_binding::*;
+///     let a       = bind(1);
+///     let b       = bind(2);
+///     let sum     = computed!([a, b] => a.get() + b.get());
+///
+///     let value   = sum.get();    // == 3
+/// # assert!(value == 3);
+///     a.set(3);
+///     let value   = sum.get();    // == 5
+/// # assert!(value == 5);
+/// ```
+///
+#[macro_export]
+macro_rules! computed {
+    ([$($binding:ident),* $(,)?] => $calculate_value:expr) => {{
+        $( let $binding = $binding.clone(); )*
+        $crate::computed(move || $calculate_value)
+    }};
+}
+
+///
+/// Creates a binding of a struct whose fields are read from a set of other bindings
+///
+/// Each field is set to the value of the binding following it, and the struct is rebuilt whenever any of these
+/// bindings change. As with `computed!`, the bindings are cloned so the originals can still be used:
+///
+/// ```
+/// # use flo_binding::*;
+///     #[derive(Clone)]
+///     struct Label { text: String, size: u32 }
+///
+///     let label_text  = bind("Hello".to_string());
+///     let label_size  = bind(12);
+///     let label       = bind_struct!(Label { text: label_text, size: label_size });
+///
+///     let value       = label.get();  // == Label { text: "Hello", size: 12 }
+/// # assert!(value.text == "Hello" && value.size == 12);
+///     label_size.set(14);
+///     let value       = label.get();  // == Label { text: "Hello", size: 14 }
+/// # assert!(value.size == 14);
+/// ```
+///
+#[macro_export]
+macro_rules! bind_struct {
+    ($($struct_name:ident)::+ { $($field:ident : $binding:expr),* $(,)? }) => {{
+        $( let $field = $binding.clone(); )*
+        $crate::BindRef::new(&$crate::computed(move || $($struct_name)::+ { $( $field: $crate::Bound::get(&$field) ),* }))
+    }};
+}
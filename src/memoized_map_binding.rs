@@ -0,0 +1,81 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+use super::sync::*;
+
+///
+/// Creates a binding that maps the value of another binding, only calling the mapping function when the value is different
+/// from the last one that was mapped
+///
+/// Computed bindings are recalculated whenever something they depend on changes, even if the value they produce is the
+/// same as before. This keeps the last value and its mapping, and reuses the mapping when the source binding produces an
+/// equal value, which avoids running expensive mapping functions when nothing they depend on has actually changed.
+///
+pub fn memoized_map_binding<Value, Mapped, TFn>(binding: BindRef<Value>, map_fn: TFn) -> BindRef<Mapped>
+where
+Value:  'static+Clone+Send+PartialEq,
+Mapped: 'static+Clone+Send+PartialEq,
+TFn:    'static+Send+Sync+Fn(&Value) -> Mapped {
+    // The last value that was mapped, and the result of mapping it
+    let last_mapped: Mutex<Option<(Value, Mapped)>> = Mutex::new(None);
+
+    let mapped      = ComputedBinding::new(move || {
+        let value           = binding.get();
+        let mut last_mapped = last_mapped.lock().unwrap();
+
+        match &*last_mapped {
+            Some((last_value, mapped)) if *last_value == value => mapped.clone(),
+
+            _ => {
+                let mapped      = map_fn(&value);
+                *last_mapped    = Some((value, mapped.clone()));
+
+                mapped
+            }
+        }
+    });
+
+    BindRef::from(mapped)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn maps_values() {
+        let number  = bind(2);
+        let doubled = memoized_map_binding(BindRef::new(&number), |number| number * 2);
+
+        assert!(doubled.get() == 4);
+
+        number.set(3);
+        assert!(doubled.get() == 6);
+    }
+
+    #[test]
+    fn does_not_map_equal_values_again() {
+        let number      = bind(2);
+        let number_copy = number.clone();
+        let halved      = computed(move || number_copy.get() / 2);
+
+        let map_count   = Arc::new(AtomicUsize::new(0));
+        let count_copy  = Arc::clone(&map_count);
+        let mapped      = memoized_map_binding(BindRef::from(halved), move |value| { count_copy.fetch_add(1, Ordering::SeqCst); value + 1 });
+
+        assert!(mapped.get() == 2);
+        assert!(map_count.load(Ordering::SeqCst) == 1);
+
+        // 3/2 is the same as 2/2, so the mapping function isn't called again
+        number.set(3);
+        assert!(mapped.get() == 2);
+        assert!(map_count.load(Ordering::SeqCst) == 1);
+
+        number.set(4);
+        assert!(mapped.get() == 3);
+        assert!(map_count.load(Ordering::SeqCst) == 2);
+    }
+}
@@ -0,0 +1,139 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+use super::source_monitor::*;
+
+use std::sync::*;
+
+///
+/// Creates a binding that takes its value from whichever of a set of bindings was most recently changed
+///
+/// The merged binding initially has the value of the first binding in the list. This will panic if the list of
+/// bindings is empty.
+///
+pub fn merge<Value>(bindings: Vec<BindRef<Value>>) -> BindRef<Value>
+where Value: 'static+Clone+Send+PartialEq {
+    assert!(!bindings.is_empty(), "Cannot merge an empty list of bindings");
+
+    // The index of the binding that was most recently changed
+    let last_changed    = Arc::new(Mutex::new(0));
+
+    // Update the index whenever a binding changes. These are registered before the computed binding's dependencies so
+    // the index will always be updated before the merged binding is notified of the change
+    let change_monitors = bindings.iter()
+        .enumerate()
+        .map(|(idx, binding)| {
+            let last_changed = Arc::clone(&last_changed);
+            watch_source(binding.clone(), move |_| *last_changed.lock().unwrap() = idx)
+        })
+        .collect::<Vec<_>>();
+    let change_monitors = Mutex::new(change_monitors);
+
+    let merged          = ComputedBinding::new(move || {
+        // The change monitors are released when the merged binding is dropped
+        let _change_monitors = &change_monitors;
+
+        // Read every binding so they all notify when they're next changed
+        let mut values  = bindings.iter().map(|binding| binding.get()).collect::<Vec<_>>();
+        let last        = *last_changed.lock().unwrap();
+
+        values.swap_remove(last)
+    });
+
+    BindRef::from(merged)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn merge_starts_with_first_value() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let merged  = merge(vec![BindRef::new(&a), BindRef::new(&b)]);
+
+        assert!(merged.get() == 1);
+    }
+
+    #[test]
+    fn merge_follows_most_recent_change() {
+        let a       = bind(1);
+        let b       = bind(2);
+        let c       = bind(3);
+        let merged  = merge(vec![BindRef::new(&a), BindRef::new(&b), BindRef::new(&c)]);
+
+        assert!(merged.get() == 1);
+
+        b.set(4);
+        assert!(merged.get() == 4);
+
+        c.set(5);
+        assert!(merged.get() == 5);
+
+        a.set(6);
+        assert!(merged.get() == 6);
+
+        b.set(7);
+        assert!(merged.get() == 7);
+    }
+
+    #[test]
+    fn merge_follows_computed_bindings() {
+        let a           = bind(1);
+        let b           = bind(2);
+        let a_copy      = a.clone();
+        let doubled_a   = computed(move || a_copy.get() * 2);
+        let merged      = merge(vec![BindRef::from(doubled_a), BindRef::new(&b)]);
+
+        assert!(merged.get() == 2);
+
+        b.set(3);
+        assert!(merged.get() == 3);
+
+        a.set(4);
+        assert!(merged.get() == 8);
+
+        b.set(5);
+        assert!(merged.get() == 5);
+
+        a.set(6);
+        assert!(merged.get() == 12);
+    }
+
+    #[test]
+    fn merge_follows_computed_bindings_that_change_without_being_read() {
+        let a           = bind(1);
+        let b           = bind(2);
+        let a_copy      = a.clone();
+        let doubled_a   = computed(move || a_copy.get() * 2);
+        let merged      = merge(vec![BindRef::from(doubled_a), BindRef::new(&b)]);
+
+        assert!(merged.get() == 2);
+
+        // The merged binding is only read at the end, so the computed binding is not read by it in between
+        a.set(3);
+        b.set(4);
+        a.set(5);
+        assert!(merged.get() == 10);
+    }
+
+    #[test]
+    fn merge_notifies_on_change() {
+        let a           = bind(1);
+        let b           = bind(2);
+        let merged      = merge(vec![BindRef::new(&a), BindRef::new(&b)]);
+
+        let changed     = bind(false);
+        let notify_changed = changed.clone();
+        merged.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        assert!(merged.get() == 1);
+        assert!(!changed.get());
+
+        b.set(3);
+        assert!(changed.get());
+        assert!(merged.get() == 3);
+    }
+}
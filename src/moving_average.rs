@@ -0,0 +1,106 @@
+use super::traits::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::collections::{VecDeque};
+
+///
+/// The most recent values of the source binding for a moving average
+///
+struct AverageWindow {
+    /// The values in the window, oldest first
+    values: VecDeque<f64>,
+
+    /// The sum of the values in the window
+    sum: f64,
+
+    /// The maximum number of values in the window
+    window_size: usize,
+}
+
+impl AverageWindow {
+    ///
+    /// Adds a value to the window, removing the oldest value if the window is full, and returns the new average
+    ///
+    fn add(&mut self, value: f64) -> f64 {
+        if self.values.len() >= self.window_size {
+            if let Some(evicted) = self.values.pop_front() {
+                self.sum -= evicted;
+            }
+        }
+
+        self.values.push_back(value);
+        self.sum += value;
+
+        self.sum / (self.values.len() as f64)
+    }
+}
+
+///
+/// Creates a binding to the average of the most recent `window_size` values of another binding
+///
+/// The initial value of the source binding is the first value in the window, and a new value is added every time the source
+/// changes. Until the source has changed `window_size - 1` times, the average is of the values seen so far. This is useful
+/// for smoothing a noisy input, such as a frame rate or a sensor reading.
+///
+pub fn moving_average<Value>(source: BindRef<Value>, window_size: usize) -> BindRef<f64>
+where
+Value: 'static+Clone+Send+Into<f64> {
+    let mut window      = AverageWindow { values: VecDeque::new(), sum: 0.0, window_size: window_size.max(1) };
+    let initial_average = window.add(source.get().into());
+
+    // Add to the window whenever the source changes
+    bind_to_source_with_state(source, window, initial_average, |window, new_value: Value| Some(window.add(new_value.into())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn averages_recent_values() {
+        let value   = bind(2.0);
+        let average = moving_average(BindRef::new(&value), 3);
+
+        assert!(average.get() == 2.0);
+
+        value.set(4.0);
+        assert!(average.get() == 3.0);
+
+        value.set(6.0);
+        assert!(average.get() == 4.0);
+
+        // The oldest value leaves the window
+        value.set(8.0);
+        assert!(average.get() == 6.0);
+    }
+
+    #[test]
+    fn averages_integer_values() {
+        let value   = bind(1);
+        let average = moving_average(BindRef::new(&value), 2);
+
+        value.set(2);
+        assert!(average.get() == 1.5);
+
+        value.set(4);
+        assert!(average.get() == 3.0);
+    }
+
+    #[test]
+    fn averages_computed_values() {
+        let value   = bind(1);
+        let copy    = value.clone();
+        let doubled = computed(move || copy.get() * 2);
+        let average = moving_average(BindRef::from(doubled), 2);
+
+        assert!(average.get() == 2.0);
+
+        value.set(2);
+        assert!(average.get() == 3.0);
+
+        value.set(3);
+        assert!(average.get() == 5.0);
+    }
+}
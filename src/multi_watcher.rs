@@ -0,0 +1,140 @@
+use super::traits::*;
+use super::bindref::*;
+use super::notify_fn::*;
+use super::binding_context::*;
+
+use std::mem;
+use std::sync::*;
+
+///
+/// Reads a group of bindings together, and sends a single notification when any of them changes after they were read
+///
+/// This is useful for something like a render loop, which reads several related bindings at once and only needs to know
+/// that something has changed since the last time it read them. The notification is sent once after each call to
+/// `get_all()`: further changes are not reported until the values are read again. Nothing is notified before the first
+/// call to `get_all()`.
+///
+pub struct MultiWatcher<Value> {
+    /// The bindings that are being watched
+    bindings: Vec<BindRef<Value>>,
+
+    /// True if the bindings have been read since the notification was last sent
+    armed: Arc<Mutex<bool>>,
+
+    /// The notifications for the watched bindings (released when the watcher is dropped)
+    _monitors: Vec<Box<dyn Releasable>>,
+}
+
+impl<Value: 'static+Clone> MultiWatcher<Value> {
+    ///
+    /// Creates a new watcher for a group of bindings, which will notify `what` when they change after `get_all()` is called
+    ///
+    pub fn new(bindings: Vec<BindRef<Value>>, what: Arc<dyn Notifiable>) -> MultiWatcher<Value> {
+        let armed = Arc::new(Mutex::new(false));
+
+        // Notify when any of the bindings change, if the watcher is armed
+        let monitors = bindings.iter()
+            .map(|binding| {
+                let armed   = Arc::clone(&armed);
+                let what    = Arc::clone(&what);
+
+                binding.when_changed(notify(move || {
+                    let was_armed = mem::take(&mut *armed.lock().unwrap());
+
+                    if was_armed {
+                        what.mark_as_changed();
+                    }
+                }))
+            })
+            .collect();
+
+        MultiWatcher {
+            bindings,
+            armed,
+            _monitors: monitors,
+        }
+    }
+
+    ///
+    /// Reads the values of all of the bindings, in the order they were passed to `new()`, and arms the notification
+    ///
+    pub fn get_all(&self) -> Vec<Value> {
+        // The watcher is armed before reading the values, so a change made while they're being read is not missed
+        *self.armed.lock().unwrap() = true;
+
+        // Reading the bindings also means that computed bindings will notify again when they're next changed
+        BindingContext::out_of_context(|| {
+            self.bindings.iter().map(|binding| binding.get()).collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn count_notifications() -> (Arc<AtomicUsize>, Arc<dyn Notifiable>) {
+        let count       = Arc::new(AtomicUsize::new(0));
+        let count_copy  = Arc::clone(&count);
+
+        (count, notify(move || { count_copy.fetch_add(1, Ordering::Relaxed); }))
+    }
+
+    #[test]
+    fn notifies_once_after_reading() {
+        let a               = bind(1);
+        let b               = bind(2);
+        let (count, what)   = count_notifications();
+        let watcher         = MultiWatcher::new(vec![BindRef::new(&a), BindRef::new(&b)], what);
+
+        // Not armed until the values are read
+        a.set(3);
+        assert!(count.load(Ordering::Relaxed) == 0);
+
+        assert!(watcher.get_all() == vec![3, 2]);
+
+        // Only one notification until the values are read again
+        a.set(4);
+        b.set(5);
+        assert!(count.load(Ordering::Relaxed) == 1);
+
+        assert!(watcher.get_all() == vec![4, 5]);
+
+        b.set(6);
+        assert!(count.load(Ordering::Relaxed) == 2);
+    }
+
+    #[test]
+    fn watches_computed_bindings() {
+        let a               = bind(1);
+        let a_copy          = a.clone();
+        let doubled         = computed(move || a_copy.get() * 2);
+        let (count, what)   = count_notifications();
+        let watcher         = MultiWatcher::new(vec![BindRef::from(doubled)], what);
+
+        assert!(watcher.get_all() == vec![2]);
+
+        a.set(2);
+        assert!(count.load(Ordering::Relaxed) == 1);
+        assert!(watcher.get_all() == vec![4]);
+
+        a.set(3);
+        assert!(count.load(Ordering::Relaxed) == 2);
+    }
+
+    #[test]
+    fn stops_notifying_when_dropped() {
+        let a               = bind(1);
+        let (count, what)   = count_notifications();
+        let watcher         = MultiWatcher::new(vec![BindRef::new(&a)], what);
+
+        watcher.get_all();
+        drop(watcher);
+
+        a.set(2);
+        assert!(count.load(Ordering::Relaxed) == 0);
+    }
+}
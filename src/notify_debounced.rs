@@ -0,0 +1,254 @@
+use super::traits::*;
+use super::bindref::*;
+use super::releasable::*;
+use super::source_monitor::*;
+use super::binding_context::*;
+use super::timer::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use std::sync::*;
+use std::time::{Duration, Instant};
+
+///
+/// The state of the notifications for a `notify_debounced()` binding
+///
+struct DebounceState {
+    /// When the notifications were last sent
+    last_notified: Option<Instant>,
+
+    /// True if a notification has been scheduled to be sent later
+    scheduled: bool,
+
+    /// What to notify when the binding changes
+    when_changed: Vec<ReleasableNotifiable>,
+}
+
+///
+/// The shared part of a `NotifyDebounced` binding
+///
+struct DebounceCore<Value> {
+    /// The binding that supplies the value
+    source: BindRef<Value>,
+
+    /// The state of the notifications
+    state: Arc<Mutex<DebounceState>>,
+
+    /// The notification attached to the source binding (released when this binding is dropped)
+    change_monitor: Mutex<Box<dyn Releasable>>,
+}
+
+///
+/// A binding that reads its value from another binding but limits how often it notifies about changes
+///
+struct NotifyDebounced<Value> {
+    core: Arc<DebounceCore<Value>>,
+}
+
+impl DebounceState {
+    ///
+    /// Notifies everything that's listening for changes (the state should not be locked when this is called)
+    ///
+    fn notify_all(state: &Mutex<DebounceState>, now: Instant) {
+        let notifications = {
+            let mut state = state.lock().unwrap();
+
+            state.last_notified = Some(now);
+            state.when_changed.retain(|notify| notify.is_in_use());
+            state.when_changed.iter().map(|notify| notify.clone_for_inspection()).collect::<Vec<_>>()
+        };
+
+        for notify in notifications {
+            notify.mark_as_changed();
+        }
+    }
+}
+
+///
+/// Creates a binding with the same value as another binding that limits its notifications to one per `interval`
+///
+/// Reading the binding always returns the latest value of the source binding. If the source changes again less than
+/// `interval` after the last notification was sent, the notification is delayed until the interval has passed, and
+/// any other changes made in the meantime are combined into it. This is useful when some code reads the binding
+/// directly while other code, such as a UI redraw, reacts to its notifications and shouldn't run too often.
+///
+pub fn notify_debounced<Value>(binding: BindRef<Value>, interval: Duration) -> BindRef<Value>
+where Value: 'static+Clone+Send {
+    notify_debounced_with_timer(binding, interval, shared_timer())
+}
+
+///
+/// Creates a `notify_debounced()` binding that schedules its delayed notifications on the specified timer
+///
+fn notify_debounced_with_timer<Value>(binding: BindRef<Value>, interval: Duration, timer: Arc<dyn Timer>) -> BindRef<Value>
+where Value: 'static+Clone+Send {
+    let state = Arc::new(Mutex::new(DebounceState {
+        last_notified:  None,
+        scheduled:      false,
+        when_changed:   vec![],
+    }));
+
+    // Notify immediately if the last notification was long enough ago, otherwise schedule a notification
+    let source          = binding.clone();
+    let change_state    = Arc::clone(&state);
+    let change_monitor  = watch_source(binding, move |_: Value| {
+        let now         = timer.now();
+        let wait_time   = {
+            let mut state = change_state.lock().unwrap();
+
+            if state.scheduled {
+                // This change will be included in the notification that's already scheduled
+                return;
+            }
+
+            let since_last = state.last_notified.map(|last_notified| now.duration_since(last_notified)).unwrap_or(interval);
+            if since_last >= interval {
+                None
+            } else {
+                state.scheduled = true;
+                Some(interval - since_last)
+            }
+        };
+
+        if let Some(wait_time) = wait_time {
+            // Send the notification once the interval has passed, unless the binding is dropped first
+            let weak_state  = Arc::downgrade(&change_state);
+            let notify_time = now + wait_time;
+
+            timer.schedule(notify_time, Box::new(move || {
+                if let Some(state) = weak_state.upgrade() {
+                    state.lock().unwrap().scheduled = false;
+                    DebounceState::notify_all(&state, notify_time);
+                }
+            }));
+        } else {
+            DebounceState::notify_all(&change_state, now);
+        }
+    });
+
+    BindRef::from_arc(Arc::new(NotifyDebounced {
+        core: Arc::new(DebounceCore {
+            source,
+            state,
+            change_monitor: Mutex::new(Box::new(change_monitor)),
+        })
+    }))
+}
+
+impl<Value> Drop for DebounceCore<Value> {
+    fn drop(&mut self) {
+        // Stop following the source binding
+        self.change_monitor.lock().unwrap().done();
+    }
+}
+
+impl<Value> Clone for NotifyDebounced<Value> {
+    fn clone(&self) -> Self {
+        NotifyDebounced {
+            core: Arc::clone(&self.core)
+        }
+    }
+}
+
+impl<Value: 'static+Clone+Send> Changeable for NotifyDebounced<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        let releasable  = ReleasableNotifiable::new(what);
+        let mut state   = self.core.state.lock().unwrap();
+
+        state.when_changed.push(releasable.clone_as_owned());
+        state.when_changed.retain(|notify| notify.is_in_use());
+
+        Box::new(releasable)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.core.state.lock().unwrap().when_changed.retain(|notify| notify.is_in_use());
+        self.core.source.release_unused_notifications();
+    }
+
+    fn identity(&self) -> Option<usize> {
+        Some(Arc::as_ptr(&self.core) as *const () as usize)
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.core.source.binding_id()
+    }
+}
+
+impl<Value: 'static+Clone+Send> Bound<Value> for NotifyDebounced<Value> {
+    fn get(&self) -> Value {
+        // Anything reading this binding should be notified by the debounced notifications rather than by the source
+        BindingContext::add_dependency(self.clone());
+
+        BindingContext::out_of_context(|| self.core.source.get())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn value_is_always_current() {
+        let a           = bind(1);
+        let debounced   = notify_debounced(BindRef::new(&a), Duration::from_millis(500));
+
+        for value in 2..10 {
+            a.set(value);
+            assert!(debounced.get() == value);
+        }
+    }
+
+    #[test]
+    fn notifications_are_combined() {
+        let timer       = ManualTimer::new();
+        let a           = bind(1);
+        let debounced   = notify_debounced_with_timer(BindRef::new(&a), Duration::from_millis(50), timer.clone());
+        let count       = Arc::new(Mutex::new(0));
+
+        let notify_count = Arc::clone(&count);
+        debounced.when_changed(notify(move || *notify_count.lock().unwrap() += 1)).keep_alive();
+
+        // The first change is notified immediately, and the rest are combined into a single later notification
+        for value in 2..10 {
+            a.set(value);
+        }
+        assert!(*count.lock().unwrap() == 1);
+
+        timer.advance(Duration::from_millis(49));
+        assert!(*count.lock().unwrap() == 1);
+
+        timer.advance(Duration::from_millis(1));
+        assert!(*count.lock().unwrap() == 2);
+        assert!(debounced.get() == 9);
+    }
+
+    #[test]
+    fn computed_bindings_follow_debounced_notifications() {
+        let timer       = ManualTimer::new();
+        let a           = bind(1);
+        let debounced   = notify_debounced_with_timer(BindRef::new(&a), Duration::from_millis(50), timer.clone());
+        let computed    = computed(move || debounced.get() * 2);
+        let count       = Arc::new(Mutex::new(0));
+
+        let notify_count = Arc::clone(&count);
+        computed.when_changed(notify(move || *notify_count.lock().unwrap() += 1)).keep_alive();
+        assert!(computed.get() == 2);
+
+        a.set(2);
+        assert!(*count.lock().unwrap() == 1);
+        assert!(computed.get() == 4);
+
+        // The computed binding isn't notified by the source directly, so it only hears about these changes once the interval has passed
+        a.set(3);
+        assert!(computed.get() == 4);
+        a.set(4);
+        assert!(*count.lock().unwrap() == 1);
+
+        timer.advance(Duration::from_millis(50));
+        assert!(*count.lock().unwrap() == 2);
+        assert!(computed.get() == 8);
+    }
+}
@@ -1,6 +1,5 @@
 use super::traits::*;
-
-use std::sync::*;
+use super::sync::*;
 
 struct NotifyFn<TFn> {
     when_changed: Mutex<TFn>
@@ -0,0 +1,92 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+///
+/// Extension methods for projecting a field out of the value of a binding
+///
+pub trait BoundProjectExt<Value> {
+    ///
+    /// Returns a binding to a field of the value in this binding
+    ///
+    /// `project_fn` returns a reference to the field, for example `point.project(|point| &point.x)`. Unlike a computed
+    /// binding that reads the field, this only notifies when the field changes, so changes to other parts of the value
+    /// don't cause anything that depends on the field to be recalculated.
+    ///
+    fn project<Field, ProjectFn>(&self, project_fn: ProjectFn) -> BindRef<Field>
+    where
+    Field:      'static+Clone+Send+PartialEq,
+    ProjectFn:  'static+Send+Sync+Fn(&Value) -> &Field;
+}
+
+impl<Value, TBound> BoundProjectExt<Value> for TBound
+where
+Value:  'static+Clone+Send,
+TBound: 'static+Clone+Bound<Value> {
+    fn project<Field, ProjectFn>(&self, project_fn: ProjectFn) -> BindRef<Field>
+    where
+    Field:      'static+Clone+Send+PartialEq,
+    ProjectFn:  'static+Send+Sync+Fn(&Value) -> &Field {
+        let field           = Binding::new(project_fn(&self.get()).clone());
+
+        // Update the field whenever the source changes. Setting a binding to its current value doesn't notify, so nothing
+        // happens if the field is the same as the cached version.
+        let field_copy      = field.clone();
+        bind_to_source(self.clone(), move |new_value: Value| field_copy.set(project_fn(&new_value).clone()), move || field.get())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[derive(Clone, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[test]
+    fn follows_field() {
+        let point   = bind(Point { x: 1, y: 2 });
+        let x       = point.project(|point| &point.x);
+
+        assert!(x.get() == 1);
+
+        point.set(Point { x: 3, y: 2 });
+        assert!(x.get() == 3);
+    }
+
+    #[test]
+    fn only_notifies_when_field_changes() {
+        let point   = bind(Point { x: 1, y: 2 });
+        let x       = point.project(|point| &point.x);
+        let changed = bind(false);
+
+        let notify_changed = changed.clone();
+        x.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+        assert!(x.get() == 1);
+
+        point.set(Point { x: 1, y: 5 });
+        assert!(!changed.get());
+
+        point.set(Point { x: 4, y: 5 });
+        assert!(changed.get());
+        assert!(x.get() == 4);
+    }
+
+    #[test]
+    fn projects_computed_bindings() {
+        let point       = bind(Point { x: 1, y: 2 });
+        let point_copy  = point.clone();
+        let swapped     = computed(move || { let point = point_copy.get(); Point { x: point.y, y: point.x } });
+        let x           = swapped.project(|point| &point.x);
+
+        assert!(x.get() == 2);
+
+        point.set(Point { x: 1, y: 7 });
+        assert!(x.get() == 7);
+    }
+}
@@ -0,0 +1,159 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::computed::*;
+use super::timer::*;
+use super::binding_context::*;
+
+use std::sync::*;
+use std::collections::{VecDeque};
+use std::time::{Duration, Instant};
+
+///
+/// The number of times the source binding is sampled during each window
+///
+const SAMPLES_PER_WINDOW: u32 = 10;
+
+///
+/// Samples the source of a `rate_binding()` on a timer, and updates the rate
+///
+struct RateSampler<Value> {
+    /// The binding that's being sampled
+    source: BindRef<Value>,
+
+    /// The length of time that the rate is measured over
+    window: Duration,
+
+    /// The time between samples
+    tick_interval: Duration,
+
+    /// The samples that are in the current window, oldest first
+    samples: Mutex<VecDeque<(Instant, f64)>>,
+
+    /// The most recently measured rate
+    rate: Binding<f64>,
+
+    /// The timer that triggers the samples
+    timer: Arc<dyn Timer>,
+}
+
+impl<Value> RateSampler<Value>
+where Value: 'static+Clone+Send+Into<f64> {
+    ///
+    /// Samples the source binding, and updates the rate from the samples in the window
+    ///
+    fn sample(&self) {
+        let now         = self.timer.now();
+        let mut samples = self.samples.lock().unwrap();
+
+        // Sample the source, then discard the samples that have left the window
+        samples.push_back((now, self.source.get().into()));
+        while samples.front().map(|(when, _)| now.duration_since(*when) > self.window).unwrap_or(false) {
+            samples.pop_front();
+        }
+
+        if let (Some((first_time, first_value)), Some((last_time, last_value))) = (samples.front(), samples.back()) {
+            let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+
+            if elapsed > 0.0 {
+                self.rate.set((last_value - first_value) / elapsed);
+            }
+        }
+    }
+
+    ///
+    /// Takes a sample, then schedules the next one, as long as the sampler is still in use
+    ///
+    fn tick(sampler: Weak<RateSampler<Value>>) {
+        let sampler = if let Some(sampler) = sampler.upgrade() { sampler } else { return; };
+
+        sampler.sample();
+
+        let next_tick   = sampler.timer.now() + sampler.tick_interval;
+        let weak        = Arc::downgrade(&sampler);
+        sampler.timer.schedule(next_tick, Box::new(move || Self::tick(weak)));
+    }
+}
+
+///
+/// Creates a binding to the rate at which another binding is changing, in units per second
+///
+/// The source binding is sampled on a timer ten times per `window`, and the rate is the difference between the newest
+/// sample and the oldest sample in the window, divided by the time between them. This is useful for things like turning
+/// a counter into an 'events per second' value for a dashboard. The samples are taken by the timer that's shared with the
+/// other timed bindings, and stop once the returned binding is dropped. The rate is 0 until the source has been sampled twice.
+/// Samples are never taken more often than once per millisecond, however short the window.
+///
+pub fn rate_binding<Value>(source: BindRef<Value>, window: Duration) -> BindRef<f64>
+where Value: 'static+Clone+Send+Into<f64> {
+    rate_binding_with_timer(source, window, shared_timer())
+}
+
+///
+/// Creates a `rate_binding()` that samples its source on the specified timer
+///
+fn rate_binding_with_timer<Value>(source: BindRef<Value>, window: Duration, timer: Arc<dyn Timer>) -> BindRef<f64>
+where Value: 'static+Clone+Send+Into<f64> {
+    let rate    = Binding::new(0.0);
+    let sampler = Arc::new(RateSampler {
+        source,
+        window,
+        tick_interval:  (window / SAMPLES_PER_WINDOW).max(MIN_TICK_INTERVAL),
+        samples:        Mutex::new(VecDeque::new()),
+        rate:           rate.clone(),
+        timer,
+    });
+
+    // The sampler takes the first sample immediately, then keeps sampling until it's dropped (the source is not a dependency of any binding that's creating this one)
+    BindingContext::out_of_context(|| RateSampler::tick(Arc::downgrade(&sampler)));
+
+    let rate    = ComputedBinding::new(move || {
+        // The samples stop when the binding is dropped
+        let _sampler = &sampler;
+
+        rate.get()
+    });
+
+    BindRef::from(rate)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn rate_follows_changes() {
+        let timer   = ManualTimer::new();
+        let counter = bind(0.0);
+        let rate    = rate_binding_with_timer(BindRef::new(&counter), Duration::from_millis(200), timer.clone());
+
+        timer.advance(Duration::from_millis(100));
+        assert!(rate.get() == 0.0);
+
+        // Increase the counter steadily by 10 every 20ms (500 per second)
+        for count in 1..=30 {
+            counter.set((count * 10) as f64);
+            timer.advance(Duration::from_millis(20));
+        }
+
+        let measured = rate.get();
+        assert!((measured - 500.0).abs() < 1e-6, "{}", measured);
+
+        // Once the counter stops changing, the rate drops back to 0 after the window has passed
+        timer.advance(Duration::from_millis(220));
+        assert!(rate.get() == 0.0);
+    }
+
+    #[test]
+    fn zero_window_does_not_stop_the_timer() {
+        let timer   = ManualTimer::new();
+        let counter = bind(0.0);
+        let rate    = rate_binding_with_timer(BindRef::new(&counter), Duration::ZERO, timer.clone());
+
+        // Advancing the timer finishes even though the window is empty
+        counter.set(10.0);
+        timer.advance(Duration::from_millis(10));
+        assert!(rate.get() == 0.0);
+    }
+}
@@ -0,0 +1,168 @@
+use super::traits::*;
+use super::binding::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use alloc::collections::{VecDeque};
+
+///
+/// A binding that keeps a record of the most recent values it has been set to
+///
+/// This can be used for undo and redo of simple values, or for debugging how a value changed over time. Only changes
+/// are recorded: setting the binding to its current value does not add a new entry to the history.
+///
+#[derive(Clone)]
+pub struct RecordedBinding<Value> {
+    /// The binding where the current value is stored
+    binding: Binding<Value>,
+
+    /// The values of the binding, oldest first (the last value is always the current value)
+    history: Arc<Mutex<VecDeque<Value>>>,
+
+    /// The maximum number of values to keep in the history
+    max_history: usize
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> RecordedBinding<Value> {
+    ///
+    /// Creates a new recorded binding, which will remember up to `max_history` values (including its current value)
+    ///
+    pub fn new(initial_value: Value, max_history: usize) -> RecordedBinding<Value> {
+        let max_history = max_history.max(1);
+        let mut history = VecDeque::with_capacity(max_history);
+        history.push_back(initial_value.clone());
+
+        RecordedBinding {
+            binding:    Binding::new(initial_value),
+            history:    Arc::new(Mutex::new(history)),
+            max_history
+        }
+    }
+
+    ///
+    /// Returns the values that this binding has had, oldest first
+    ///
+    /// The last value in the list is the current value of the binding.
+    ///
+    pub fn history(&self) -> Vec<Value> {
+        self.history.lock().unwrap().iter().cloned().collect()
+    }
+
+    ///
+    /// Sets the binding back to a value from its history, returning false if the index is out of range
+    ///
+    /// The index is the same as the index of the value in the list returned by `history()`. Restoring a value is a change
+    /// like any other, so the restored value is also added to the end of the history.
+    ///
+    pub fn restore(&self, index: usize) -> bool {
+        let value = self.history.lock().unwrap().get(index).cloned();
+
+        if let Some(value) = value {
+            self.set(value);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Changeable for RecordedBinding<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.binding.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.binding.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.binding.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.binding.binding_id()
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Bound<Value> for RecordedBinding<Value> {
+    fn get(&self) -> Value {
+        self.binding.get()
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> MutableBound<Value> for RecordedBinding<Value> {
+    fn set(&self, new_value: Value) {
+        let history     = &self.history;
+        let max_history = self.max_history;
+
+        // The history is updated while the binding is locked, so the values are recorded in the order they were set
+        self.binding.with_mut(move |value| {
+            if *value == new_value {
+                return false;
+            }
+
+            let mut history = history.lock().unwrap();
+            if history.len() >= max_history {
+                history.pop_front();
+            }
+            history.push_back(new_value.clone());
+
+            *value = new_value;
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn records_changes() {
+        let value = bind_recorded(1, 10);
+
+        value.set(2);
+        value.set(2);
+        value.set(3);
+
+        assert!(value.history() == vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let value = bind_recorded(0, 3);
+
+        for new_value in 1..10 {
+            value.set(new_value);
+        }
+
+        assert!(value.history() == vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn restore_past_value() {
+        let value           = bind_recorded(1, 10);
+        let changed         = bind(false);
+
+        let notify_changed  = changed.clone();
+        value.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        value.set(2);
+        value.set(3);
+
+        changed.set(false);
+        assert!(value.restore(0));
+        assert!(changed.get());
+        assert!(value.get() == 1);
+        assert!(value.history() == vec![1, 2, 3, 1]);
+
+        assert!(!value.restore(10));
+        assert!(value.get() == 1);
+    }
+}
@@ -1,6 +1,8 @@
 use super::traits::*;
+use super::sync::*;
 
-use std::sync::*;
+use alloc::vec::Vec;
+use alloc::boxed::Box;
 
 // TODO: issue with new 'drop' behaviour is what to do when we clone, as if keep_alive is
 // false on the clone then dropping the clone will also drop this object. Sometimes we
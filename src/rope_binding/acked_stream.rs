@@ -0,0 +1,185 @@
+use crate::rope_binding::core::*;
+use crate::rope_binding::stream::*;
+
+use flo_rope::*;
+use ::desync::*;
+use futures::prelude::*;
+use futures::task::*;
+
+use std::pin::*;
+use std::sync::*;
+use std::collections::{VecDeque};
+
+///
+/// Shared between an acknowledged stream and its acknowledgers: the stream state in the core is removed when this is dropped
+///
+struct AckedStreamHandle<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The core of the rope that's being followed
+    core: Arc<Desync<RopeBindingCore<Cell, Attribute>>>,
+
+    /// The ID of the stream state in the core (this changes when the stream is resumed)
+    stream_id: Mutex<usize>,
+}
+
+///
+/// A stream of the changes to a rope, where each action is returned along with a version number that can be acknowledged
+///
+/// Created by `follow_changes_acked()`. The core of the rope keeps the actions that haven't been acknowledged, and these are
+/// delivered again by the stream returned by `RopeAcknowledger::resume()`, so a consumer that fails part-way through applying
+/// the changes can pick up from the last change it acknowledged.
+///
+pub struct RopeAckedStream<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The changes to the rope (the core sends these one at a time for an acknowledged stream)
+    changes: RopeStream<Cell, Attribute>,
+
+    /// The version of the next action that this stream will return
+    next_version: u64,
+
+    /// The handle for the stream state in the core
+    handle: Arc<AckedStreamHandle<Cell, Attribute>>,
+}
+
+///
+/// Acknowledges the actions returned by a `RopeAckedStream`, and creates new streams that deliver any actions that
+/// weren't acknowledged
+///
+pub struct RopeAcknowledger<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The handle for the stream state in the core
+    handle: Arc<AckedStreamHandle<Cell, Attribute>>,
+}
+
+impl<Cell, Attribute> RopeAckedStream<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Creates a stream that assigns versions to the actions from a stream of changes, and tells the core to keep them until they're acknowledged
+    ///
+    pub (super) fn new(changes: RopeStream<Cell, Attribute>) -> RopeAckedStream<Cell, Attribute> {
+        let stream_id   = changes.identifier;
+        let core        = Arc::clone(&changes.core);
+
+        core.sync(move |core| core.start_acks(stream_id));
+
+        RopeAckedStream {
+            changes,
+            next_version:   0,
+            handle:         Arc::new(AckedStreamHandle {
+                core,
+                stream_id:  Mutex::new(stream_id),
+            }),
+        }
+    }
+
+    ///
+    /// Marks every action up to and including `version` as acknowledged, so they won't be delivered again
+    ///
+    pub fn ack(&self, version: u64) {
+        self.acknowledger().ack(version);
+    }
+
+    ///
+    /// Returns an object that can acknowledge the actions from this stream, and create a new stream if this one fails
+    ///
+    pub fn acknowledger(&self) -> RopeAcknowledger<Cell, Attribute> {
+        RopeAcknowledger {
+            handle: Arc::clone(&self.handle)
+        }
+    }
+}
+
+impl<Cell, Attribute> RopeAcknowledger<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Marks every action up to and including `version` as acknowledged, so they won't be delivered again
+    ///
+    pub fn ack(&self, version: u64) {
+        let stream_id = *self.handle.stream_id.lock().unwrap();
+
+        self.handle.core.desync(move |core| core.ack_stream(stream_id, version));
+    }
+
+    ///
+    /// Creates a new stream that returns the actions that haven't been acknowledged yet, followed by any new changes
+    ///
+    /// The stream that was previously returning the changes ends when this is called.
+    ///
+    pub fn resume(&self) -> RopeAckedStream<Cell, Attribute> {
+        let mut stream_id   = self.handle.stream_id.lock().unwrap();
+        let old_id          = *stream_id;
+
+        // The core moves the stream state to a new ID, which ends the old stream
+        let (new_id, next_version) = self.handle.core.sync(move |core| core.resume_acked_stream(old_id)).unwrap_or((old_id, 0));
+        *stream_id = new_id;
+
+        RopeAckedStream {
+            changes:        RopeStream {
+                identifier:     new_id,
+                core:           Arc::clone(&self.handle.core),
+                poll_future:    None,
+                draining:       VecDeque::new(),
+                retains_core:   false,
+            },
+            next_version,
+            handle:         Arc::clone(&self.handle),
+        }
+    }
+}
+
+impl<Cell, Attribute> Clone for RopeAcknowledger<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    fn clone(&self) -> Self {
+        RopeAcknowledger {
+            handle: Arc::clone(&self.handle)
+        }
+    }
+}
+
+impl<Cell, Attribute> Drop for AckedStreamHandle<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    fn drop(&mut self) {
+        // Nothing can resume the stream any more, so the core can stop keeping its changes
+        let stream_id = *self.stream_id.lock().unwrap();
+
+        self.core.desync(move |core| {
+            core.stream_states.retain(|state| state.identifier != stream_id);
+        });
+    }
+}
+
+impl<Cell, Attribute> Stream for RopeAckedStream<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    type Item = (u64, RopeAction<Cell, Attribute>);
+
+    fn poll_next(mut self: Pin<&mut Self>, ctxt: &mut Context<'_>) -> Poll<Option<(u64, RopeAction<Cell, Attribute>)>> {
+        match self.changes.poll_next_unpin(ctxt) {
+            Poll::Ready(Some(action)) => {
+                // The core sends the changes in version order, one at a time
+                let version         = self.next_version;
+                self.next_version   += 1;
+
+                Poll::Ready(Some((version, action)))
+            }
+
+            Poll::Ready(None)   => Poll::Ready(None),
+            Poll::Pending       => Poll::Pending,
+        }
+    }
+}
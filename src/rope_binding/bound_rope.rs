@@ -1,4 +1,5 @@
 use crate::traits::*;
+use crate::binding_context::*;
 use super::stream::*;
 
 use flo_rope::*;
@@ -17,4 +18,26 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
 
     /// Follows the changes to the bound rope as a stream. The stream does not end if the original rope binding is dropped.
     fn follow_changes_retained(&self) -> RopeStream<Cell, Attribute>;
+
+    /// Follows the changes to the bound rope as a stream that reads the changed cells from the rope when it's polled,
+    /// instead of storing a backlog of changes
+    ///
+    /// Ropes that can't read their changes on demand return a stream from `follow_changes()` instead, which produces the
+    /// same result when the changes are applied, but stores every change until it's read.
+    fn follow_changes_pull(&self) -> RopeStream<Cell, Attribute> {
+        self.follow_changes()
+    }
+
+    /// Reads the current contents of the rope and follows the changes made after that point as a stream
+    ///
+    /// Calling `get()` and then `follow_changes()` can miss an edit or see it twice if the rope is edited in between. Here
+    /// `read_fn` is called with the contents of the rope at the point where the stream starts, so every edit is either
+    /// already in the contents or returned by the stream. `read_fn` must not access the rope itself.
+    fn read_and_follow_changes(&self, read_fn: &mut (dyn Send+FnMut(&dyn Rope<Cell=Cell, Attribute=Attribute>))) -> RopeStream<Cell, Attribute> {
+        // Ropes without a core that can do both at once read the rope before following it
+        let rope = BindingContext::out_of_context(|| self.get());
+        read_fn(&rope);
+
+        self.follow_changes()
+    }
 }
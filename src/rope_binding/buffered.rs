@@ -0,0 +1,332 @@
+use crate::traits::*;
+use crate::binding_context::*;
+use crate::rope_binding::stream::*;
+use crate::rope_binding::bound_rope::*;
+use crate::rope_binding::rope_binding_mut::*;
+use crate::timer::*;
+
+use flo_rope::*;
+
+use std::mem;
+use std::sync::*;
+use std::ops::{Range};
+use std::time::{Duration, Instant};
+
+///
+/// The edits that have been made to a buffered rope but not sent to its followers yet
+///
+struct BufferedEdits<Cell, Attribute>
+where
+Cell:       Clone+PartialEq,
+Attribute:  Clone+PartialEq+Default {
+    /// The contents of the rope, including the edits that haven't been flushed
+    rope: AttributedRope<Cell, Attribute>,
+
+    /// The edits that haven't been flushed, in the order they were made
+    actions: Vec<RopeAction<Cell, Attribute>>,
+
+    /// When the most recent edit was made
+    last_edit: Instant,
+
+    /// If set, the edits are flushed automatically once nothing has been edited for this long
+    idle_time: Option<Duration>,
+
+    /// True if the timer is waiting to flush the edits when the rope is idle
+    flush_scheduled: bool,
+}
+
+///
+/// The data shared between the copies of a buffered rope
+///
+struct BufferedRopeCore<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The rope that followers see
+    rope: RopeBindingMut<Cell, Attribute>,
+
+    /// The edits that are waiting to be flushed
+    edits: Mutex<BufferedEdits<Cell, Attribute>>,
+
+    /// Held while the edits are being flushed, so that they can't arrive out of order if several threads flush at once
+    flushing: Mutex<()>,
+
+    /// The timer used to flush the edits when the rope is idle
+    timer: Arc<dyn Timer>,
+}
+
+///
+/// A mutable rope that buffers its edits, and only sends them to anything following it when `flush()` is called
+///
+/// This is useful for editors that want to group several edits, such as a series of keystrokes, into a single change.
+/// Reading from the buffered rope, including with `get()`, returns its contents including any edits that haven't been
+/// flushed, but streams that follow the rope only see the edits once they're flushed, and anything that depends on the
+/// rope is only notified when they're flushed. All of the edits made since the last flush are sent to the followers
+/// together. `flush_when_idle()` can be used to flush the edits automatically once the rope hasn't been edited for a while.
+///
+pub struct BufferedRopeBindingMut<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The core of this rope
+    core: Arc<BufferedRopeCore<Cell, Attribute>>,
+}
+
+impl<Cell, Attribute> BufferedRopeCore<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Sends any edits that haven't been flushed to the rope that the followers see
+    ///
+    fn flush(&self) {
+        let _flushing = self.flushing.lock().unwrap();
+
+        // The edits are unlocked while they're sent, as anything that's notified of the change might read this rope
+        let actions   = mem::take(&mut self.edits.lock().unwrap().actions);
+
+        if !actions.is_empty() {
+            self.rope.with_rope(move |rope| {
+                for action in actions {
+                    rope.edit(action);
+                }
+            });
+        }
+    }
+
+    ///
+    /// Schedules a flush for when the rope becomes idle, if one isn't already scheduled
+    ///
+    fn schedule_flush(core: &Arc<Self>, edits: &mut BufferedEdits<Cell, Attribute>) {
+        let idle_time = if let Some(idle_time) = edits.idle_time { idle_time } else { return; };
+        if edits.flush_scheduled {
+            return;
+        }
+
+        // The flush is skipped if the rope is dropped before it's idle
+        edits.flush_scheduled   = true;
+        let weak_core           = Arc::downgrade(core);
+
+        core.timer.schedule(edits.last_edit + idle_time, Box::new(move || Self::flush_if_idle(weak_core)));
+    }
+
+    ///
+    /// Flushes the edits if the rope hasn't been edited for its idle time, or waits for it to become idle if it has
+    ///
+    fn flush_if_idle(core: Weak<Self>) {
+        let core = if let Some(core) = core.upgrade() { core } else { return; };
+
+        {
+            let mut edits   = core.edits.lock().unwrap();
+            let idle_time   = edits.idle_time.unwrap_or_default();
+            let idle_at     = edits.last_edit + idle_time;
+
+            if core.timer.now() < idle_at {
+                // The rope was edited while we were waiting
+                let weak_core = Arc::downgrade(&core);
+                core.timer.schedule(idle_at, Box::new(move || Self::flush_if_idle(weak_core)));
+                return;
+            }
+
+            edits.flush_scheduled = false;
+        }
+
+        core.flush();
+    }
+}
+
+impl<Cell, Attribute> BufferedRopeBindingMut<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Creates a buffered rope that sends its edits to a mutable rope when they're flushed
+    ///
+    /// The buffered rope starts with the current contents of `rope`. Any further edits to the rope should be made through
+    /// the buffered rope: editing `rope` directly moves the cells that the buffered edits refer to, so the edits that are
+    /// flushed afterwards will change the wrong cells.
+    ///
+    pub fn new(rope: &RopeBindingMut<Cell, Attribute>) -> BufferedRopeBindingMut<Cell, Attribute> {
+        Self::new_with_timer(rope, shared_timer())
+    }
+
+    ///
+    /// Creates a buffered rope that uses the specified timer to flush its edits when it's idle
+    ///
+    pub (super) fn new_with_timer(rope: &RopeBindingMut<Cell, Attribute>, timer: Arc<dyn Timer>) -> BufferedRopeBindingMut<Cell, Attribute> {
+        let edits = BufferedEdits {
+            rope:               BindingContext::out_of_context(|| rope.get()),
+            actions:            vec![],
+            last_edit:          timer.now(),
+            idle_time:          None,
+            flush_scheduled:    false,
+        };
+
+        BufferedRopeBindingMut {
+            core: Arc::new(BufferedRopeCore {
+                rope:       rope.clone(),
+                edits:      Mutex::new(edits),
+                flushing:   Mutex::new(()),
+                timer,
+            })
+        }
+    }
+
+    ///
+    /// Flushes the edits automatically once the rope hasn't been edited for `idle_time`
+    ///
+    /// Calling `flush()` still sends the edits immediately.
+    ///
+    pub fn flush_when_idle(&self, idle_time: Duration) {
+        let mut edits = self.core.edits.lock().unwrap();
+        edits.idle_time = Some(idle_time);
+
+        if !edits.actions.is_empty() {
+            BufferedRopeCore::schedule_flush(&self.core, &mut edits);
+        }
+    }
+
+    ///
+    /// Sends the edits that have been made since the last flush to anything that's following this rope
+    ///
+    pub fn flush(&self) {
+        self.core.flush();
+    }
+
+    ///
+    /// True if there are edits that haven't been flushed yet
+    ///
+    pub fn has_pending_edits(&self) -> bool {
+        !self.core.edits.lock().unwrap().actions.is_empty()
+    }
+
+    ///
+    /// Returns the number of cells in this rope, including the edits that haven't been flushed
+    ///
+    pub fn len(&self) -> usize {
+        self.core.edits.lock().unwrap().rope.len()
+    }
+
+    ///
+    /// True if this rope has no cells (including the edits that haven't been flushed)
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Reads cells from this rope, including the edits that haven't been flushed
+    ///
+    pub fn read_cells(&self, range: Range<usize>) -> impl Iterator<Item=Cell> {
+        let edits = self.core.edits.lock().unwrap();
+        edits.rope.read_cells(range).cloned().collect::<Vec<_>>().into_iter()
+    }
+
+    ///
+    /// Returns the attributes set at the specified location and their extent, including the edits that haven't been flushed
+    ///
+    pub fn read_attributes(&self, pos: usize) -> (Attribute, Range<usize>) {
+        let edits               = self.core.edits.lock().unwrap();
+        let (attribute, range)  = edits.rope.read_attributes(pos);
+
+        (attribute.clone(), range)
+    }
+
+    ///
+    /// Performs the specified editing action to this rope (followers see it when the rope is flushed)
+    ///
+    pub fn edit(&self, action: RopeAction<Cell, Attribute>) {
+        let mut edits = self.core.edits.lock().unwrap();
+
+        edits.rope.edit(action.clone());
+        edits.actions.push(action);
+        edits.last_edit = self.core.timer.now();
+
+        BufferedRopeCore::schedule_flush(&self.core, &mut edits);
+    }
+
+    ///
+    /// Replaces a range of cells. The attributes applied to the new cells will be the same
+    /// as the attributes that were applied to the first cell in the replacement range
+    ///
+    pub fn replace<NewCells: IntoIterator<Item=Cell>>(&self, range: Range<usize>, new_cells: NewCells) {
+        self.edit(RopeAction::Replace(range, new_cells.into_iter().collect()));
+    }
+
+    ///
+    /// Sets the attributes for a range of cells
+    ///
+    pub fn set_attributes(&self, range: Range<usize>, new_attributes: Attribute) {
+        self.edit(RopeAction::SetAttributes(range, new_attributes));
+    }
+
+    ///
+    /// Replaces a range of cells and sets the attributes for them.
+    ///
+    pub fn replace_attributes<NewCells: IntoIterator<Item=Cell>>(&self, range: Range<usize>, new_cells: NewCells, new_attributes: Attribute) {
+        self.edit(RopeAction::ReplaceAttributes(range, new_cells.into_iter().collect(), new_attributes));
+    }
+}
+
+impl<Cell, Attribute> Clone for BufferedRopeBindingMut<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    fn clone(&self) -> BufferedRopeBindingMut<Cell, Attribute> {
+        BufferedRopeBindingMut {
+            core: Arc::clone(&self.core)
+        }
+    }
+}
+
+impl<Cell, Attribute> BoundRope<Cell, Attribute> for BufferedRopeBindingMut<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    fn follow_changes(&self) -> RopeStream<Cell, Attribute> {
+        self.core.rope.follow_changes()
+    }
+
+    fn follow_changes_retained(&self) -> RopeStream<Cell, Attribute> {
+        self.core.rope.follow_changes_retained()
+    }
+
+    fn follow_changes_pull(&self) -> RopeStream<Cell, Attribute> {
+        self.core.rope.follow_changes_pull()
+    }
+
+    fn read_and_follow_changes(&self, read_fn: &mut (dyn Send+FnMut(&dyn Rope<Cell=Cell, Attribute=Attribute>))) -> RopeStream<Cell, Attribute> {
+        // Followers only see the edits once they're flushed, so they start from the rope without the pending edits
+        self.core.rope.read_and_follow_changes(read_fn)
+    }
+}
+
+impl<Cell, Attribute> Changeable for BufferedRopeBindingMut<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Supplies a function to be notified when the edits to this rope are flushed
+    ///
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.core.rope.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.core.rope.release_unused_notifications()
+    }
+}
+
+impl<Cell, Attribute> Bound<AttributedRope<Cell, Attribute>> for BufferedRopeBindingMut<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Retrieves the contents of this rope, including the edits that haven't been flushed
+    ///
+    fn get(&self) -> AttributedRope<Cell, Attribute> {
+        BindingContext::add_dependency(self.clone());
+
+        self.core.edits.lock().unwrap().rope.clone()
+    }
+}
@@ -0,0 +1,77 @@
+use crate::rope_binding::rope_binding_mut::*;
+
+use flo_rope::*;
+
+///
+/// Builds up the initial contents of a `RopeBindingMut` before it is created
+///
+/// Create one of these with `RopeBindingMut::builder()`. Cells that are pushed without any attributes are given the
+/// default attribute.
+///
+pub struct RopeBindingMutBuilder<Cell, Attribute> {
+    /// The cells to add to the rope, as runs of cells with the same attributes
+    runs: Vec<(Vec<Cell>, Attribute)>,
+}
+
+impl<Cell, Attribute> RopeBindingMutBuilder<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Creates a builder for an empty rope
+    ///
+    pub fn new() -> Self {
+        RopeBindingMutBuilder {
+            runs: vec![]
+        }
+    }
+
+    ///
+    /// Adds a cell to the end of the rope
+    ///
+    pub fn push(self, cell: Cell) -> Self {
+        self.push_with_attributes(Some(cell), Attribute::default())
+    }
+
+    ///
+    /// Adds some cells to the end of the rope
+    ///
+    pub fn extend<NewCells: IntoIterator<Item=Cell>>(self, cells: NewCells) -> Self {
+        self.push_with_attributes(cells, Attribute::default())
+    }
+
+    ///
+    /// Adds some cells with a set of attributes to the end of the rope
+    ///
+    pub fn push_with_attributes<NewCells: IntoIterator<Item=Cell>>(mut self, cells: NewCells, attributes: Attribute) -> Self {
+        match self.runs.last_mut() {
+            Some((run_cells, run_attributes)) if *run_attributes == attributes  => run_cells.extend(cells),
+            _                                                                   => self.runs.push((cells.into_iter().collect(), attributes)),
+        }
+
+        self
+    }
+
+    ///
+    /// Creates the rope binding
+    ///
+    pub fn build(self) -> RopeBindingMut<Cell, Attribute> {
+        let mut rope = AttributedRope::new();
+
+        for (cells, attributes) in self.runs {
+            let len = rope.len();
+            rope.replace_attributes(len..len, cells, attributes);
+        }
+
+        RopeBindingMut::from_attributed_rope(rope)
+    }
+}
+
+impl<Cell, Attribute> Default for RopeBindingMutBuilder<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    fn default() -> Self {
+        Self::new()
+    }
+}
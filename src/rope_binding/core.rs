@@ -4,6 +4,8 @@ use crate::rope_binding::stream_state::*;
 use flo_rope::*;
 use futures::task::*;
 
+use std::collections::{VecDeque};
+
 ///
 /// The core of a rope binding represents the data that's shared amongst all ropes
 ///
@@ -49,18 +51,29 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
 
         // Wake any streams that are waiting for changes to be pulled
         for stream in self.stream_states.iter_mut() {
-            let waker = stream.waker.take();
+            // If the stream is trying to sleep, make sure it wakes up immediately (a stream that's woken here can still go back to
+            // sleep if it's already waiting on a request to the core that was made before this change)
+            stream.needs_pull = true;
 
-            if let Some(waker) = waker {
+            if let Some(waker) = stream.waker.take() {
                 // Wake the stream so that it pulls the changes
                 waker.wake();
-            } else {
-                // If the stream is trying to sleep, make sure it wakes up immediately
-                stream.needs_pull = true;
             }
         }
     }
 
+    ///
+    /// Marks the end of an edit to the rope, and wakes anything that's listening to the core
+    ///
+    pub (super) fn end_edit(&mut self) {
+        if self.stream_states.iter().any(|state| state.group_sizes.is_some()) {
+            // Pull the changes immediately so they aren't combined with the changes from the next edit (this wakes the core if there were any changes)
+            self.pull_rope();
+        } else {
+            self.wake();
+        }
+    }
+
     ///
     /// Callback: the rope has changes to pull
     ///
@@ -91,36 +104,107 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
 
         // Push to each stream
         for stream in self.stream_states.iter_mut() {
-            stream.pending_changes.extend(actions.iter().cloned());
+            stream.add_changes(&actions);
         }
 
         // Wake all of the streams
         self.wake();
     }
 
+    ///
+    /// Adds the state for a new stream that follows the changes to this rope, returning its identifier
+    ///
+    /// If `grouped` is true, the stream keeps track of which changes were made by each edit, so it can return them together.
+    ///
+    pub (super) fn add_stream_state(&mut self, pull_mode: bool, grouped: bool) -> usize {
+        // Assign an ID to the stream
+        let next_id = self.next_stream_id;
+        self.next_stream_id += 1;
+
+        // Create a state for this stream
+        let state = RopeStreamState {
+            identifier:         next_id,
+            waker:              None,
+            pending_changes:    VecDeque::new(),
+            needs_pull:         false,
+            pull_mode,
+            changed_range:      None,
+            group_sizes:        if grouped { Some(VecDeque::new()) } else { None },
+            acks:               None,
+        };
+        self.stream_states.push(state);
+
+        next_id
+    }
+
+    ///
+    /// Starts keeping the changes taken by a stream until they're acknowledged
+    ///
+    pub (super) fn start_acks(&mut self, stream_id: usize) {
+        if let Some(state) = self.stream_states.iter_mut().find(|state| state.identifier == stream_id) {
+            state.acks = Some(StreamAcks::new());
+        }
+    }
+
+    ///
+    /// Marks the changes taken by a stream as acknowledged, up to and including the specified version
+    ///
+    pub (super) fn ack_stream(&mut self, stream_id: usize, version: u64) {
+        if let Some(acks) = self.stream_states.iter_mut().find(|state| state.identifier == stream_id).and_then(|state| state.acks.as_mut()) {
+            acks.ack(version);
+        }
+    }
+
+    ///
+    /// Moves the state of an acknowledged stream to a new stream ID, which will send the unacknowledged changes again
+    ///
+    /// Returns the ID of the new stream and the version of the first change it will send. The stream using the old ID
+    /// is woken so that it can finish.
+    ///
+    pub (super) fn resume_acked_stream(&mut self, stream_id: usize) -> Option<(usize, u64)> {
+        let new_id  = self.next_stream_id;
+        let state   = self.stream_states.iter_mut().find(|state| state.identifier == stream_id && state.acks.is_some())?;
+        self.next_stream_id += 1;
+
+        state.identifier = new_id;
+        let acks        = state.acks.as_mut()?;
+        acks.sent       = 0;
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+
+        Some((new_id, acks.first_version))
+    }
+
     ///
     /// Sets a stream to wake when the rope changes
     ///
     pub (super) fn wake_stream(&mut self, stream_id: usize, waker: Waker) {
-        self.stream_states
+        let state = self.stream_states
             .iter_mut()
             .filter(|state| state.identifier == stream_id)
-            .nth(0)
-            .map(move |state| {
-                if !state.needs_pull {
-                    // There are no pending values so we should wait for the rope to pull some extra data
-
-                    // Wake the stream when there's some more data to receive
-                    state.waker = Some(waker);
-                } else {
-                    // There are pending values so we should immediately re-awaken the stream
-
-                    // Disable the waker in case there's a stale one
-                    state.waker = None;
-
-                    // Wake the stream so it reads the next value
-                    waker.wake();
-                }
-            });
+            .nth(0);
+
+        if let Some(state) = state {
+            if !state.needs_pull {
+                // There are no pending values so we should wait for the rope to pull some extra data
+
+                // Wake the stream when there's some more data to receive
+                state.waker = Some(waker);
+            } else {
+                // There are pending values so we should immediately re-awaken the stream
+
+                // Disable the waker in case there's a stale one
+                state.waker = None;
+
+                // Wake the stream so it reads the next value
+                waker.wake();
+            }
+        } else {
+            // The state has gone (eg, it was moved to a resumed stream between the stream checking its state and
+            // registering its waker), so wake the stream so it can find out that it has finished
+            waker.wake();
+        }
     }
 }
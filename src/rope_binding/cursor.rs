@@ -0,0 +1,102 @@
+use crate::traits::*;
+use crate::binding::*;
+
+use flo_rope::*;
+use ::desync::*;
+use futures::prelude::*;
+
+use std::sync::*;
+use std::ops::{Range};
+
+///
+/// A cursor is a position in a rope that moves along with the edits made to the rope
+///
+/// Inserting or deleting cells before the cursor moves it so that it stays next to the same cell, and replacing a range
+/// that contains the cursor clamps it so that it's no further than the end of the replacement cells. Cells inserted at
+/// the cursor's position are inserted after it. The cursor's position can also be changed by calling `set()`.
+///
+/// Edits are applied to the cursor in the background, so as with `RopeBinding::from_mutable()`, the cursor might not
+/// reflect the most recent edits immediately. The cursor stops following the rope once every copy of it has been dropped.
+///
+#[derive(Clone)]
+pub struct RopeCursor {
+    /// The current position of the cursor
+    position: Binding<usize>,
+
+    /// The target of the pipe that moves the cursor as the rope is edited (the pipe stops once this is dropped)
+    _follow_edits: Arc<Desync<Binding<usize>>>,
+}
+
+impl RopeCursor {
+    ///
+    /// Creates a new cursor that follows the specified stream of edits
+    ///
+    pub (super) fn new<Cell, Attribute, TStream>(position: usize, edits: TStream) -> RopeCursor
+    where
+    Cell:       'static+Send+Unpin+Clone+PartialEq,
+    Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default,
+    TStream:    'static+Send+Unpin+Stream<Item=RopeAction<Cell, Attribute>> {
+        let position        = Binding::new(position);
+        let follow_edits    = Arc::new(Desync::new(position.clone()));
+
+        pipe_in(Arc::clone(&follow_edits), edits, |position, action| {
+            async move {
+                match action {
+                    RopeAction::Replace(range, cells)               => move_cursor(position, range, cells.len()),
+                    RopeAction::ReplaceAttributes(range, cells, _)  => move_cursor(position, range, cells.len()),
+                    RopeAction::SetAttributes(_, _)                 => { }
+                }
+            }.boxed()
+        });
+
+        RopeCursor {
+            position,
+            _follow_edits: follow_edits
+        }
+    }
+}
+
+///
+/// Updates a cursor position after a range of cells has been replaced with `new_len` cells
+///
+fn move_cursor(position: &Binding<usize>, range: Range<usize>, new_len: usize) {
+    position.with_mut(|pos| {
+        let new_pos = if *pos <= range.start {
+            // Edit is after the cursor
+            *pos
+        } else if *pos >= range.end {
+            // Edit is before the cursor
+            *pos - range.len() + new_len
+        } else {
+            // Edit contains the cursor
+            (*pos).min(range.start + new_len)
+        };
+
+        let changed = new_pos != *pos;
+        *pos = new_pos;
+
+        changed
+    });
+}
+
+impl Changeable for RopeCursor {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.position.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.position.release_unused_notifications()
+    }
+}
+
+impl Bound<usize> for RopeCursor {
+    fn get(&self) -> usize {
+        self.position.get()
+    }
+}
+
+impl MutableBound<usize> for RopeCursor {
+    fn set(&self, new_value: usize) {
+        self.position.set(new_value)
+    }
+}
@@ -0,0 +1,14 @@
+///
+/// Describes which attributes are applied to cells inserted by `RopeBindingMut::edit_with_policy()`
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InsertPolicy {
+    /// The new cells take the attributes of the cell before the edited range (or the default attributes if the range is at the start of the rope)
+    InheritFromLeft,
+
+    /// The new cells take the attributes of the cell after the edited range (or the default attributes if the range is at the end of the rope)
+    InheritFromRight,
+
+    /// The new cells always have the default attributes
+    UseDefault,
+}
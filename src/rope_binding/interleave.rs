@@ -0,0 +1,205 @@
+use flo_rope::*;
+
+use std::ops::{Range};
+
+///
+/// Chooses which rope supplies the next cell when interleaving two ropes with `RopeBinding::interleave()`
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InterleaveSide {
+    /// The next cell is taken from the rope that `interleave()` was called on
+    Left,
+
+    /// The next cell is taken from the other rope
+    Right,
+}
+
+///
+/// Tracks which rope supplied each cell of an interleaved rope, so that the edits to either rope can be turned into edits
+/// to the interleaved rope
+///
+pub (super) struct InterleavedCells<Cell, TSelectFn> {
+    /// The cells in the left-hand rope
+    left: Vec<Cell>,
+
+    /// The cells in the right-hand rope
+    right: Vec<Cell>,
+
+    /// The rope that supplied each cell in the interleaved rope
+    sides: Vec<InterleaveSide>,
+
+    /// Chooses which rope supplies the next cell
+    selector: TSelectFn,
+}
+
+impl<Cell, TSelectFn> InterleavedCells<Cell, TSelectFn>
+where
+Cell:       Clone,
+TSelectFn:  Fn(&Cell, &Cell) -> InterleaveSide {
+    ///
+    /// Interleaves two lists of cells
+    ///
+    pub (super) fn new(left: Vec<Cell>, right: Vec<Cell>, selector: TSelectFn) -> Self {
+        let mut interleaved = InterleavedCells { left, right, sides: vec![], selector };
+        let (sides, _)      = interleaved.interleave_from(0, 0, |_, _| false);
+        interleaved.sides   = sides;
+
+        interleaved
+    }
+
+    ///
+    /// Returns the cells in the interleaved rope
+    ///
+    pub (super) fn cells(&self) -> Vec<Cell> {
+        self.cells_in(0..self.sides.len())
+    }
+
+    ///
+    /// Runs the selector from the point where `left_pos` cells have been taken from the left rope and `right_pos` from the right,
+    /// returning the sides that supply each cell
+    ///
+    /// This stops early when `stop_at` returns true for the number of cells taken from each rope. The return value is the
+    /// sides that were chosen, and whether or not `stop_at` stopped the interleaving.
+    ///
+    fn interleave_from(&self, mut left_pos: usize, mut right_pos: usize, mut stop_at: impl FnMut(usize, usize) -> bool) -> (Vec<InterleaveSide>, bool) {
+        let mut sides = vec![];
+
+        loop {
+            if stop_at(left_pos, right_pos) {
+                return (sides, true);
+            }
+
+            let next_side = match (self.left.get(left_pos), self.right.get(right_pos)) {
+                (Some(left), Some(right))   => (self.selector)(left, right),
+                (Some(_), None)             => InterleaveSide::Left,
+                (None, Some(_))             => InterleaveSide::Right,
+                (None, None)                => { return (sides, false); }
+            };
+
+            match next_side {
+                InterleaveSide::Left    => left_pos += 1,
+                InterleaveSide::Right   => right_pos += 1,
+            }
+
+            sides.push(next_side);
+        }
+    }
+
+    ///
+    /// Applies an edit to one of the ropes, and returns the edit that should be made to the interleaved rope
+    ///
+    /// Only the part of the interleaved rope from the edit to the point where the selector makes the same choices as it
+    /// did before the edit is interleaved again.
+    ///
+    pub (super) fn edit<Attribute>(&mut self, side: InterleaveSide, action: RopeAction<Cell, Attribute>) -> Option<RopeAction<Cell, Attribute>> {
+        // The interleaved rope has no attributes, so only the changes to the cells matter
+        let (range, new_cells) = match action {
+            RopeAction::Replace(range, cells)               => (range, cells),
+            RopeAction::ReplaceAttributes(range, cells, _)  => (range, cells),
+            RopeAction::SetAttributes(_, _)                 => { return None; }
+        };
+
+        // Choices made before the edited cell was reached don't change, so the interleaving is repeated from the first position where it was
+        let start_pos   = self.position_after(side, range.start);
+        let other_start = start_pos - range.start;
+        let removed     = range.len();
+        let added       = new_cells.len();
+
+        let edited      = match side {
+            InterleaveSide::Left    => &mut self.left,
+            InterleaveSide::Right   => &mut self.right,
+        };
+        edited.splice(range.clone(), new_cells);
+
+        // Interleave until the selector is making the same choices as before: this is when the same number of cells have been
+        // taken from the other rope, and the edited rope is at the same place after the edit. The old choices are counted as
+        // the new ones are made.
+        let old_sides       = &self.sides;
+        let mut old_pos     = start_pos;
+        let mut old_edited  = range.start;
+
+        let (new_sides, stopped) = {
+            let (left_pos, right_pos) = match side {
+                InterleaveSide::Left    => (range.start, other_start),
+                InterleaveSide::Right   => (other_start, range.start),
+            };
+
+            self.interleave_from(left_pos, right_pos, |left_pos, right_pos| {
+                let (edited_pos, other_pos) = match side {
+                    InterleaveSide::Left    => (left_pos, right_pos),
+                    InterleaveSide::Right   => (right_pos, left_pos),
+                };
+
+                if edited_pos < range.start + added {
+                    return false;
+                }
+
+                // Find the same point in the old interleaving
+                let target_edited   = edited_pos - added + removed;
+                let target_pos      = target_edited + other_pos;
+                while old_pos < target_pos && old_pos < old_sides.len() {
+                    if old_sides[old_pos] == side { old_edited += 1; }
+                    old_pos += 1;
+                }
+
+                old_pos == target_pos && old_edited == target_edited
+            })
+        };
+
+        // If the interleaving didn't stop early, it has replaced everything to the end
+        let end_pos         = if stopped { old_pos } else { self.sides.len() };
+
+        // Cells from the other rope that are still in the same place are left alone
+        let unchanged       = new_sides.iter().zip(self.sides[start_pos..end_pos].iter())
+            .take_while(|(new_side, old_side)| new_side == old_side && **new_side != side)
+            .count();
+        let start_pos       = start_pos + unchanged;
+        let new_sides       = new_sides.into_iter().skip(unchanged).collect::<Vec<_>>();
+        let new_len         = new_sides.len();
+        self.sides.splice(start_pos..end_pos, new_sides);
+
+        if start_pos == end_pos && new_len == 0 {
+            None
+        } else {
+            Some(RopeAction::Replace(start_pos..end_pos, self.cells_in(start_pos..(start_pos+new_len))))
+        }
+    }
+
+    ///
+    /// Returns the position in the interleaved rope just after the cell at `pos` in one of the ropes was added (0 if `pos` is 0)
+    ///
+    fn position_after(&self, side: InterleaveSide, pos: usize) -> usize {
+        if pos == 0 {
+            return 0;
+        }
+
+        let mut count = 0;
+        for (interleaved_pos, cell_side) in self.sides.iter().enumerate() {
+            if *cell_side == side {
+                count += 1;
+
+                if count == pos {
+                    return interleaved_pos + 1;
+                }
+            }
+        }
+
+        self.sides.len()
+    }
+
+    ///
+    /// Reads a range of cells from the interleaved rope
+    ///
+    fn cells_in(&self, range: Range<usize>) -> Vec<Cell> {
+        let left_start  = self.sides[0..range.start].iter().filter(|side| **side == InterleaveSide::Left).count();
+        let mut left    = self.left[left_start..].iter();
+        let mut right   = self.right[(range.start - left_start)..].iter();
+
+        self.sides[range].iter()
+            .map(|side| match side {
+                InterleaveSide::Left    => left.next().unwrap().clone(),
+                InterleaveSide::Right   => right.next().unwrap().clone(),
+            })
+            .collect()
+    }
+}
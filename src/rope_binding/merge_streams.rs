@@ -0,0 +1,143 @@
+use crate::rope_binding::rope_binding::*;
+
+use futures::prelude::*;
+use futures::stream;
+
+use flo_rope::*;
+
+use std::ops::{Range};
+
+///
+/// Transforms an action so that it can be applied after another action that was made at the same time
+///
+/// This is a simple operational transform that can be used with `RopeBinding::from_two_streams()`. `action_first` is true
+/// if `action` comes from the first of the two streams, which decides which cells go first when the two actions insert
+/// cells at the same position or replace the same range.
+///
+/// The range of `action` is moved to allow for any cells that `against` inserted or removed before it. If the two actions
+/// edit overlapping ranges, the cells from both actions are kept in the order of their ranges, except that when one action
+/// replaces a range that contains the whole of the other on both sides, the cells from the inner action are replaced too.
+/// The result is the same whichever of the two actions is applied first, so long as the two calls agree on `action_first`.
+///
+pub fn transform_rope_action<Cell, Attribute>(action: RopeAction<Cell, Attribute>, against: &RopeAction<Cell, Attribute>, action_first: bool) -> RopeAction<Cell, Attribute> {
+    use RopeAction::*;
+
+    // The range that `against` edited, and the number of cells that it left there
+    let (against_range, against_len) = match against {
+        Replace(range, cells) | ReplaceAttributes(range, cells, _)  => (range.clone(), cells.len()),
+        SetAttributes(..)                                           => { return action; }
+    };
+    let against_end = against_range.start + against_len;
+
+    // Moves a position to where it is after `against` has been applied
+    let move_position = |pos: usize| pos - against_range.len() + against_len;
+
+    // Setting attributes only affects the cells that `against` didn't replace
+    let transform_attribute_range = |range: Range<usize>| {
+        let start   = if range.start < against_range.start { range.start } else if range.start >= against_range.end { move_position(range.start) } else { against_end };
+        let end     = if range.end <= against_range.start { range.end } else if range.end >= against_range.end { move_position(range.end) } else { against_range.start };
+
+        start..end.max(start)
+    };
+
+    // Replacing cells keeps the cells from both actions when they overlap (None if `against` replaced the whole range)
+    let transform_replace_range = |range: Range<usize>| {
+        if range.end < against_range.start {
+            // Before the edit
+            Some(range)
+        } else if range.start > against_range.end {
+            // After the edit
+            Some(move_position(range.start)..move_position(range.end))
+        } else {
+            // The ranges touch or overlap: order them by range, with the shorter range first if they start at the same place
+            let is_first = (range.start, range.end) < (against_range.start, against_range.end)
+                || ((range.start, range.end) == (against_range.start, against_range.end) && action_first);
+
+            if is_first && range.start < against_range.start && against_range.end < range.end {
+                // Replace the cells from `against` along with the rest of the range
+                Some(range.start..move_position(range.end))
+            } else if !is_first && against_range.start < range.start && range.end < against_range.end {
+                // `against` replaced the whole of this range
+                None
+            } else if is_first {
+                // Replace the cells before `against`
+                Some(range.start..against_range.start)
+            } else {
+                // Replace the cells after `against`
+                Some(against_end..move_position(range.end))
+            }
+        }
+    };
+
+    match action {
+        Replace(range, cells)                       => match transform_replace_range(range) {
+            Some(range) => Replace(range, cells),
+            None        => Replace(against_range.start..against_range.start, vec![]),
+        },
+        ReplaceAttributes(range, cells, attributes) => match transform_replace_range(range) {
+            Some(range) => ReplaceAttributes(range, cells, attributes),
+            None        => Replace(against_range.start..against_range.start, vec![]),
+        },
+        SetAttributes(range, attributes)            => SetAttributes(transform_attribute_range(range), attributes),
+    }
+}
+
+impl<Cell, Attribute> RopeBinding<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Creates a new rope binding from two streams of changes that are editing the same rope
+    ///
+    /// Each stream is treated as editing its own copy of the rope, starting from the same empty rope, so neither stream sees
+    /// the changes made by the other. Changes are applied as soon as they arrive: `resolve` is called to transform each change
+    /// against the changes from the other stream that have already been applied, and is called with the arguments the other
+    /// way around to keep those changes up to date with the new change (the final argument is true when the action being
+    /// transformed is from the first stream). `transform_rope_action()` can be used as the resolve function.
+    ///
+    /// As neither stream ever sees the changes from the other, the changes from each stream are kept for as long as the
+    /// other stream is still running.
+    ///
+    pub fn from_two_streams<S1, S2, TResolveFn>(first: S1, second: S2, resolve: TResolveFn) -> Self
+    where
+    S1:         'static+Stream<Item=RopeAction<Cell, Attribute>>+Unpin+Send,
+    S2:         'static+Stream<Item=RopeAction<Cell, Attribute>>+Unpin+Send,
+    TResolveFn: 'static+Send+Fn(RopeAction<Cell, Attribute>, &RopeAction<Cell, Attribute>, bool) -> RopeAction<Cell, Attribute> {
+        // The changes from the other stream that each stream hasn't seen, relative to the rope as that stream sees it (None once the stream has finished)
+        let mut unseen_by_first     = Some(vec![]);
+        let mut unseen_by_second    = Some(vec![]);
+
+        // Each stream ends with 'None' so that we can stop keeping changes for it
+        let first           = first.map(|action| (true, Some(action))).chain(stream::once(future::ready((true, None))));
+        let second          = second.map(|action| (false, Some(action))).chain(stream::once(future::ready((false, None))));
+        let merged_stream   = stream::select(first, second)
+            .filter_map(move |(from_first, action)| {
+                let (unseen, other_unseen) = if from_first { (&mut unseen_by_first, &mut unseen_by_second) } else { (&mut unseen_by_second, &mut unseen_by_first) };
+
+                let action = if let Some(action) = action {
+                    // Transform the action against each of the changes it hasn't seen, and those changes against the action
+                    let mut action = action;
+                    for unseen_action in unseen.iter_mut().flatten() {
+                        let next_action = resolve(action.clone(), unseen_action, from_first);
+                        *unseen_action  = resolve(unseen_action.clone(), &action, !from_first);
+                        action          = next_action;
+                    }
+
+                    // The other stream hasn't seen this action yet
+                    if let Some(other_unseen) = other_unseen {
+                        other_unseen.push(action.clone());
+                    }
+
+                    Some(action)
+                } else {
+                    // This stream has finished, so it won't need to see any more changes
+                    *unseen = None;
+                    None
+                };
+
+                future::ready(action)
+            });
+
+        Self::from_stream(merged_stream)
+    }
+}
@@ -3,12 +3,41 @@ mod bound_rope;
 mod stream_state;
 mod rope_binding;
 mod rope_binding_mut;
+mod builder;
+mod buffered;
 mod stream;
+mod acked_stream;
 mod rope_ext;
+mod rope_action_ext;
+mod cursor;
+mod insert_policy;
+mod interleave;
+mod text;
+mod merge_streams;
+#[cfg(any(feature = "stream", feature = "diff"))]
+mod vec_binding;
+#[cfg(feature = "stream")]
+mod total_len;
+#[cfg(feature = "serde")]
+mod serialize;
 #[cfg(test)] mod tests;
 
 pub use self::bound_rope::*;
 pub use self::rope_binding::*;
 pub use self::rope_binding_mut::*;
+pub use self::builder::*;
+pub use self::buffered::*;
 pub use self::stream::*;
+pub use self::acked_stream::*;
 pub use self::rope_ext::*;
+pub use self::rope_action_ext::*;
+pub use self::cursor::*;
+pub use self::insert_policy::*;
+pub use self::interleave::*;
+pub use self::merge_streams::*;
+#[cfg(feature = "stream")]
+pub use self::vec_binding::*;
+#[cfg(feature = "stream")]
+pub use self::total_len::*;
+#[cfg(feature = "serde")]
+pub use self::serialize::*;
@@ -0,0 +1,130 @@
+#[cfg(feature = "stream")]
+use crate::bindref::*;
+#[cfg(feature = "stream")]
+use crate::bind_stream::*;
+
+use flo_rope::*;
+#[cfg(feature = "stream")]
+use futures::prelude::*;
+
+use std::ops::{Range};
+
+///
+/// Statistics describing how a single rope action changes the cells of a rope
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RopeChangeStats {
+    /// The number of cells that were inserted into the rope
+    pub inserted: usize,
+
+    /// The number of cells that were removed from the rope
+    pub deleted: usize,
+
+    /// The range of cells in the rope that was affected by the action (before the action was applied)
+    pub range: Range<usize>,
+}
+
+///
+/// Extension methods for applying rope actions to other data structures
+///
+/// These are useful for consumers of a `RopeStream` that want to maintain a flat copy of the rope
+///
+pub trait RopeActionExt<Cell, Attribute> {
+    ///
+    /// Applies this action to a vector of cells (attributes are ignored)
+    ///
+    fn apply_to_vec(&self, vec: &mut Vec<Cell>);
+
+    ///
+    /// Applies this action to a vector of cells and their attributes
+    ///
+    /// As with `AttributedRope`, cells inserted by a `Replace` action take on the attributes of the cell that precedes
+    /// the replaced range (or the first cell if the range is at the start of the vector)
+    ///
+    fn apply_to_attributed_vec(&self, vec: &mut Vec<(Cell, Attribute)>);
+
+    ///
+    /// Returns the number of cells inserted and deleted by this action
+    ///
+    /// Setting attributes does not insert or delete any cells, so the statistics for a `SetAttributes` action only
+    /// contain the range that was affected.
+    ///
+    fn change_stats(&self) -> RopeChangeStats;
+}
+
+impl<Cell, Attribute> RopeActionExt<Cell, Attribute> for RopeAction<Cell, Attribute>
+where
+Cell:       Clone,
+Attribute:  Clone+Default {
+    fn apply_to_vec(&self, vec: &mut Vec<Cell>) {
+        use RopeAction::*;
+
+        match self {
+            Replace(range, cells)                   => { vec.splice(range.clone(), cells.iter().cloned()); }
+            SetAttributes(_range, _attributes)      => { }
+            ReplaceAttributes(range, cells, _attr)  => { vec.splice(range.clone(), cells.iter().cloned()); }
+        }
+    }
+
+    fn change_stats(&self) -> RopeChangeStats {
+        use RopeAction::*;
+
+        match self {
+            Replace(range, cells) | ReplaceAttributes(range, cells, _)  => RopeChangeStats { inserted: cells.len(), deleted: range.len(), range: range.clone() },
+            SetAttributes(range, _)                                     => RopeChangeStats { inserted: 0, deleted: 0, range: range.clone() },
+        }
+    }
+
+    fn apply_to_attributed_vec(&self, vec: &mut Vec<(Cell, Attribute)>) {
+        use RopeAction::*;
+
+        match self {
+            Replace(range, cells) => {
+                // Use the same attributes as the rope would for the new cells
+                let attribute = if range.start > 0 {
+                    vec[range.start-1].1.clone()
+                } else if let Some((_, attribute)) = vec.first() {
+                    attribute.clone()
+                } else {
+                    Attribute::default()
+                };
+
+                vec.splice(range.clone(), cells.iter().map(|cell| (cell.clone(), attribute.clone())));
+            }
+
+            SetAttributes(range, attributes) => {
+                // Attributes set beyond the end of the vector are ignored
+                let end     = range.end.min(vec.len());
+                let start   = range.start.min(end);
+
+                for (_, cell_attributes) in vec[start..end].iter_mut() {
+                    *cell_attributes = attributes.clone();
+                }
+            }
+
+            ReplaceAttributes(range, cells, attributes) => {
+                vec.splice(range.clone(), cells.iter().map(|cell| (cell.clone(), attributes.clone())));
+            }
+        }
+    }
+}
+
+///
+/// Creates a binding to a vector of cells that is updated by applying the actions from a stream of changes to a rope
+///
+/// This is useful for consumers that want to follow a rope without needing the rope itself: for example, to display
+/// a short list, where a plain vector of cells is simpler to work with. Attributes are ignored.
+///
+#[cfg(feature = "stream")]
+pub fn bind_rope_stream<Cell, Attribute, S>(stream: S) -> BindRef<Vec<Cell>>
+where
+Cell:       'static+Send+Clone+PartialEq,
+Attribute:  'static+Send+Clone+Default,
+S:          'static+Send+Unpin+Stream<Item=RopeAction<Cell, Attribute>> {
+    let cells = bind_stream(stream, vec![], |mut cells, action| {
+        action.apply_to_vec(&mut cells);
+        cells
+    });
+
+    BindRef::from(cells)
+}
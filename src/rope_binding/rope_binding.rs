@@ -2,11 +2,27 @@ use crate::traits::*;
 use crate::notify_fn::*;
 use crate::releasable::*;
 use crate::binding_context::*;
+#[cfg(any(test, feature = "test-support"))]
+use crate::flush_pending::*;
 use crate::rope_binding::core::*;
 use crate::rope_binding::stream::*;
 use crate::rope_binding::bound_rope::*;
-use crate::rope_binding::stream_state::*;
 use crate::rope_binding::rope_binding_mut::*;
+#[cfg(feature = "stream")]
+use crate::rope_binding::rope_ext::{attribute_runs, contents_actions};
+use crate::rope_binding::interleave::*;
+#[cfg(feature = "diff")]
+use crate::rope_binding::vec_binding::*;
+#[cfg(feature = "stream")]
+use crate::bindref::*;
+#[cfg(feature = "stream")]
+use crate::computed::*;
+#[cfg(feature = "stream")]
+use crate::follow::*;
+#[cfg(feature = "stream")]
+use crate::bind_stream::*;
+#[cfg(feature = "stream")]
+use crate::rope_binding::rope_action_ext::*;
 
 use flo_rope::*;
 use ::desync::*;
@@ -14,15 +30,43 @@ use futures::prelude::*;
 use futures::stream;
 use futures::task::{Poll};
 
-#[cfg(feature = "diff")]
-use similar::*;
-
 use std::mem;
 use std::sync::*;
 use std::ops::{Range};
 use std::hash::{Hash};
 use std::collections::{VecDeque};
 
+///
+/// Updates the matches found by `match_ranges()` after the cells in `edited` have been replaced by `new_len` cells
+///
+/// The matches that don't touch the edit are kept (moving the ones after it), and the cells between them are searched again
+///
+#[cfg(feature = "stream")]
+fn rescan_matches<Cell, TMatchFn>(matches: Vec<Range<usize>>, edited: Range<usize>, new_len: usize, cells: &[Cell], matcher: &TMatchFn) -> Vec<Range<usize>>
+where
+TMatchFn: Fn(&[Cell]) -> Vec<Range<usize>> {
+    let mut before  = vec![];
+    let mut after   = vec![];
+
+    for found in matches {
+        if found.end < edited.start {
+            before.push(found);
+        } else if found.start > edited.end {
+            after.push((found.start - edited.len() + new_len)..(found.end - edited.len() + new_len));
+        }
+    }
+
+    // Search the cells between the matches that were kept
+    let scan_start  = before.last().map(|found| found.end).unwrap_or(0);
+    let scan_end    = after.first().map(|found| found.start).unwrap_or(cells.len());
+    let rescanned   = matcher(&cells[scan_start..scan_end]).into_iter()
+        .map(|found| (found.start + scan_start)..(found.end + scan_start));
+
+    before.extend(rescanned);
+    before.extend(after);
+    before
+}
+
 ///
 /// A rope binding binds a vector of cells and attributes
 ///
@@ -56,6 +100,19 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     /// Creates a new rope binding from a stream of changes
     ///
     pub fn from_stream<S: 'static+Stream<Item=RopeAction<Cell, Attribute>>+Unpin+Send>(stream: S) -> Self {
+        Self::from_stream_with_initial(stream, vec![])
+    }
+
+    ///
+    /// Creates a new rope binding from a stream of changes, starting with a known set of cells
+    ///
+    /// This is useful when the stream only describes the changes made after a snapshot of a rope: the binding contains
+    /// the snapshot immediately rather than being empty until the first changes arrive.
+    ///
+    pub fn from_stream_with_initial<S, InitialCells>(stream: S, initial_cells: InitialCells) -> Self
+    where
+    S:              'static+Stream<Item=RopeAction<Cell, Attribute>>+Unpin+Send,
+    InitialCells:   'static+Send+IntoIterator<Item=Cell> {
         // Create the core
         let core        = RopeBindingCore {
             usage_count:    1,
@@ -70,7 +127,10 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         // Recreate the rope in the core with a version that responds to pull events
         let weak_core   = Arc::downgrade(&core);
         core.sync(move |core| {
-            core.rope = PullRope::from(AttributedRope::new(), Box::new(move || {
+            let mut initial_rope = AttributedRope::new();
+            initial_rope.replace(0..0, initial_cells);
+
+            core.rope = PullRope::from(initial_rope, Box::new(move || {
                 // Pass the event through to the core
                 let core = weak_core.upgrade();
                 if let Some(core) = core {
@@ -93,6 +153,39 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         }
     }
 
+    ///
+    /// Creates a rope that interleaves the cells from this rope with the cells from another rope
+    ///
+    /// The selector is called with the next cell from each rope, and chooses which of them comes next in the interleaved
+    /// rope. Once all of the cells from one of the ropes have been used, the rest of the cells from the other rope are added
+    /// to the end. For example, this can be used to merge two sorted lists, or to show the lines of two documents in a
+    /// combined diff view.
+    ///
+    /// The interleaved rope keeps track of which rope each of its cells came from, so when either rope is edited, the selector
+    /// is only called again from the edit to the point where it makes the same choices as before. The cells in the interleaved
+    /// rope always have the default attributes.
+    ///
+    pub fn interleave<TSelectFn>(&self, other: &RopeBinding<Cell, Attribute>, selector: TSelectFn) -> Self
+    where
+    TSelectFn: 'static+Send+Fn(&Cell, &Cell) -> InterleaveSide {
+        // Read both ropes at the same time as following them, so no edits are missed
+        let mut left_cells  = vec![];
+        let mut right_cells = vec![];
+        let left_changes    = self.read_and_follow_changes(&mut |rope| left_cells = rope.read_cells(0..rope.len()).cloned().collect());
+        let right_changes   = other.read_and_follow_changes(&mut |rope| right_cells = rope.read_cells(0..rope.len()).cloned().collect());
+
+        let mut interleaved = InterleavedCells::new(left_cells, right_cells, selector);
+        let initial_cells   = interleaved.cells();
+
+        // Each edit to either rope becomes an edit to the interleaved rope
+        let left_changes    = left_changes.map(|action| (InterleaveSide::Left, action));
+        let right_changes   = right_changes.map(|action| (InterleaveSide::Right, action));
+        let changes         = stream::select(left_changes, right_changes)
+            .filter_map(move |(side, action)| future::ready(interleaved.edit(side, action)));
+
+        Self::from_stream_with_initial(changes, initial_cells)
+    }
+
     ///
     /// Creates a rope binding that entirely replaces its set of cells by following a computed value (the attributes will always
     /// have their default values when using this method)
@@ -151,6 +244,83 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         Self::from_stream(stream)
     }
 
+    ///
+    /// Creates a rope binding made up of several sections, each of which is computed by a separate function
+    ///
+    /// This works like `computed()`, except each section tracks its own dependencies. When a dependency changes, only the
+    /// section that depends on it is recalculated, and only the cells for that section are replaced in the rope. The
+    /// attributes will always have their default values when using this method.
+    ///
+    /// Closures of different types can be used for the sections by boxing them (`Box<dyn Fn() -> Vec<Cell>+Send>`).
+    ///
+    pub fn computed_sections<TFn: 'static+Send+Fn() -> TValueIter, TValueIter: IntoIterator<Item=Cell>>(sections: Vec<TFn>) -> Self {
+        // Every section starts out needing to be calculated
+        let num_sections            = sections.len();
+        let mut lengths             = vec![0; num_sections];
+        let new_value               = Arc::new(Mutex::new(vec![true; num_sections]));
+        let waker                   = Arc::new(Mutex::new(None));
+        let mut dependency_monitors = (0..num_sections).map(|_| None).collect::<Vec<_>>();
+
+        let stream                  = stream::poll_fn(move |ctxt| {
+            // Store the waker so we can poll the stream again when it changes
+            (*waker.lock().unwrap()) = Some(ctxt.waker().clone());
+
+            let mut actions = vec![];
+            let mut offset  = 0;
+
+            for (section_idx, calculate_value) in sections.iter().enumerate() {
+                // Replace the contents of the section whenever there is a new value
+                if mem::take(&mut new_value.lock().unwrap()[section_idx]) {
+                    // Loop until the value is stable
+                    loop {
+                        // Release the monitor (this holds on to the bindings from the previous calculation)
+                        dependency_monitors[section_idx] = None;
+
+                        // Compute the new value and the dependencies
+                        let (value_iter, dependencies)  = BindingContext::bind(calculate_value);
+
+                        // When the dependencies change, mark that this section has changed and wake up the stream
+                        let new_value                   = Arc::clone(&new_value);
+                        let waker                       = Arc::clone(&waker);
+                        let new_dependency_monitor      = dependencies.when_changed_if_unchanged(notify(move || {
+                            // Mark as changed
+                            new_value.lock().unwrap()[section_idx] = true;
+
+                            // Wake the stream
+                            let waker           = mem::take(&mut *waker.lock().unwrap());
+                            if let Some(waker)  = waker { waker.wake() }
+                        }));
+
+                        // Recalculate the value if it has already changed
+                        if new_dependency_monitor.is_none() { continue; }
+
+                        // Keep the releasable alongside this stream
+                        dependency_monitors[section_idx] = new_dependency_monitor;
+
+                        // The action is to replace all of the cells in this section with the new values
+                        let new_cells   = value_iter.into_iter().collect::<Vec<_>>();
+                        let old_length  = lengths[section_idx];
+                        lengths[section_idx] = new_cells.len();
+
+                        actions.push(RopeAction::Replace(offset..(offset+old_length), new_cells));
+                        break;
+                    }
+                }
+
+                // The next section starts after this one
+                offset += lengths[section_idx];
+            }
+
+            if actions.is_empty() {
+                Poll::Pending
+            } else {
+                Poll::Ready(Some(stream::iter(actions)))
+            }
+        });
+
+        Self::from_stream(stream.flatten())
+    }
+
     ///
     /// Returns the number of cells in this rope
     ///
@@ -178,6 +348,42 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         cells.into_iter()
     }
 
+    ///
+    /// Calls a function with an iterator over the cells in a range of this rope, without copying them
+    ///
+    /// This avoids the allocation that `read_cells()` needs to make, which is useful when reading large ropes frequently. The
+    /// rope is locked while the function runs, so it should return quickly, and must not try to read from or edit this rope
+    /// (which will deadlock).
+    ///
+    pub fn with_cells<TResult, TFn>(&self, range: Range<usize>, action: TFn) -> TResult
+    where
+    TResult:    Send,
+    TFn:        Send+FnOnce(&mut dyn Iterator<Item=&Cell>) -> TResult {
+        BindingContext::add_dependency(self.clone());
+
+        self.core.sync(move |core| {
+            core.pull_rope();
+            action(&mut core.rope.read_cells(range))
+        })
+    }
+
+    ///
+    /// Calls a function with the length of this rope and an iterator over all of its cells, reading both with the rope locked
+    ///
+    pub (super) fn with_all_cells<TResult, TFn>(&self, action: TFn) -> TResult
+    where
+    TResult:    Send,
+    TFn:        Send+FnOnce(usize, &mut dyn Iterator<Item=&Cell>) -> TResult {
+        BindingContext::add_dependency(self.clone());
+
+        self.core.sync(move |core| {
+            core.pull_rope();
+
+            let len = core.rope.len();
+            action(len, &mut core.rope.read_cells(0..len))
+        })
+    }
+
     ///
     /// Returns the attributes set at the specified location and their extent
     ///
@@ -193,6 +399,85 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
 
         (attribute, range)
     }
+
+    ///
+    /// Returns a binding that is true whenever the content of this rope is the same as the specified snapshot
+    ///
+    /// This is useful for 'is this document modified?' style checks: take a snapshot of the rope using `get()` when the
+    /// document is saved and bind to whether or not the rope still matches it. The returned binding only notifies when
+    /// the result changes between equal and unequal, rather than for every edit made to the rope.
+    ///
+    #[cfg(feature = "stream")]
+    pub fn equals_binding(&self, other_snapshot: AttributedRope<Cell, Attribute>) -> BindRef<bool> {
+        // Compare the rope against the snapshot whenever it changes
+        let rope            = self.clone();
+        let other_snapshot  = Mutex::new(other_snapshot);
+        let is_equal        = ComputedBinding::new(move || rope.get() == *other_snapshot.lock().unwrap());
+
+        // The stream binding caches the last comparison so it only notifies when the comparison result is different
+        let initial_value   = is_equal.get();
+        let is_equal        = bind_stream(follow(is_equal), initial_value, |_old_value, new_value| new_value);
+
+        BindRef::from(is_equal)
+    }
+
+    ///
+    /// Returns a binding of the attribute runs in this rope
+    ///
+    /// Each run is the attribute applied to a range of cells. The binding only notifies when the attribute runs change: edits
+    /// that only change the content of the cells do not generate a notification, so this can be used to update something that
+    /// depends on the styling of the rope separately from something that depends on its content.
+    ///
+    #[cfg(feature = "stream")]
+    pub fn attribute_runs_binding(&self) -> BindRef<Vec<(Attribute, Range<usize>)>> {
+        // Keep a copy of the rope so that the runs can be read after each change
+        let mut rope_copy   = AttributedRope::new();
+        let changes         = self.read_and_follow_changes(&mut |rope| {
+            for action in contents_actions(rope) {
+                rope_copy.edit(action);
+            }
+        });
+        let initial_runs    = attribute_runs(&rope_copy);
+
+        let runs            = bind_stream(changes, initial_runs, move |_runs, action| {
+            // Even replacing cells with the same number of cells can change the runs, as the new cells take on the attribute of the cell before them
+            rope_copy.edit(action);
+            attribute_runs(&rope_copy)
+        });
+
+        BindRef::from(runs)
+    }
+
+    ///
+    /// Returns a binding of the ranges of the cells in this rope that match a pattern
+    ///
+    /// The matcher is called with the cells in the rope and returns the ranges that match, for example to highlight
+    /// the results of a search in a document. When the rope is edited, only the part of the rope around the edit is
+    /// searched again: matches that don't touch the edit are kept, and the matcher is called with the cells between the
+    /// nearest of these on either side. This means that the matcher should find a match from the cells it covers alone.
+    ///
+    #[cfg(feature = "stream")]
+    pub fn match_ranges<TMatchFn>(&self, matcher: TMatchFn) -> BindRef<Vec<Range<usize>>>
+    where
+    TMatchFn: 'static+Send+Fn(&[Cell]) -> Vec<Range<usize>> {
+        // Keep a copy of the cells so the edited part of the rope can be searched after each change
+        let mut cells       = vec![];
+        let changes         = self.read_and_follow_changes(&mut |rope| cells = rope.read_cells(0..rope.len()).cloned().collect());
+        let initial_matches = matcher(&cells);
+
+        let matches         = bind_stream(changes, initial_matches, move |matches, action| {
+            let (edited, new_len) = match &action {
+                RopeAction::Replace(range, new_cells)               => (range.clone(), new_cells.len()),
+                RopeAction::ReplaceAttributes(range, new_cells, _)  => (range.clone(), new_cells.len()),
+                RopeAction::SetAttributes(_, _)                     => { return matches; }
+            };
+
+            action.apply_to_vec(&mut cells);
+            rescan_matches(matches, edited, new_len, &cells, &matcher)
+        });
+
+        BindRef::from(matches)
+    }
 }
 
 impl<Cell, Attribute> RopeBinding<Cell, Attribute>
@@ -252,19 +537,7 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
 
                     // Figure out the differences between the old and the new values
                     let new_cells       = value_iter.into_iter().collect::<Vec<_>>();
-                    let mut differences = capture_diff_slices(Algorithm::Myers, &last_cells, &new_cells);
-                    differences.sort_by(|a, b| a.new_range().start.cmp(&b.new_range().start));
-
-                    let mut actions     = vec![];
-                    for diff in differences {
-                        use self::DiffOp::*;
-                        match diff {
-                            Equal { old_index: _, new_index: _, len: _ }            => { /* No difference */ },
-                            Delete { old_index: _, old_len, new_index }             => { actions.push(RopeAction::Replace(new_index..(new_index+old_len), vec![])) },
-                            Insert { old_index: _, new_index, new_len }             => { actions.push(RopeAction::Replace(new_index..new_index, new_cells[new_index..(new_index+new_len)].iter().cloned().collect())) },
-                            Replace { old_index: _, old_len, new_index, new_len }   => { actions.push(RopeAction::Replace(new_index..(new_index+old_len), new_cells[new_index..(new_index+new_len)].iter().cloned().collect())) }
-                        }
-                    }
+                    let actions         = diff_actions(&last_cells, &new_cells);
 
                     last_cells          = new_cells;
 
@@ -289,23 +562,7 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     ///
     fn follow_changes(&self) -> RopeStream<Cell, Attribute> {
         // Fetch an ID for the next stream from the core and generate a state
-        let stream_id = self.core.sync(|core| {
-            // Assign an ID to the stream
-            let next_id = core.next_stream_id;
-            core.next_stream_id += 1;
-
-            // Create a state for this stream
-            let state = RopeStreamState {
-                identifier:         next_id,
-                waker:              None,
-                pending_changes:    VecDeque::new(),
-                needs_pull:         false,
-            };
-            core.stream_states.push(state);
-
-            // Return the stream ID
-            next_id
-        });
+        let stream_id = self.core.sync(|core| core.add_stream_state(false, false));
 
         // Create the stream
         RopeStream {
@@ -326,22 +583,8 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     fn follow_changes_retained(&self) -> RopeStream<Cell, Attribute> {
         // Fetch an ID for the next stream from the core and generate a state
         let stream_id = self.core.sync(|core| {
-            // Assign an ID to the stream
-            let next_id = core.next_stream_id;
-            core.next_stream_id += 1;
-            core.usage_count    += 1;
-
-            // Create a state for this stream
-            let state = RopeStreamState {
-                identifier:         next_id,
-                waker:              None,
-                pending_changes:    VecDeque::new(),
-                needs_pull:         false,
-            };
-            core.stream_states.push(state);
-
-            // Return the stream ID
-            next_id
+            core.usage_count += 1;
+            core.add_stream_state(false, false)
         });
 
         // Create the stream
@@ -353,6 +596,59 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
             retains_core:   true,
         }
     }
+
+    ///
+    /// Creates a stream that follows the changes to this rope, computing the changes when the stream is polled
+    ///
+    /// Rather than storing every change until the stream reads it, this stream only tracks which part of the rope has
+    /// changed, and reads the cells in that part of the rope when it's polled. This uses less memory for streams that are
+    /// read infrequently, but it will generally return larger changes than `follow_changes()`.
+    ///
+    fn follow_changes_pull(&self) -> RopeStream<Cell, Attribute> {
+        // Fetch an ID for the next stream from the core and generate a state
+        let stream_id = self.core.sync(|core| core.add_stream_state(true, false));
+
+        // Create the stream
+        RopeStream {
+            identifier:     stream_id,
+            core:           self.core.clone(),
+            poll_future:    None,
+            draining:       VecDeque::new(),
+            retains_core:   false,
+        }
+    }
+
+    ///
+    /// Reads the current contents of this rope and creates a stream that follows the changes made after that point
+    ///
+    fn read_and_follow_changes(&self, read_fn: &mut (dyn Send+FnMut(&dyn Rope<Cell=Cell, Attribute=Attribute>))) -> RopeStream<Cell, Attribute> {
+        // The rope is read and the stream is created in the same operation on the core, so no edits can happen in between
+        let stream_id = self.core.sync(|core| {
+            core.pull_rope();
+            read_fn(&core.rope);
+
+            core.add_stream_state(false, false)
+        });
+
+        // Create the stream
+        RopeStream {
+            identifier:     stream_id,
+            core:           self.core.clone(),
+            poll_future:    None,
+            draining:       VecDeque::new(),
+            retains_core:   false,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl<Cell, Attribute> FlushPending for RopeBinding<Cell, Attribute>
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    fn flush_pending(&self) {
+        self.core.sync(|_| { });
+    }
 }
 
 impl<Cell, Attribute> Clone for RopeBinding<Cell, Attribute>
@@ -413,6 +709,10 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
 
         Box::new(releasable)
     }
+
+    fn release_unused_notifications(&self) {
+        self.core.desync(|core| core.filter_unused_notifications());
+    }
 }
 
 ///
@@ -1,10 +1,14 @@
 use crate::traits::*;
 use crate::releasable::*;
 use crate::binding_context::*;
+#[cfg(any(test, feature = "test-support"))]
+use crate::flush_pending::*;
 use crate::rope_binding::core::*;
 use crate::rope_binding::stream::*;
 use crate::rope_binding::bound_rope::*;
-use crate::rope_binding::stream_state::*;
+use crate::rope_binding::cursor::*;
+use crate::rope_binding::insert_policy::*;
+use crate::rope_binding::builder::*;
 
 use flo_rope::*;
 use ::desync::*;
@@ -35,6 +39,24 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     /// Creates a new rope binding from a stream of changes
     ///
     pub fn new() -> RopeBindingMut<Cell, Attribute> {
+        Self::from_attributed_rope(AttributedRope::new())
+    }
+
+    ///
+    /// Returns a builder that can be used to set up the initial contents of a rope before it's created
+    ///
+    /// Edits made to a `RopeBindingMut` have to update the state used for following the rope, so this is faster when
+    /// loading a large amount of content that nothing is following yet. As with any other rope, streams that follow the
+    /// built rope only return the changes that are made after they were created.
+    ///
+    pub fn builder() -> RopeBindingMutBuilder<Cell, Attribute> {
+        RopeBindingMutBuilder::new()
+    }
+
+    ///
+    /// Creates a new rope binding with some initial content
+    ///
+    pub (super) fn from_attributed_rope(rope: AttributedRope<Cell, Attribute>) -> RopeBindingMut<Cell, Attribute> {
         // Create the core
         let core        = RopeBindingCore {
             usage_count:    1,
@@ -49,7 +71,7 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         // Recreate the rope in the core with a version that responds to pull events
         let weak_core   = Arc::downgrade(&core);
         core.sync(move |core| {
-            core.rope = PullRope::from(AttributedRope::new(), Box::new(move || {
+            core.rope = PullRope::from(rope, Box::new(move || {
                 // Pass the event through to the core
                 let core = weak_core.upgrade();
                 if let Some(core) = core {
@@ -91,6 +113,25 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         cells.into_iter()
     }
 
+    ///
+    /// Calls a function with an iterator over the cells in a range of this rope, without copying them
+    ///
+    /// This avoids the allocation that `read_cells()` needs to make, which is useful when reading large ropes frequently. The
+    /// rope is locked while the function runs, so it should return quickly, and must not try to read from or edit this rope
+    /// (which will deadlock).
+    ///
+    pub fn with_cells<TResult, TFn>(&self, range: Range<usize>, action: TFn) -> TResult
+    where
+    TResult:    Send,
+    TFn:        Send+FnOnce(&mut dyn Iterator<Item=&Cell>) -> TResult {
+        BindingContext::add_dependency(self.clone());
+
+        self.core.sync(move |core| {
+            core.pull_rope();
+            action(&mut core.rope.read_cells(range))
+        })
+    }
+
     ///
     /// Returns the attributes set at the specified location and their extent
     ///
@@ -113,7 +154,7 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     pub fn edit(&self, action: RopeAction<Cell, Attribute>) {
         self.core.sync(move |core| {
             core.rope.edit(action);
-            core.wake();
+            core.end_edit();
         });
     }
 
@@ -124,7 +165,127 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     pub fn replace<NewCells: 'static+Send+IntoIterator<Item=Cell>>(&self, range: Range<usize>, new_cells: NewCells) {
         self.core.sync(move |core| {
             core.rope.replace(range, new_cells);
-            core.wake();
+            core.end_edit();
+        });
+    }
+
+    ///
+    /// Replaces a range of cells if the new cells are different from the existing ones, returning true if the rope was changed
+    ///
+    /// This is the same as `replace()` except that nothing is edited (and nothing following the rope is woken) if the range
+    /// already contains the new cells, so callers can skip any work that would follow a change.
+    ///
+    pub fn try_replace<NewCells: 'static+Send+IntoIterator<Item=Cell>>(&self, range: Range<usize>, new_cells: NewCells) -> bool {
+        self.core.sync(move |core| {
+            let new_cells   = new_cells.into_iter().collect::<Vec<_>>();
+            let len         = core.rope.len();
+            let end         = range.end.min(len);
+            let start       = range.start.min(end);
+            let unchanged   = core.rope.read_cells(start..end).eq(new_cells.iter());
+
+            if unchanged {
+                false
+            } else {
+                core.rope.replace(range, new_cells);
+                core.end_edit();
+
+                true
+            }
+        })
+    }
+
+    ///
+    /// Removes every cell that doesn't match a predicate, returning true if any cells were removed
+    ///
+    /// All of the cells are removed before anything following the rope is woken.
+    ///
+    pub fn retain_cells<TFn: Send+Fn(&Cell) -> bool>(&self, predicate: TFn) -> bool {
+        self.core.sync(move |core| {
+            // Find the ranges of cells that should be removed
+            let mut ranges      = vec![];
+            let mut range_start = None;
+
+            for (idx, cell) in core.rope.read_cells(0..core.rope.len()).enumerate() {
+                match (range_start, predicate(cell)) {
+                    (None, false)       => { range_start = Some(idx); }
+                    (Some(start), true) => { ranges.push(start..idx); range_start = None; }
+                    _                   => { }
+                }
+            }
+
+            if let Some(start) = range_start {
+                ranges.push(start..core.rope.len());
+            }
+
+            // Remove the ranges, starting at the end so the earlier ranges aren't moved
+            if ranges.is_empty() {
+                false
+            } else {
+                for range in ranges.into_iter().rev() {
+                    core.rope.replace(range, vec![]);
+                }
+
+                core.end_edit();
+                true
+            }
+        })
+    }
+
+    ///
+    /// Shortens the rope to the specified length, removing any cells after that point
+    ///
+    /// This has no effect if the rope is already shorter than the specified length.
+    ///
+    pub fn truncate(&self, len: usize) {
+        self.core.sync(move |core| {
+            let old_len = core.rope.len();
+
+            if len < old_len {
+                core.rope.replace(len..old_len, vec![]);
+                core.end_edit();
+            }
+        });
+    }
+
+    ///
+    /// Changes the length of the rope, either by removing cells from the end or by adding copies of `filler` to the end
+    ///
+    /// Added cells have the same attributes as the last cell in the rope.
+    ///
+    pub fn resize(&self, new_len: usize, filler: Cell) {
+        self.core.sync(move |core| {
+            let old_len = core.rope.len();
+
+            if new_len < old_len {
+                core.rope.replace(new_len..old_len, vec![]);
+                core.end_edit();
+            } else if new_len > old_len {
+                let attributes = if old_len > 0 { core.rope.read_attributes(old_len-1).0.clone() } else { Attribute::default() };
+
+                core.rope.replace_attributes(old_len..old_len, vec![filler; new_len-old_len], attributes);
+                core.end_edit();
+            }
+        });
+    }
+
+    ///
+    /// Replaces a range of cells, using a policy to decide which attributes are applied to the new cells
+    ///
+    /// `replace()` gives the new cells the attributes of the cell before the replaced range (or of the first cell, if the range
+    /// is at the start of the rope), which is not always what's wanted when inserting at the boundary between two differently
+    /// styled ranges.
+    ///
+    pub fn edit_with_policy<NewCells: 'static+Send+IntoIterator<Item=Cell>>(&self, range: Range<usize>, new_cells: NewCells, policy: InsertPolicy) {
+        self.core.sync(move |core| {
+            let len         = core.rope.len();
+            let attributes  = match policy {
+                InsertPolicy::InheritFromLeft  if range.start > 0  => core.rope.read_attributes(range.start-1).0.clone(),
+                InsertPolicy::InheritFromRight if range.end < len  => core.rope.read_attributes(range.end).0.clone(),
+                _                                                   => Attribute::default()
+            };
+
+            core.rope.replace_attributes(range, new_cells, attributes);
+            core.end_edit();
         });
     }
 
@@ -134,7 +295,76 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     pub fn set_attributes(&self, range: Range<usize>, new_attributes: Attribute) {
         self.core.sync(move |core| {
             core.rope.set_attributes(range, new_attributes);
-            core.wake();
+            core.end_edit();
+        });
+    }
+
+    ///
+    /// Sets the attributes for every cell that matches a predicate
+    ///
+    /// Adjacent matching cells are grouped into ranges, so this performs one attribute change per range of matching cells
+    /// and only wakes anything following the rope once all of the changes have been made.
+    ///
+    pub fn set_attributes_matching<TFn: Send+Fn(&Cell) -> bool>(&self, predicate: TFn, new_attributes: Attribute) {
+        self.core.sync(move |core| {
+            // Find the ranges of cells that match the predicate
+            let mut ranges      = vec![];
+            let mut range_start = None;
+
+            for (idx, cell) in core.rope.read_cells(0..core.rope.len()).enumerate() {
+                match (range_start, predicate(cell)) {
+                    (None, true)         => { range_start = Some(idx); }
+                    (Some(start), false) => { ranges.push(start..idx); range_start = None; }
+                    _                    => { }
+                }
+            }
+
+            if let Some(start) = range_start {
+                ranges.push(start..core.rope.len());
+            }
+
+            // Set the attributes for each range
+            if !ranges.is_empty() {
+                for range in ranges {
+                    core.rope.set_attributes(range, new_attributes.clone());
+                }
+
+                core.end_edit();
+            }
+        });
+    }
+
+    ///
+    /// Replaces each cell in a range with the result of calling a function on it
+    ///
+    /// The cells are read and written back in a single operation on the rope, so no other edit can be made to the range
+    /// while it's being updated. The attributes of the cells are left as they were, and anything following the rope is
+    /// only woken once every cell has been updated.
+    ///
+    pub fn map_cells_in_range<TFn: Send+Fn(Cell) -> Cell>(&self, range: Range<usize>, map_fn: TFn) {
+        self.core.sync(move |core| {
+            let end     = range.end.min(core.rope.len());
+            let mut pos = range.start;
+
+            // Replace the cells one attribute run at a time so that the attributes are preserved
+            while pos < end {
+                let (attr, attr_range)  = core.rope.read_attributes(pos);
+                if attr_range.is_empty() {
+                    pos += 1;
+                    continue;
+                }
+
+                let attr                = attr.clone();
+                let run_end             = attr_range.end.min(end);
+                let cells               = core.rope.read_cells(pos..run_end).cloned().map(&map_fn).collect::<Vec<_>>();
+
+                core.rope.replace_attributes(pos..run_end, cells, attr);
+                pos = run_end;
+            }
+
+            if range.start < end {
+                core.end_edit();
+            }
         });
     }
 
@@ -144,8 +374,102 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     pub fn replace_attributes<NewCells: 'static+Send+IntoIterator<Item=Cell>>(&self, range: Range<usize>, new_cells: NewCells, new_attributes: Attribute) {
         self.core.sync(move |core| {
             core.rope.replace_attributes(range, new_cells, new_attributes); 
-            core.wake();
+            core.end_edit();
+        });
+    }
+
+    ///
+    /// Inserts the contents of another rope at the specified position
+    ///
+    /// The other rope is read as a snapshot, and its cells are inserted along with their attributes. All of the cells
+    /// are inserted before anything following this rope is woken, so followers see a single insertion.
+    ///
+    pub fn insert_rope<OtherRope: BoundRope<Cell, Attribute>>(&self, pos: usize, other: &OtherRope) {
+        // Take a snapshot of the other rope (this is read in a single operation on its core)
+        let snapshot = other.get();
+
+        self.core.sync(move |core| {
+            let len         = snapshot.len();
+            let mut offset  = 0;
+
+            // Insert each run of attributes in turn
+            while offset < len {
+                let (attr, range) = snapshot.read_attributes(offset);
+                if range.is_empty() {
+                    offset += 1;
+                    continue;
+                }
+
+                let cells = snapshot.read_cells(range.clone()).cloned().collect::<Vec<_>>();
+                core.rope.replace_attributes((pos+offset)..(pos+offset), cells, attr.clone());
+
+                offset = range.end;
+            }
+
+            if len > 0 {
+                core.end_edit();
+            }
+        });
+    }
+
+    ///
+    /// Creates a cursor at the specified position that moves as this rope is edited
+    ///
+    pub fn cursor(&self, position: usize) -> RopeCursor {
+        // Any edits that haven't been pulled yet were made before the cursor was created, so they shouldn't move it
+        self.core.sync(|core| core.pull_rope());
+
+        RopeCursor::new(position, self.follow_changes())
+    }
+
+    ///
+    /// Performs an operation directly on the underlying rope
+    ///
+    /// This provides access to the full `flo_rope` API for operations that aren't provided by this binding. Edits made
+    /// to the rope are tracked by the `PullRope` in the same way as edits made through the binding, so any streams
+    /// following this rope will receive them. Followers are woken once the operation completes.
+    ///
+    pub fn with_rope<TResult, TFn>(&self, action: TFn) -> TResult
+    where
+    TResult:    Send,
+    TFn:        Send+FnOnce(&mut PullRope<AttributedRope<Cell, Attribute>, Box<dyn Fn()+Send+Sync>>) -> TResult {
+        self.core.sync(move |core| {
+            let result = action(&mut core.rope);
+            core.end_edit();
+
+            result
+        })
+    }
+
+    ///
+    /// Creates a stream that follows the changes to this rope, returning the actions made by each edit as a group
+    ///
+    /// A single edit, such as a call to `replace()`, can produce several `RopeAction`s. This stream returns all of the actions
+    /// for an edit together, so consumers that need to apply whole edits at once can tell where each edit begins and ends.
+    /// Edits made before the stream was created are not returned.
+    ///
+    /// While this stream exists, the changes from each edit are pulled from the rope immediately, so streams created by
+    /// `follow_changes()` will receive the changes from separate edits separately too.
+    ///
+    pub fn follow_changes_grouped(&self) -> RopeGroupedStream<Cell, Attribute> {
+        // Fetch an ID for the next stream from the core and generate a state
+        let stream_id = self.core.sync(|core| {
+            // Any changes that haven't been pulled yet are sent to the existing streams
+            core.pull_rope();
+
+            core.add_stream_state(false, true)
         });
+
+        // Create the stream
+        let changes = RopeStream {
+            identifier:     stream_id,
+            core:           self.core.clone(),
+            poll_future:    None,
+            draining:       VecDeque::new(),
+            retains_core:   false,
+        };
+
+        RopeGroupedStream { changes }
     }
 }
 
@@ -158,23 +482,7 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     ///
     fn follow_changes(&self) -> RopeStream<Cell, Attribute> {
         // Fetch an ID for the next stream from the core and generate a state
-        let stream_id = self.core.sync(|core| {
-            // Assign an ID to the stream
-            let next_id = core.next_stream_id;
-            core.next_stream_id += 1;
-
-            // Create a state for this stream
-            let state = RopeStreamState {
-                identifier:         next_id,
-                waker:              None,
-                pending_changes:    VecDeque::new(),
-                needs_pull:         false,
-            };
-            core.stream_states.push(state);
-
-            // Return the stream ID
-            next_id
-        });
+        let stream_id = self.core.sync(|core| core.add_stream_state(false, false));
 
         // Create the stream
         RopeStream {
@@ -196,6 +504,59 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         // Mutable ropes can't continue to receive changes after they've been dropped so this still stops the stream once all copies of this rope are gone
         self.follow_changes()
     }
+
+    ///
+    /// Creates a stream that follows the changes to this rope, computing the changes when the stream is polled
+    ///
+    /// Rather than storing every change until the stream reads it, this stream only tracks which part of the rope has
+    /// changed, and reads the cells in that part of the rope when it's polled. This uses less memory for streams that are
+    /// read infrequently, but it will generally return larger changes than `follow_changes()`.
+    ///
+    fn follow_changes_pull(&self) -> RopeStream<Cell, Attribute> {
+        // Fetch an ID for the next stream from the core and generate a state
+        let stream_id = self.core.sync(|core| core.add_stream_state(true, false));
+
+        // Create the stream
+        RopeStream {
+            identifier:     stream_id,
+            core:           self.core.clone(),
+            poll_future:    None,
+            draining:       VecDeque::new(),
+            retains_core:   false,
+        }
+    }
+
+    ///
+    /// Reads the current contents of this rope and creates a stream that follows the changes made after that point
+    ///
+    fn read_and_follow_changes(&self, read_fn: &mut (dyn Send+FnMut(&dyn Rope<Cell=Cell, Attribute=Attribute>))) -> RopeStream<Cell, Attribute> {
+        // The rope is read and the stream is created in the same operation on the core, so no edits can happen in between
+        let stream_id = self.core.sync(|core| {
+            core.pull_rope();
+            read_fn(&core.rope);
+
+            core.add_stream_state(false, false)
+        });
+
+        // Create the stream
+        RopeStream {
+            identifier:     stream_id,
+            core:           self.core.clone(),
+            poll_future:    None,
+            draining:       VecDeque::new(),
+            retains_core:   false,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl<Cell, Attribute> FlushPending for RopeBindingMut<Cell, Attribute>
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    fn flush_pending(&self) {
+        self.core.sync(|_| { });
+    }
 }
 
 impl<Cell, Attribute> Clone for RopeBindingMut<Cell, Attribute>
@@ -256,6 +617,10 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
 
         Box::new(releasable)
     }
+
+    fn release_unused_notifications(&self) {
+        self.core.desync(|core| core.filter_unused_notifications());
+    }
 }
 
 
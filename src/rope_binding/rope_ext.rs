@@ -1,5 +1,12 @@
 use crate::rope_binding::bound_rope::*;
 use crate::rope_binding::rope_binding::*;
+use crate::rope_binding::rope_action_ext::*;
+use crate::rope_binding::stream::*;
+use crate::rope_binding::acked_stream::*;
+#[cfg(feature = "stream")]
+use crate::bindref::*;
+#[cfg(feature = "stream")]
+use crate::bind_stream::*;
 
 use futures::prelude::*;
 use futures::stream;
@@ -7,7 +14,7 @@ use futures::task::{Poll};
 
 use flo_rope::*;
 
-use std::iter;
+use std::ops::{Range};
 use std::collections::{VecDeque};
 
 ///
@@ -22,11 +29,105 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     ///
     fn chain<OtherRope: BoundRope<Cell, Attribute>>(&self, other: &OtherRope) -> RopeBinding<Cell, Attribute>;
 
+    ///
+    /// Returns a new rope that concatenates the contents of this rope and another one, with a separator between them
+    ///
+    /// The separator cells are only present when both ropes have some content, so there's never a leading or trailing
+    /// separator. The separator cells have the default attribute.
+    ///
+    fn chain_with_separator<OtherRope: BoundRope<Cell, Attribute>>(&self, other: &OtherRope, separator: Vec<Cell>) -> RopeBinding<Cell, Attribute>;
+
     ///
     /// Returns a new rope that maps the values of the cells to new values
     ///
     fn map<NewCell, MapFn: 'static+Send+Fn(Cell) -> NewCell>(&self, map_fn: MapFn) -> RopeBinding<NewCell, Attribute>
     where NewCell: 'static+Send+Unpin+Clone+PartialEq;
+
+    ///
+    /// Returns a new rope where each cell is generated from a window of the cells around the same position in this rope
+    ///
+    /// The map function is called with up to `window_size` cells either side of each position, along with the index of the
+    /// cell at that position within the window (the window is shorter near the start and end of the rope). When this rope
+    /// is edited, the cells whose windows include any of the changed cells are generated again. This is useful for things
+    /// like text shaping, where the appearance of a character can depend on the characters around it.
+    ///
+    fn map_windowed<NewCell, MapFn: 'static+Send+Fn(&[Cell], usize) -> NewCell>(&self, window_size: usize, map_fn: MapFn) -> RopeBinding<NewCell, Attribute>
+    where NewCell: 'static+Send+Unpin+Clone+PartialEq;
+
+    ///
+    /// Returns a new rope where each cell is paired with its index in this rope
+    ///
+    /// The indexes are kept up to date as this rope is edited: when cells are inserted or removed, the cells that come
+    /// after the edit are renumbered. This is useful for things like displaying line numbers next to a list of lines.
+    ///
+    fn enumerate(&self) -> RopeBinding<(usize, Cell), Attribute>;
+
+    ///
+    /// Returns a new rope where the cells with attributes that match a predicate are transformed by a map function
+    ///
+    /// Cells whose attributes don't match are passed through unchanged. Both the cells and their attributes are tracked, so
+    /// setting the attributes of a range of cells transforms or restores them as needed. This is useful for applying effects
+    /// to styled regions, such as redacting all of the cells with a 'secret' attribute.
+    ///
+    fn map_cells_with_attribute<PredFn, MapFn>(&self, attribute_pred: PredFn, map_fn: MapFn) -> RopeBinding<Cell, Attribute>
+    where
+    PredFn: 'static+Send+Fn(&Attribute) -> bool,
+    MapFn:  'static+Send+Fn(Cell) -> Cell;
+
+    ///
+    /// Splits this rope into two ropes at the specified index
+    ///
+    /// The first rope contains the cells before the index and the second contains the cells from the index onwards. These
+    /// continue to track this rope as it's edited: for example, if a cell is inserted before the split point, the last cell
+    /// of the first rope will move to the start of the second rope.
+    ///
+    fn split_at(&self, index: usize) -> (RopeBinding<Cell, Attribute>, RopeBinding<Cell, Attribute>);
+
+    ///
+    /// Returns a new rope that always contains exactly `length` cells
+    ///
+    /// If this rope is longer than `length` cells, the result is truncated, and if it's shorter, the result is padded at
+    /// the end with copies of the `filler` cell. Padding cells have the default attribute.
+    ///
+    fn fixed_length(&self, length: usize, filler: Cell) -> RopeBinding<Cell, Attribute>;
+
+    ///
+    /// Returns a stream of the full contents of this rope, starting with its current contents and then returning a new
+    /// snapshot each time it changes
+    ///
+    /// This is useful for consumers that can't apply incremental updates to a copy of the rope. Any changes that are made to
+    /// the rope between reads from the stream are combined into a single snapshot.
+    ///
+    fn follow_snapshots(&self) -> RopeSnapshotStream<Cell, Attribute>;
+
+    ///
+    /// Returns a stream of the number of cells inserted and deleted by each change to this rope
+    ///
+    fn follow_change_stats(&self) -> RopeChangeStatsStream<Cell, Attribute>;
+
+    ///
+    /// Returns a stream of the changes to this rope where each change has a version number that can be acknowledged
+    ///
+    /// Changes are kept until they're acknowledged by calling `ack()` on the stream or its acknowledger. If the consumer
+    /// fails before applying all of the changes, `RopeAcknowledger::resume()` returns a new stream that delivers the
+    /// changes that weren't acknowledged again before continuing with any new changes.
+    ///
+    fn follow_changes_acked(&self) -> RopeAckedStream<Cell, Attribute>;
+
+    ///
+    /// Returns a binding that folds the cells of this rope into a single value, and updates it incrementally as the rope changes
+    ///
+    /// `fold_fn` adds a cell to the accumulated value and `unfold_fn` removes one. When the rope is edited, only the cells that
+    /// were removed are passed to `unfold_fn` and only the cells that were added are passed to `fold_fn`, so the whole rope does
+    /// not need to be folded again. For example, a binding to the sum of a rope of numbers can be created using
+    /// `rope.fold_binding(0, |sum, cell| sum + cell, |sum, cell| sum - cell)`.
+    ///
+    #[cfg(feature = "stream")]
+    fn fold_binding<Value, FoldFn, UnfoldFn>(&self, initial: Value, fold_fn: FoldFn, unfold_fn: UnfoldFn) -> BindRef<Value>
+    where
+    Value:      'static+Send+Clone+PartialEq,
+    FoldFn:     'static+Send+Fn(Value, &Cell) -> Value,
+    UnfoldFn:   'static+Send+Fn(Value, &Cell) -> Value;
 }
 
 impl<Cell, Attribute, TRope> BoundRopeExt<Cell, Attribute> for TRope
@@ -35,83 +136,690 @@ Cell:       'static+Send+Unpin+Clone+PartialEq,
 Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default,
 TRope:      BoundRope<Cell, Attribute> {
     fn chain<OtherRope: BoundRope<Cell, Attribute>>(&self, other: &OtherRope) -> RopeBinding<Cell, Attribute> {
-        // Follow the left and right-hand streams
-        let mut follow_left     = Some(self.follow_changes_retained());
-        let mut follow_right    = Some(other.follow_changes_retained());
+        chain_ropes(self.follow_changes_retained(), other.follow_changes_retained(), None)
+    }
+
+    fn chain_with_separator<OtherRope: BoundRope<Cell, Attribute>>(&self, other: &OtherRope, separator: Vec<Cell>) -> RopeBinding<Cell, Attribute> {
+        chain_ropes(self.follow_changes_retained(), other.follow_changes_retained(), Some(separator))
+    }
 
-        // Concatenator and pending values
-        let mut pending         = VecDeque::new();
-        let mut concatenator    = RopeConcatenator::new();
+    fn map<NewCell, MapFn: 'static+Send+Fn(Cell) -> NewCell>(&self, map_fn: MapFn) -> RopeBinding<NewCell, Attribute>
+    where NewCell: 'static+Send+Unpin+Clone+PartialEq {
+        // Follow the changes to this stream
+        let mut changes     = self.follow_changes();
+
+        // Process them via the map function
+        let mapped_stream   = stream::poll_fn(move |ctxt| {
+            use RopeAction::*;
+
+            match changes.poll_next_unpin(ctxt) {
+                Poll::Ready(None)                                               => Poll::Ready(None),
+                Poll::Pending                                                   => Poll::Pending,
+                Poll::Ready(Some(Replace(range, cells)))                        => Poll::Ready(Some(Replace(range, cells.into_iter().map(&map_fn).collect()))),
+                Poll::Ready(Some(SetAttributes(range, attributes)))             => Poll::Ready(Some(SetAttributes(range, attributes))),
+                Poll::Ready(Some(ReplaceAttributes(range, cells, attributes)))  => Poll::Ready(Some(ReplaceAttributes(range, cells.into_iter().map(&map_fn).collect(), attributes)))
+            }
+        });
+
+        RopeBinding::from_stream(mapped_stream)
+    }
+
+    fn map_windowed<NewCell, MapFn: 'static+Send+Fn(&[Cell], usize) -> NewCell>(&self, window_size: usize, map_fn: MapFn) -> RopeBinding<NewCell, Attribute>
+    where NewCell: 'static+Send+Unpin+Clone+PartialEq {
+        // We track the content of the rope so the windows around the changed cells can be read (starting with its current contents)
+        let mut cells       = vec![];
+        let mut runs        = vec![];
+        let mut changes     = self.read_and_follow_changes(&mut |rope| {
+            cells   = rope.read_cells(0..rope.len()).cloned().collect();
+            runs    = attribute_runs(rope);
+        });
+
+        // The attributes are tracked too, so the cells next to an edit can be generated again without changing their attributes
+        let mut attribute_rope = AttributedRope::<(), Attribute>::new();
+        for (attributes, range) in runs.iter() {
+            attribute_rope.replace_attributes(range.start..range.start, range.clone().map(|_| ()), attributes.clone());
+        }
+
+        // Generates the output cell for a position in the rope
+        let map_cell        = move |cells: &[Cell], pos: usize| {
+            let window_start    = pos.saturating_sub(window_size);
+            let window_end      = (pos + window_size + 1).min(cells.len());
+
+            map_fn(&cells[window_start..window_end], pos - window_start)
+        };
+
+        // The windowed rope starts with the current contents of this rope, mapped one run of attributes at a time
+        let mut pending     = runs.into_iter()
+            .map(|(attributes, range)| RopeAction::ReplaceAttributes(range.start..range.start, range.map(|pos| map_cell(&cells, pos)).collect(), attributes))
+            .collect::<VecDeque<_>>();
+
+        let windowed_stream = stream::poll_fn(move |ctxt| {
+            use RopeAction::*;
 
-        // Create a new polling stream that concatenates the two sides
-        let concat_stream       = stream::poll_fn(move |ctxt| {
             if let Some(next) = pending.pop_front() {
                 // Always process pending changes first
                 return Poll::Ready(Some(next));
             }
 
-            // Process left-hand side changes, if there are any
-            let poll_left = follow_left.as_mut().map(|left| left.poll_next_unpin(ctxt));
-
-            match poll_left {
-                None                            => { }
-                Some(Poll::Pending)             => { }
-                Some(Poll::Ready(None))         => { follow_left = None; }
-                Some(Poll::Ready(Some(action))) => {
-                    // Send to the LHS 
-                    for action in concatenator.send_left(iter::once(action)) {
-                        pending.push_back(action);
-                    }
+            let action = match changes.poll_next_unpin(ctxt) {
+                Poll::Ready(Some(action))   => action,
+                Poll::Ready(None)           => { return Poll::Ready(None); }
+                Poll::Pending               => { return Poll::Pending; }
+            };
+
+            action.apply_to_vec(&mut cells);
+            attribute_rope.edit(match &action {
+                Replace(range, new_cells)                       => Replace(range.clone(), vec![(); new_cells.len()]),
+                SetAttributes(range, attributes)                => SetAttributes(range.clone(), attributes.clone()),
+                ReplaceAttributes(range, new_cells, attributes) => ReplaceAttributes(range.clone(), vec![(); new_cells.len()], attributes.clone()),
+            });
 
-                    // Return any pending actions
-                    if let Some(next) = pending.pop_front() { return Poll::Ready(Some(next)); }
+            // Generate the cells that replace the edited range (keeping the same attributes as the original action)
+            let (start, new_len) = match action {
+                SetAttributes(range, attributes) => {
+                    // The cells are unchanged, so only the attributes need to be updated
+                    return Poll::Ready(Some(SetAttributes(range, attributes)));
                 }
-            }
 
-            // Process right-hand side changes, if there are any
-            let poll_right = follow_right.as_mut().map(|right| right.poll_next_unpin(ctxt));
-
-            match poll_right {
-                None                            => { }
-                Some(Poll::Pending)             => { }
-                Some(Poll::Ready(None))         => { follow_right = None; }
-                Some(Poll::Ready(Some(action))) => {
-                    // Send to the LHS 
-                    for action in concatenator.send_right(iter::once(action)) {
-                        pending.push_back(action);
-                    }
+                Replace(range, new_cells) => {
+                    let (start, new_len) = (range.start, new_cells.len());
+                    pending.push_back(Replace(range, (start..(start+new_len)).map(|pos| map_cell(&cells, pos)).collect()));
+
+                    (start, new_len)
+                }
+
+                ReplaceAttributes(range, new_cells, attributes) => {
+                    let (start, new_len) = (range.start, new_cells.len());
+                    pending.push_back(ReplaceAttributes(range, (start..(start+new_len)).map(|pos| map_cell(&cells, pos)).collect(), attributes));
 
-                    // Return any pending actions
-                    if let Some(next) = pending.pop_front() { return Poll::Ready(Some(next)); }
+                    (start, new_len)
                 }
+            };
+
+            // The cells either side of the edit have different windows, so are generated again (replacing a cell on its own would give it
+            // the attributes of the cell before it, so the attributes are set explicitly)
+            let before  = start.saturating_sub(window_size)..start;
+            let after   = (start+new_len)..(start+new_len+window_size).min(cells.len());
+
+            for pos in before.chain(after) {
+                let cell_attributes = attribute_rope.read_attributes(pos).0.clone();
+                pending.push_back(ReplaceAttributes(pos..(pos+1), vec![map_cell(&cells, pos)], cell_attributes));
             }
 
-            // No actions: will be woken up once something happens to either of the two streams
-            Poll::Pending
+            Poll::Ready(pending.pop_front())
         });
 
-        // Result is a rope reading from this stream
-        RopeBinding::from_stream(concat_stream)
+        RopeBinding::from_stream(windowed_stream)
     }
 
-    fn map<NewCell, MapFn: 'static+Send+Fn(Cell) -> NewCell>(&self, map_fn: MapFn) -> RopeBinding<NewCell, Attribute>
-    where NewCell: 'static+Send+Unpin+Clone+PartialEq {
-        // Follow the changes to this stream
-        let mut changes     = self.follow_changes();
+    fn enumerate(&self) -> RopeBinding<(usize, Cell), Attribute> {
+        // We track the content of the rope so the cells after an edit can be renumbered (starting with its current contents)
+        let mut rope_copy   = AttributedRope::<Cell, Attribute>::new();
+        let mut changes     = self.read_and_follow_changes(&mut |rope| {
+            for action in contents_actions(rope) {
+                rope_copy.edit(action);
+            }
+        });
 
-        // Process them via the map function
-        let mapped_stream   = stream::poll_fn(move |ctxt| {
+        // Numbers the cells in a range of the rope, a run of attributes at a time so the attributes can be kept
+        let number_runs     = |rope_copy: &AttributedRope<Cell, Attribute>, range: Range<usize>| {
+            let mut runs    = vec![];
+            let mut pos     = range.start;
+
+            while pos < range.end {
+                let (attributes, run)   = rope_copy.read_attributes(pos);
+                let run_end             = run.end.min(range.end).max(pos+1);
+                let cells               = rope_copy.read_cells(pos..run_end).cloned().enumerate().map(|(idx, cell)| (pos+idx, cell)).collect::<Vec<_>>();
+
+                runs.push((pos..run_end, cells, attributes.clone()));
+                pos = run_end;
+            }
+
+            runs
+        };
+
+        // The enumerated rope starts with the current contents of this rope
+        let mut pending     = number_runs(&rope_copy, 0..rope_copy.len()).into_iter()
+            .map(|(range, cells, attributes)| RopeAction::ReplaceAttributes(range.start..range.start, cells, attributes))
+            .collect::<VecDeque<_>>();
+
+        let enumerated_stream = stream::poll_fn(move |ctxt| {
             use RopeAction::*;
 
-            match changes.poll_next_unpin(ctxt) {
-                Poll::Ready(None)                                               => Poll::Ready(None),
-                Poll::Pending                                                   => Poll::Pending,
-                Poll::Ready(Some(Replace(range, cells)))                        => Poll::Ready(Some(Replace(range, cells.into_iter().map(&map_fn).collect()))),
-                Poll::Ready(Some(SetAttributes(range, attributes)))             => Poll::Ready(Some(SetAttributes(range, attributes))),
-                Poll::Ready(Some(ReplaceAttributes(range, cells, attributes)))  => Poll::Ready(Some(ReplaceAttributes(range, cells.into_iter().map(&map_fn).collect(), attributes)))
+            if let Some(next) = pending.pop_front() {
+                // Always process pending changes first
+                return Poll::Ready(Some(next));
             }
+
+            let action = match changes.poll_next_unpin(ctxt) {
+                Poll::Ready(Some(action))   => action,
+                Poll::Ready(None)           => { return Poll::Ready(None); }
+                Poll::Pending               => { return Poll::Pending; }
+            };
+
+            rope_copy.edit(action.clone());
+
+            // Number the cells that replace the edited range (keeping the same attributes as the original action)
+            let (range, new_len) = match action {
+                SetAttributes(range, attributes) => {
+                    // The cells are unchanged, so only the attributes need to be updated
+                    return Poll::Ready(Some(SetAttributes(range, attributes)));
+                }
+
+                Replace(range, new_cells) => {
+                    let (start, new_len) = (range.start, new_cells.len());
+                    pending.push_back(Replace(range.clone(), new_cells.into_iter().enumerate().map(|(idx, cell)| (start+idx, cell)).collect()));
+
+                    (range, new_len)
+                }
+
+                ReplaceAttributes(range, new_cells, attributes) => {
+                    let (start, new_len) = (range.start, new_cells.len());
+                    pending.push_back(ReplaceAttributes(range.clone(), new_cells.into_iter().enumerate().map(|(idx, cell)| (start+idx, cell)).collect(), attributes));
+
+                    (range, new_len)
+                }
+            };
+
+            // If the length of the rope changed, the cells after the edit have moved, so are renumbered (with one action for each run of attributes)
+            if new_len != range.len() {
+                let moved_start = range.start+new_len;
+
+                for (range, cells, attributes) in number_runs(&rope_copy, moved_start..rope_copy.len()) {
+                    pending.push_back(ReplaceAttributes(range, cells, attributes));
+                }
+            }
+
+            Poll::Ready(pending.pop_front())
+        });
+
+        RopeBinding::from_stream(enumerated_stream)
+    }
+
+    fn map_cells_with_attribute<PredFn, MapFn>(&self, attribute_pred: PredFn, map_fn: MapFn) -> RopeBinding<Cell, Attribute>
+    where
+    PredFn: 'static+Send+Fn(&Attribute) -> bool,
+    MapFn:  'static+Send+Fn(Cell) -> Cell {
+        // The rope starts with its current contents, which are mapped a run of attributes at a time like any other edit
+        let mut changes = follow_changes_with_contents(self);
+
+        // We track the content of the rope so the original cells can be read when their attributes change
+        let mut rope    = AttributedRope::<Cell, Attribute>::new();
+
+        let mapped_stream = stream::poll_fn(move |ctxt| {
+            use RopeAction::*;
+
+            let action = match changes.poll_next_unpin(ctxt) {
+                Poll::Ready(Some(action))   => action,
+                Poll::Ready(None)           => { return Poll::Ready(None); }
+                Poll::Pending               => { return Poll::Pending; }
+            };
+
+            // Check if setting attributes changes whether or not any of the cells in the range are transformed (before the rope is edited)
+            let mapping_changed = if let SetAttributes(range, attributes) = &action {
+                let now_matches = attribute_pred(attributes);
+                let mut pos     = range.start;
+                let mut changed = false;
+
+                while pos < range.end && !changed {
+                    let (old_attributes, run) = rope.read_attributes(pos);
+
+                    changed = attribute_pred(old_attributes) != now_matches;
+                    pos     = run.end.max(pos+1);
+                }
+
+                changed
+            } else {
+                false
+            };
+
+            rope.edit(action.clone());
+
+            let mapped_action = match action {
+                Replace(range, new_cells) => {
+                    // The new cells take on the attributes of the cells they replaced
+                    let matches = !new_cells.is_empty() && attribute_pred(rope.read_attributes(range.start).0);
+
+                    if matches {
+                        Replace(range, new_cells.into_iter().map(&map_fn).collect())
+                    } else {
+                        Replace(range, new_cells)
+                    }
+                }
+
+                ReplaceAttributes(range, new_cells, attributes) => {
+                    if attribute_pred(&attributes) {
+                        ReplaceAttributes(range, new_cells.into_iter().map(&map_fn).collect(), attributes)
+                    } else {
+                        ReplaceAttributes(range, new_cells, attributes)
+                    }
+                }
+
+                SetAttributes(range, attributes) => {
+                    if !mapping_changed {
+                        SetAttributes(range, attributes)
+                    } else {
+                        // Regenerate the cells from the originals so they're transformed or restored to match their new attributes
+                        let original_cells = rope.read_cells(range.clone()).cloned();
+
+                        if attribute_pred(&attributes) {
+                            ReplaceAttributes(range, original_cells.map(&map_fn).collect(), attributes)
+                        } else {
+                            ReplaceAttributes(range, original_cells.collect(), attributes)
+                        }
+                    }
+                }
+            };
+
+            Poll::Ready(Some(mapped_action))
         });
 
         RopeBinding::from_stream(mapped_stream)
     }
+
+    fn split_at(&self, index: usize) -> (RopeBinding<Cell, Attribute>, RopeBinding<Cell, Attribute>) {
+        let before  = RopeBinding::from_stream(slice_changes(follow_changes_with_contents(self), 0, Some(index)));
+        let after   = RopeBinding::from_stream(slice_changes(follow_changes_with_contents(self), index, None));
+
+        (before, after)
+    }
+
+    fn fixed_length(&self, length: usize, filler: Cell) -> RopeBinding<Cell, Attribute> {
+        // Truncate the rope to the length, then pad it out
+        let mut truncated   = slice_changes(follow_changes_with_contents(self), 0, Some(length));
+        let mut prefix_len  = 0;
+        let mut pending     = VecDeque::new();
+
+        // The rope starts out entirely made up of padding
+        if length > 0 {
+            pending.push_back(RopeAction::ReplaceAttributes(0..0, vec![filler.clone(); length], Attribute::default()));
+        }
+
+        let padded_stream   = stream::poll_fn(move |ctxt| {
+            use RopeAction::*;
+
+            if let Some(next) = pending.pop_front() {
+                // Always process pending changes first
+                return Poll::Ready(Some(next));
+            }
+
+            let action = match truncated.poll_next_unpin(ctxt) {
+                Poll::Ready(Some(action))   => action,
+                Poll::Ready(None)           => { return Poll::Ready(None); }
+                Poll::Pending               => { return Poll::Pending; }
+            };
+
+            // Work out how the length of the truncated rope is changed by this action
+            let new_prefix_len = match &action {
+                Replace(range, cells) | ReplaceAttributes(range, cells, _)  => prefix_len - range.len() + cells.len(),
+                SetAttributes(_, _)                                         => prefix_len,
+            };
+
+            if new_prefix_len > prefix_len {
+                // Remove padding from the end before the rope gets longer, so it never contains more than `length` cells
+                pending.push_back(Replace((length - (new_prefix_len - prefix_len))..length, vec![]));
+                pending.push_back(action);
+            } else if new_prefix_len < prefix_len {
+                // Add padding to the end after the rope gets shorter
+                let end = length - (prefix_len - new_prefix_len);
+
+                pending.push_back(action);
+                pending.push_back(ReplaceAttributes(end..end, vec![filler.clone(); prefix_len - new_prefix_len], Attribute::default()));
+            } else {
+                pending.push_back(action);
+            }
+
+            prefix_len = new_prefix_len;
+
+            Poll::Ready(pending.pop_front())
+        });
+
+        RopeBinding::from_stream(padded_stream)
+    }
+
+    fn follow_snapshots(&self) -> RopeSnapshotStream<Cell, Attribute> {
+        // Start from the current contents of the rope, then follow any changes made after that
+        let mut cells   = vec![];
+        let changes     = self.read_and_follow_changes(&mut |rope| cells = rope.read_cells(0..rope.len()).cloned().collect());
+
+        RopeSnapshotStream {
+            changes,
+            cells,
+            changed:    true,
+            finished:   false,
+        }
+    }
+
+    fn follow_change_stats(&self) -> RopeChangeStatsStream<Cell, Attribute> {
+        RopeChangeStatsStream {
+            changes: self.follow_changes()
+        }
+    }
+
+    fn follow_changes_acked(&self) -> RopeAckedStream<Cell, Attribute> {
+        RopeAckedStream::new(self.follow_changes())
+    }
+
+    #[cfg(feature = "stream")]
+    fn fold_binding<Value, FoldFn, UnfoldFn>(&self, initial: Value, fold_fn: FoldFn, unfold_fn: UnfoldFn) -> BindRef<Value>
+    where
+    Value:      'static+Send+Clone+PartialEq,
+    FoldFn:     'static+Send+Fn(Value, &Cell) -> Value,
+    UnfoldFn:   'static+Send+Fn(Value, &Cell) -> Value {
+        // We need to know which cells are removed by each action, so we keep a copy of the cells in the rope (starting with its current contents)
+        let mut cells   = vec![];
+        let changes     = self.read_and_follow_changes(&mut |rope| cells = rope.read_cells(0..rope.len()).cloned().collect());
+        let initial     = cells.iter().fold(initial, &fold_fn);
+
+        let folded      = bind_stream(changes, initial, move |value, action| {
+            use RopeAction::*;
+
+            let value = match &action {
+                Replace(range, new_cells) | ReplaceAttributes(range, new_cells, _) => {
+                    // Remove the old cells from the value, then add the new ones
+                    let value = cells[range.clone()].iter().fold(value, &unfold_fn);
+                    new_cells.iter().fold(value, &fold_fn)
+                }
+
+                SetAttributes(_, _) => value
+            };
+
+            action.apply_to_vec(&mut cells);
+
+            value
+        });
+
+        BindRef::from(folded)
+    }
 } 
+
+///
+/// Reads the attribute runs from a rope
+///
+pub (super) fn attribute_runs<Cell, Attribute>(rope: &dyn Rope<Cell=Cell, Attribute=Attribute>) -> Vec<(Attribute, Range<usize>)>
+where 
+Cell:       Clone+PartialEq,
+Attribute:  Clone+PartialEq+Default {
+    let len         = rope.len();
+    let mut runs    = vec![];
+    let mut pos     = 0;
+
+    while pos < len {
+        let (attr, range) = rope.read_attributes(pos);
+        if range.is_empty() {
+            pos += 1;
+            continue;
+        }
+
+        pos = range.end;
+        runs.push((attr.clone(), range));
+    }
+
+    runs
+}
+
+///
+/// Returns the actions that insert the contents of a rope into an empty rope, one run of attributes at a time
+///
+pub (super) fn contents_actions<Cell, Attribute>(rope: &dyn Rope<Cell=Cell, Attribute=Attribute>) -> Vec<RopeAction<Cell, Attribute>>
+where
+Cell:       Clone+PartialEq,
+Attribute:  Clone+PartialEq+Default {
+    attribute_runs(rope).into_iter()
+        .map(|(attribute, range)| RopeAction::ReplaceAttributes(range.start..range.start, rope.read_cells(range).cloned().collect(), attribute))
+        .collect()
+}
+
+///
+/// Follows the changes to a rope, starting with actions that insert its current contents
+///
+/// Adapters that track a copy of the rope start with an empty copy, so this brings them up to date with a rope that already
+/// has some content before the changes made after that point arrive.
+///
+fn follow_changes_with_contents<Cell, Attribute, TRope>(rope: &TRope) -> impl 'static+Send+Unpin+Stream<Item=RopeAction<Cell, Attribute>>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default,
+TRope:      ?Sized+BoundRope<Cell, Attribute> {
+    let mut initial_actions = vec![];
+    let changes             = rope.read_and_follow_changes(&mut |rope| initial_actions = contents_actions(rope));
+
+    stream::iter(initial_actions).chain(changes)
+}
+
+///
+/// Creates a rope that concatenates the changes from two streams, with an optional separator between them
+///
+fn chain_ropes<Cell, Attribute>(follow_left: RopeStream<Cell, Attribute>, follow_right: RopeStream<Cell, Attribute>, separator: Option<Vec<Cell>>) -> RopeBinding<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    let mut follow_left     = Some(follow_left);
+    let mut follow_right    = Some(follow_right);
+
+    // Concatenator and pending values
+    let mut pending         = VecDeque::new();
+    let mut concatenator    = ChainConcatenator::new(separator);
+
+    // Create a new polling stream that concatenates the two sides
+    let concat_stream       = stream::poll_fn(move |ctxt| {
+        if let Some(next) = pending.pop_front() {
+            // Always process pending changes first
+            return Poll::Ready(Some(next));
+        }
+
+        // Process left-hand side changes, if there are any
+        let poll_left = follow_left.as_mut().map(|left| left.poll_next_unpin(ctxt));
+
+        match poll_left {
+            None                            => { }
+            Some(Poll::Pending)             => { }
+            Some(Poll::Ready(None))         => { follow_left = None; }
+            Some(Poll::Ready(Some(action))) => {
+                concatenator.send_left(action, &mut pending);
+                if let Some(next) = pending.pop_front() { return Poll::Ready(Some(next)); }
+            }
+        }
+
+        // Process right-hand side changes, if there are any
+        let poll_right = follow_right.as_mut().map(|right| right.poll_next_unpin(ctxt));
+
+        match poll_right {
+            None                            => { }
+            Some(Poll::Pending)             => { }
+            Some(Poll::Ready(None))         => { follow_right = None; }
+            Some(Poll::Ready(Some(action))) => {
+                concatenator.send_right(action, &mut pending);
+                if let Some(next) = pending.pop_front() { return Poll::Ready(Some(next)); }
+            }
+        }
+
+        // No actions: will be woken up once something happens to either of the two streams
+        Poll::Pending
+    });
+
+    // Result is a rope reading from this stream
+    RopeBinding::from_stream(concat_stream)
+}
+
+///
+/// Tracks the lengths of the two sides of a rope created by `chain()` or `chain_with_separator()`, so the changes to the
+/// right-hand side can be moved after the left-hand side, and the separator can be added and removed
+///
+struct ChainConcatenator<Cell> {
+    /// The cells that go between the two sides, if there's a separator
+    separator: Option<Vec<Cell>>,
+
+    /// The length of the left-hand side
+    left_len: usize,
+
+    /// The length of the right-hand side
+    right_len: usize,
+}
+
+impl<Cell: Clone> ChainConcatenator<Cell> {
+    ///
+    /// Creates a new concatenator where both sides are empty
+    ///
+    fn new(separator: Option<Vec<Cell>>) -> Self {
+        ChainConcatenator {
+            separator,
+            left_len:   0,
+            right_len:  0,
+        }
+    }
+
+    ///
+    /// True if the separator is currently in the rope
+    ///
+    fn has_separator(&self) -> bool {
+        self.separator.is_some() && self.left_len > 0 && self.right_len > 0
+    }
+
+    ///
+    /// The number of cells in the separator
+    ///
+    fn separator_len(&self) -> usize {
+        self.separator.as_ref().map(|separator| separator.len()).unwrap_or(0)
+    }
+
+    ///
+    /// Processes an action for the left-hand side of the rope, adding the actions for the concatenated rope to `pending`
+    ///
+    fn send_left<Attribute: Default>(&mut self, action: RopeAction<Cell, Attribute>, pending: &mut VecDeque<RopeAction<Cell, Attribute>>) {
+        let had_separator = self.has_separator();
+
+        self.left_len = Self::new_len(self.left_len, &action);
+        pending.push_back(action);
+
+        self.update_separator(had_separator, pending);
+    }
+
+    ///
+    /// Processes an action for the right-hand side of the rope, adding the actions for the concatenated rope to `pending`
+    ///
+    fn send_right<Attribute: Default>(&mut self, action: RopeAction<Cell, Attribute>, pending: &mut VecDeque<RopeAction<Cell, Attribute>>) {
+        use RopeAction::*;
+
+        let had_separator   = self.has_separator();
+        let offset          = self.left_len + if had_separator { self.separator_len() } else { 0 };
+        let offset_range    = |range: Range<usize>| (range.start+offset)..(range.end+offset);
+
+        self.right_len = Self::new_len(self.right_len, &action);
+        pending.push_back(match action {
+            Replace(range, cells)                       => Replace(offset_range(range), cells),
+            SetAttributes(range, attributes)            => SetAttributes(offset_range(range), attributes),
+            ReplaceAttributes(range, cells, attributes) => ReplaceAttributes(offset_range(range), cells, attributes),
+        });
+
+        self.update_separator(had_separator, pending);
+    }
+
+    ///
+    /// Returns the length of one side of the rope after an action has been applied to it
+    ///
+    fn new_len<Attribute>(len: usize, action: &RopeAction<Cell, Attribute>) -> usize {
+        use RopeAction::*;
+
+        match action {
+            Replace(range, cells) | ReplaceAttributes(range, cells, _)  => len - range.len() + cells.len(),
+            SetAttributes(_, _)                                         => len,
+        }
+    }
+
+    ///
+    /// Adds or removes the separator if one of the sides has become empty or stopped being empty
+    ///
+    fn update_separator<Attribute: Default>(&self, had_separator: bool, pending: &mut VecDeque<RopeAction<Cell, Attribute>>) {
+        // The separator always starts immediately after the left-hand side
+        let start = self.left_len;
+
+        match (had_separator, self.has_separator()) {
+            (false, true)   => pending.push_back(RopeAction::ReplaceAttributes(start..start, self.separator.clone().unwrap_or_default(), Attribute::default())),
+            (true, false)   => pending.push_back(RopeAction::Replace(start..(start+self.separator_len()), vec![])),
+            _               => { }
+        }
+    }
+}
+
+///
+/// Converts a stream of changes to a rope into a stream of changes to a fixed range of that rope (`None` as the end of the range indicates
+/// that the slice extends to the end of the rope)
+///
+fn slice_changes<Cell, Attribute, TStream>(changes: TStream, start: usize, end: Option<usize>) -> impl 'static+Send+Unpin+Stream<Item=RopeAction<Cell, Attribute>>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default,
+TStream:    'static+Send+Unpin+Stream<Item=RopeAction<Cell, Attribute>> {
+    let mut changes = changes;
+
+    // We track the full content of the rope so we can tell which cells move in or out of the slice
+    let mut cells   = vec![];
+    let mut pending = VecDeque::new();
+
+    stream::poll_fn(move |ctxt| {
+        use RopeAction::*;
+
+        loop {
+            if let Some(next) = pending.pop_front() {
+                // Always process pending changes first
+                return Poll::Ready(Some(next));
+            }
+
+            let action = match changes.poll_next_unpin(ctxt) {
+                Poll::Ready(Some(action))   => action,
+                Poll::Ready(None)           => { return Poll::Ready(None); }
+                Poll::Pending               => { return Poll::Pending; }
+            };
+
+            let old_len = cells.len();
+            action.apply_to_attributed_vec(&mut cells);
+            let new_len = cells.len();
+
+            match action {
+                SetAttributes(range, attributes) => {
+                    // Setting attributes doesn't move any cells, so we just need to clip the range to the slice
+                    let slice_start = range.start.max(start);
+                    let slice_end   = end.map(|end| range.end.min(end)).unwrap_or(range.end);
+
+                    if slice_start < slice_end {
+                        pending.push_back(SetAttributes((slice_start-start)..(slice_end-start), attributes));
+                    }
+                }
+
+                Replace(range, _) | ReplaceAttributes(range, _, _) => {
+                    // If the length of the rope changes, every cell after the start of the range moves
+                    let (old_changed_end, new_changed_end) = if old_len == new_len {
+                        (range.end, range.end)
+                    } else {
+                        (old_len, new_len)
+                    };
+
+                    // Clip the changed range to the slice
+                    let changed_start   = range.start.max(start);
+                    let old_slice_end   = end.map(|end| end.min(old_len)).unwrap_or(old_len);
+                    let new_slice_end   = end.map(|end| end.min(new_len)).unwrap_or(new_len);
+                    let old_end         = old_changed_end.min(old_slice_end).max(changed_start);
+                    let new_end         = new_changed_end.min(new_slice_end).max(changed_start);
+
+                    // Remove the old cells from the slice
+                    if changed_start < old_end {
+                        pending.push_back(Replace((changed_start-start)..(old_end-start), vec![]));
+                    }
+
+                    // Insert the new cells, one run of attributes at a time
+                    let mut pos = changed_start;
+                    while pos < new_end {
+                        let attribute   = &cells[pos].1;
+                        let run_end     = (pos..new_end).find(|idx| &cells[*idx].1 != attribute).unwrap_or(new_end);
+                        let run         = cells[pos..run_end].iter().map(|(cell, _)| cell.clone()).collect();
+                        let slice_pos   = pos - start;
+
+                        pending.push_back(ReplaceAttributes(slice_pos..slice_pos, run, attribute.clone()));
+                        pos = run_end;
+                    }
+                }
+            }
+        }
+    })
+}
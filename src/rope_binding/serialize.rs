@@ -0,0 +1,62 @@
+use crate::rope_binding::rope_binding::*;
+
+use futures::prelude::*;
+use flo_rope::*;
+use serde::{Serialize, Deserialize};
+
+use std::ops::{Range};
+
+///
+/// A copy of a `RopeAction` that can be serialized, so the changes to a rope can be sent to another process
+///
+/// The changes to a rope can be converted to this format using `rope.follow_changes().map(SerializedRopeAction::from)`,
+/// and a rope can be reconstructed from them using `RopeBinding::from_serialized_stream()`.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum SerializedRopeAction<Cell, Attribute> {
+    /// Replaces a range of cells with a new set of cells, using the attributes of the first cell in the range
+    Replace(Range<usize>, Vec<Cell>),
+
+    /// Sets the attributes for a range of cells
+    SetAttributes(Range<usize>, Attribute),
+
+    /// Replaces a range of cells and sets the attributes for the new cells
+    ReplaceAttributes(Range<usize>, Vec<Cell>, Attribute),
+}
+
+impl<Cell, Attribute> From<RopeAction<Cell, Attribute>> for SerializedRopeAction<Cell, Attribute> {
+    fn from(action: RopeAction<Cell, Attribute>) -> Self {
+        match action {
+            RopeAction::Replace(range, cells)                       => SerializedRopeAction::Replace(range, cells),
+            RopeAction::SetAttributes(range, attributes)            => SerializedRopeAction::SetAttributes(range, attributes),
+            RopeAction::ReplaceAttributes(range, cells, attributes) => SerializedRopeAction::ReplaceAttributes(range, cells, attributes),
+        }
+    }
+}
+
+impl<Cell, Attribute> From<SerializedRopeAction<Cell, Attribute>> for RopeAction<Cell, Attribute> {
+    fn from(action: SerializedRopeAction<Cell, Attribute>) -> Self {
+        match action {
+            SerializedRopeAction::Replace(range, cells)                         => RopeAction::Replace(range, cells),
+            SerializedRopeAction::SetAttributes(range, attributes)              => RopeAction::SetAttributes(range, attributes),
+            SerializedRopeAction::ReplaceAttributes(range, cells, attributes)   => RopeAction::ReplaceAttributes(range, cells, attributes),
+        }
+    }
+}
+
+impl<Cell, Attribute> RopeBinding<Cell, Attribute>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Creates a new rope binding from a stream of serialized changes
+    ///
+    /// This is the inverse of following the changes to a rope and converting them to `SerializedRopeAction`s, so a rope can
+    /// be copied to another process by sending the actions over a transport and decoding them at the other end.
+    ///
+    pub fn from_serialized_stream<S>(stream: S) -> Self
+    where
+    S: 'static+Stream<Item=SerializedRopeAction<Cell, Attribute>>+Unpin+Send {
+        Self::from_stream(stream.map(RopeAction::from))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::rope_binding::core::*;
+use crate::rope_binding::rope_action_ext::*;
 
 use flo_rope::*;
 use ::desync::*;
@@ -6,7 +7,6 @@ use futures::task::*;
 use futures::prelude::*;
 use futures::future::{BoxFuture};
 
-use std::mem;
 use std::pin::*;
 use std::sync::*;
 use std::collections::{VecDeque};
@@ -45,7 +45,7 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
     fn poll_next(mut self: Pin<&mut Self>, ctxt: &mut Context<'_>) -> Poll<Option<RopeAction<Cell, Attribute>>> { 
         // If we've got a set of actions we're already reading, then return those as fast as we can
         if self.draining.len() > 0 {
-            return Poll::Ready(self.draining.pop_back());
+            return Poll::Ready(self.draining.pop_front());
         }
 
         // If we're waiting for the core to return to us, borrow the future from there
@@ -62,18 +62,17 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
                     // Pull any pending changes from the rope
                     core.pull_rope();
 
-                    // Find the state of this stream
+                    // Find the state of this stream (the stream has finished if its state has been moved to a resumed stream)
                     let stream_state = core.stream_states.iter_mut()
                         .filter(|state| state.identifier == stream_id)
-                        .nth(0)
-                        .unwrap();
+                        .nth(0);
+                    let stream_state = if let Some(stream_state) = stream_state { stream_state } else { return Poll::Ready(None); };
 
                     // Check for data
-                    if stream_state.pending_changes.len() > 0 {
-                        // Return the changes to the waiting stream
-                        let mut changes = VecDeque::new();
-                        mem::swap(&mut changes, &mut stream_state.pending_changes);
+                    let changes = stream_state.take_changes(&core.rope);
 
+                    if !changes.is_empty() {
+                        // Return the changes to the waiting stream
                         Poll::Ready(Some(changes))
                     } else if core.usage_count == 0 {
                         // No changes, and nothing is using the core any more
@@ -113,7 +112,7 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
                 } else {
                     // Have some actions ready
                     self.draining = actions;
-                    Poll::Ready(self.draining.pop_back())
+                    Poll::Ready(self.draining.pop_front())
                 }
             }
 
@@ -138,7 +137,8 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         let dropped_stream_id   = self.identifier;
         let retains_core        = self.retains_core;
         self.core.desync(move |core| {
-            core.stream_states.retain(|state| state.identifier != dropped_stream_id);
+            // Acknowledged streams keep their state so they can be resumed: it's removed when the last acknowledger is dropped
+            core.stream_states.retain(|state| state.identifier != dropped_stream_id || state.acks.is_some());
 
             if retains_core {
                 // Core is no longer in use
@@ -152,3 +152,113 @@ Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
         });
     }
 }
+
+///
+/// A rope snapshot stream returns the full contents of a rope binding every time it changes
+///
+/// Any changes that are made to the rope between polls of the stream are combined into a single snapshot.
+///
+pub struct RopeSnapshotStream<Cell, Attribute> 
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The stream of changes to the rope
+    pub (super) changes: RopeStream<Cell, Attribute>,
+
+    /// The contents of the rope after the changes that have been read so far
+    pub (super) cells: Vec<Cell>,
+
+    /// True if the cells have changed since the last snapshot was returned
+    pub (super) changed: bool,
+
+    /// True if the stream of changes has finished
+    pub (super) finished: bool,
+}
+
+impl<Cell, Attribute> Stream for RopeSnapshotStream<Cell, Attribute>
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    type Item = Vec<Cell>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctxt: &mut Context<'_>) -> Poll<Option<Vec<Cell>>> { 
+        // Apply every change that's ready to the cells
+        while !self.finished {
+            match self.changes.poll_next_unpin(ctxt) {
+                Poll::Ready(Some(action))   => {
+                    let this = &mut *self;
+                    action.apply_to_vec(&mut this.cells);
+                    this.changed = true;
+                }
+                Poll::Ready(None)           => { self.finished = true; }
+                Poll::Pending               => { break; }
+            }
+        }
+
+        if self.changed {
+            // Return a snapshot containing all of the changes so far
+            self.changed = false;
+            Poll::Ready(Some(self.cells.clone()))
+        } else if self.finished {
+            Poll::Ready(None)
+        } else {
+            // Wait for the next change
+            Poll::Pending
+        }
+    }
+}
+
+///
+/// A stream that returns the number of cells inserted and deleted by each change to a rope binding
+///
+pub struct RopeChangeStatsStream<Cell, Attribute> 
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The stream of changes to the rope
+    pub (super) changes: RopeStream<Cell, Attribute>,
+}
+
+impl<Cell, Attribute> Stream for RopeChangeStatsStream<Cell, Attribute>
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    type Item = RopeChangeStats;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctxt: &mut Context<'_>) -> Poll<Option<RopeChangeStats>> { 
+        self.changes.poll_next_unpin(ctxt).map(|action| action.map(|action| action.change_stats()))
+    }
+}
+
+///
+/// A stream that returns the actions made by each edit to a rope binding as a group
+///
+pub struct RopeGroupedStream<Cell, Attribute> 
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    /// The stream of changes to the rope (the core returns the changes for one edit each time this stream asks for more changes)
+    pub (super) changes: RopeStream<Cell, Attribute>,
+}
+
+impl<Cell, Attribute> Stream for RopeGroupedStream<Cell, Attribute>
+where 
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    type Item = Vec<RopeAction<Cell, Attribute>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctxt: &mut Context<'_>) -> Poll<Option<Vec<RopeAction<Cell, Attribute>>>> { 
+        match self.changes.poll_next_unpin(ctxt) {
+            Poll::Ready(Some(action))   => {
+                // The rest of the edit is waiting to be drained from the stream
+                let mut edit = vec![action];
+                edit.extend(self.changes.draining.drain(..));
+
+                Poll::Ready(Some(edit))
+            }
+
+            Poll::Ready(None)           => Poll::Ready(None),
+            Poll::Pending               => Poll::Pending,
+        }
+    }
+}
@@ -1,8 +1,24 @@
 use flo_rope::*;
 use futures::task::*;
 
+use std::mem;
 use std::collections::{VecDeque};
 
+///
+/// A range of a rope that has changed since a stream last read from it
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub (super) struct ChangedRange {
+    /// The start of the changed range (this is the same before and after the changes)
+    pub (super) start: usize,
+
+    /// The end of the changed range in the rope as it was when the stream last read from it
+    pub (super) old_end: usize,
+
+    /// The end of the changed range in the rope as it is now
+    pub (super) new_end: usize,
+}
+
 ///
 /// The state of a stream that is reading from a rope binding core
 ///
@@ -20,5 +36,169 @@ Attribute:  Clone+PartialEq+Default {
     pub (super) pending_changes: VecDeque<RopeAction<Cell, Attribute>>,
 
     /// True if the rope has indicated there are changes waiting to be pulled
-    pub (super) needs_pull: bool
+    pub (super) needs_pull: bool,
+
+    /// True if this stream generates its changes from the rope when it's polled rather than storing them as they're pulled
+    pub (super) pull_mode: bool,
+
+    /// For streams in pull mode, the range of the rope that has changed since the stream last read from it
+    pub (super) changed_range: Option<ChangedRange>,
+
+    /// For streams that return their changes grouped by edit, the number of pending changes made by each edit
+    pub (super) group_sizes: Option<VecDeque<usize>>,
+
+    /// For streams whose consumer acknowledges the changes, the changes that have not been acknowledged yet
+    pub (super) acks: Option<StreamAcks<Cell, Attribute>>,
+}
+
+///
+/// The changes that have been taken by a stream that acknowledges its changes
+///
+/// Changes are kept here after they've been sent until they're acknowledged, so that a new stream can send them again if
+/// the consumer of the stream fails before it has applied them.
+///
+pub (super) struct StreamAcks<Cell, Attribute>
+where
+Cell:       Clone+PartialEq,
+Attribute:  Clone+PartialEq+Default {
+    /// The version number of the first change in `unacknowledged`
+    pub (super) first_version: u64,
+
+    /// The changes that have been taken from the pending changes but not acknowledged yet
+    pub (super) unacknowledged: VecDeque<RopeAction<Cell, Attribute>>,
+
+    /// The number of changes in `unacknowledged` that have been sent to the current stream
+    pub (super) sent: usize,
+}
+
+impl<Cell, Attribute> StreamAcks<Cell, Attribute>
+where
+Cell:       Clone+PartialEq,
+Attribute:  Clone+PartialEq+Default {
+    ///
+    /// Creates the acknowledgement state for a stream that has not sent any changes yet
+    ///
+    pub (super) fn new() -> StreamAcks<Cell, Attribute> {
+        StreamAcks {
+            first_version:  0,
+            unacknowledged: VecDeque::new(),
+            sent:           0,
+        }
+    }
+
+    ///
+    /// Discards the changes up to and including the specified version (changes that haven't been sent can't be acknowledged)
+    ///
+    pub (super) fn ack(&mut self, version: u64) {
+        while self.sent > 0 && self.first_version <= version {
+            self.unacknowledged.pop_front();
+            self.first_version  += 1;
+            self.sent           -= 1;
+        }
+    }
+}
+
+impl<Cell, Attribute> RopeStreamState<Cell, Attribute>
+where
+Cell:       Clone+PartialEq,
+Attribute:  Clone+PartialEq+Default {
+    ///
+    /// Adds a set of actions that have been pulled from the rope to the changes waiting for this stream
+    ///
+    pub (super) fn add_changes(&mut self, actions: &[RopeAction<Cell, Attribute>]) {
+        if self.pull_mode {
+            // Pull mode streams just track which part of the rope has changed
+            for action in actions {
+                self.add_changed_range(action);
+            }
+        } else {
+            self.pending_changes.extend(actions.iter().cloned());
+
+            if let Some(group_sizes) = &mut self.group_sizes {
+                group_sizes.push_back(actions.len());
+            }
+        }
+    }
+
+    ///
+    /// Extends the changed range to cover an action
+    ///
+    fn add_changed_range(&mut self, action: &RopeAction<Cell, Attribute>) {
+        use RopeAction::*;
+
+        // Work out the range replaced by the action and the number of cells that replace it
+        let (range, new_len) = match action {
+            Replace(range, cells) | ReplaceAttributes(range, cells, _)  => (range.clone(), cells.len()),
+            SetAttributes(range, _)                                     => (range.clone(), range.len()),
+        };
+
+        self.changed_range = Some(match self.changed_range {
+            None            => ChangedRange { start: range.start, old_end: range.end, new_end: range.start + new_len },
+            Some(changed)   => {
+                // Combine with the existing range, in the coordinates of the rope before this action
+                let start   = changed.start.min(range.start);
+                let end     = changed.new_end.max(range.end);
+
+                // Any cells after the existing range are at the same offset from its end in the rope the stream last read
+                let old_end = changed.old_end + (end - changed.new_end);
+
+                ChangedRange { start, old_end, new_end: end - range.len() + new_len }
+            }
+        });
+    }
+
+    ///
+    /// Takes the changes that are waiting to be sent to this stream (reading the changed cells from the rope for pull mode streams)
+    ///
+    pub (super) fn take_changes<TRope: Rope<Cell=Cell, Attribute=Attribute>>(&mut self, rope: &TRope) -> VecDeque<RopeAction<Cell, Attribute>> {
+        use RopeAction::*;
+
+        if let Some(acks) = &mut self.acks {
+            // Acknowledged streams take one change at a time, so every change that's been taken has been returned by the stream
+            if acks.sent >= acks.unacknowledged.len() {
+                if let Some(action) = self.pending_changes.pop_front() {
+                    acks.unacknowledged.push_back(action);
+                } else {
+                    return VecDeque::new();
+                }
+            }
+
+            let action  = acks.unacknowledged[acks.sent].clone();
+            acks.sent   += 1;
+
+            return VecDeque::from(vec![action]);
+        }
+
+        if !self.pull_mode {
+            if let Some(group_sizes) = &mut self.group_sizes {
+                // Only return the changes for the next edit
+                let group_size = group_sizes.pop_front().unwrap_or(0);
+                return self.pending_changes.drain(0..group_size).collect();
+            }
+
+            return mem::take(&mut self.pending_changes);
+        }
+
+        let mut changes = VecDeque::new();
+
+        if let Some(changed) = self.changed_range.take() {
+            // Remove the cells that were in the changed range when the stream last read from the rope
+            if changed.start < changed.old_end {
+                changes.push_back(Replace(changed.start..changed.old_end, vec![]));
+            }
+
+            // Insert the cells that are in the range now, one run of attributes at a time
+            let mut pos = changed.start;
+            while pos < changed.new_end {
+                let (attribute, range)  = rope.read_attributes(pos);
+                let run_end             = range.end.min(changed.new_end).max(pos+1);
+                let cells               = rope.read_cells(pos..run_end).cloned().collect();
+
+                changes.push_back(ReplaceAttributes(pos..pos, cells, attribute.clone()));
+                pos = run_end;
+            }
+        }
+
+        changes
+    }
 }
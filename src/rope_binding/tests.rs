@@ -1,4 +1,6 @@
 use crate::*;
+use crate::rope_binding::interleave::*;
+use crate::timer::*;
 
 use flo_rope::*;
 
@@ -6,6 +8,7 @@ use futures::executor;
 use futures::prelude::*;
 
 use std::sync::*;
+use std::time::{Duration};
 
 #[test]
 fn mutable_rope_sends_changes_to_stream() {
@@ -132,6 +135,7 @@ fn computed_rope() {
     assert!(rope.read_cells(0..10).collect::<Vec<_>>() == vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
 }
 
+#[cfg(feature = "diff")]
 #[test]
 fn computed_rope_using_diffs_1() {
     // Create a length binding and compute a rope from it
@@ -164,6 +168,7 @@ fn computed_rope_using_diffs_1() {
     assert!(rope.read_cells(0..10).collect::<Vec<_>>() == vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
 }
 
+#[cfg(feature = "diff")]
 #[test]
 fn computed_rope_using_diffs_2() {
     // Create a length binding and compute a rope from it
@@ -192,6 +197,7 @@ fn computed_rope_using_diffs_2() {
     assert!(diff == Some(RopeAction::Replace(2..2, vec![2, 3, 4, 5, 6, 7, 8, 9])));
 }
 
+#[cfg(feature = "diff")]
 #[test]
 fn computed_rope_using_diffs_3() {
     // Bind a list of items
@@ -234,6 +240,7 @@ fn computed_rope_using_diffs_3() {
     assert!(rope.read_cells(0..val.len()).collect::<Vec<_>>() == val);
 }
 
+#[cfg(feature = "diff")]
 #[test]
 fn bind_rope_length_to_computed() {
     // Create a rope
@@ -297,6 +304,7 @@ fn bind_rope_mut_length_to_computed() {
     assert!(*is_changed.lock().unwrap() == true);
 }
 
+#[cfg(feature = "diff")]
 #[test]
 fn bind_rope_read_cells_to_computed() {
     // Create a rope
@@ -390,3 +398,1477 @@ fn following_rope_generates_when_changed() {
     assert!(*is_changed.lock().unwrap() == true);
     assert!(rope_cells.get() == vec![1,1]);
 }
+
+#[cfg(feature = "stream")]
+#[test]
+fn rope_equals_snapshot() {
+    // Create a rope and take a snapshot of its initial contents
+    let rope                = RopeBindingMut::<usize, ()>::new();
+    let following_rope      = RopeBinding::from_mutable(&rope);
+    let mut follow_rope     = following_rope.follow_changes();
+
+    rope.replace(0..0, vec![1, 2, 3]);
+    executor::block_on(async { follow_rope.next().await });
+
+    let snapshot            = following_rope.get();
+    let is_equal            = following_rope.equals_binding(snapshot);
+    let mut follow_equal    = follow(is_equal);
+
+    executor::block_on(async {
+        assert!(follow_equal.next().await == Some(true));
+
+        // Editing the rope makes it different from the snapshot
+        rope.replace(3..3, vec![4]);
+        assert!(follow_equal.next().await == Some(false));
+
+        // Reverting the edit makes it the same again
+        rope.replace(3..4, vec![]);
+        assert!(follow_equal.next().await == Some(true));
+    });
+}
+
+#[test]
+fn edit_mutable_rope_directly() {
+    let mutable_rope        = RopeBindingMut::<usize, ()>::new();
+    let mut rope_stream     = mutable_rope.follow_changes();
+
+    // Edit the rope using the flo_rope API
+    let len = mutable_rope.with_rope(|rope| {
+        rope.replace(0..0, vec![1, 2, 3, 4]);
+        rope.len()
+    });
+    assert!(len == 4);
+
+    // Changes are still sent to the stream
+    executor::block_on(async move {
+        let next = rope_stream.next().await;
+
+        assert!(next == Some(RopeAction::Replace(0..0, vec![1,2,3,4])));
+    });
+
+    assert!(mutable_rope.read_cells(0..4).collect::<Vec<_>>() == vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn apply_actions_to_vec() {
+    let actions = vec![
+        RopeAction::Replace(0..0, vec![1, 2, 3, 4]),
+        RopeAction::SetAttributes(1..3, 1),
+        RopeAction::Replace(1..2, vec![5, 6]),
+        RopeAction::ReplaceAttributes(4..4, vec![7], 2),
+        RopeAction::Replace(0..1, vec![]),
+    ];
+
+    // Apply the actions to a rope and to the vectors
+    let mut rope            = AttributedRope::<usize, usize>::new();
+    let mut cells           = vec![];
+    let mut attributed      = vec![];
+
+    for action in actions {
+        action.apply_to_vec(&mut cells);
+        action.apply_to_attributed_vec(&mut attributed);
+        rope.edit(action);
+    }
+
+    // Vectors should match the rope
+    let rope_cells          = rope.read_cells(0..rope.len()).cloned().collect::<Vec<_>>();
+    let rope_attributed     = (0..rope.len()).map(|idx| (rope_cells[idx], *rope.read_attributes(idx).0)).collect::<Vec<_>>();
+
+    assert!(cells == vec![5, 6, 3, 7, 4]);
+    assert!(cells == rope_cells);
+    assert!(attributed == rope_attributed);
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn fold_rope_into_sum() {
+    let rope            = RopeBindingMut::<usize, ()>::new();
+    let sum             = rope.fold_binding(0, |sum, cell| sum + cell, |sum, cell| sum - cell);
+    let mut follow_sum  = follow(sum);
+
+    executor::block_on(async {
+        assert!(follow_sum.next().await == Some(0));
+
+        rope.replace(0..0, vec![1, 2, 3, 4]);
+        assert!(follow_sum.next().await == Some(10));
+
+        rope.replace(1..3, vec![10]);
+        assert!(follow_sum.next().await == Some(15));
+
+        rope.replace(0..1, vec![]);
+        assert!(follow_sum.next().await == Some(14));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn fold_binding_starts_from_existing_contents() {
+    let rope            = RopeBindingMut::<usize, ()>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+
+    let sum             = rope.fold_binding(0, |sum, cell| sum + cell, |sum, cell| sum - cell);
+    assert!(sum.get() == 10);
+
+    let mut follow_sum  = follow(sum);
+
+    executor::block_on(async {
+        assert!(follow_sum.next().await == Some(10));
+
+        rope.replace(1..3, vec![10]);
+        assert!(follow_sum.next().await == Some(15));
+    });
+}
+
+///
+/// Waits for a rope to reach a state where a condition is true (edits to ropes that follow streams arrive asynchronously)
+///
+fn wait_for_rope<Cell: 'static+Send+Unpin+Clone+PartialEq, Attribute: 'static+Send+Sync+Clone+Unpin+PartialEq+Default>(rope: &RopeBinding<Cell, Attribute>, condition: impl Fn(&RopeBinding<Cell, Attribute>) -> bool) {
+    // The rope is updated before the changes are sent to any stream that's following it
+    let mut changes = rope.follow_changes();
+
+    executor::block_on(async {
+        while !condition(rope) {
+            changes.next().await;
+        }
+    });
+}
+
+///
+/// Waits for the cells in a rope to match an expected value
+///
+fn wait_for_cells<Cell: 'static+Send+Unpin+Clone+PartialEq+std::fmt::Debug, Attribute: 'static+Send+Sync+Clone+Unpin+PartialEq+Default>(rope: &RopeBinding<Cell, Attribute>, expected: Vec<Cell>) {
+    wait_for_rope(rope, |rope| rope.read_cells(0..rope.len()).collect::<Vec<_>>() == expected);
+}
+
+#[test]
+fn split_rope() {
+    let rope            = RopeBindingMut::<usize, usize>::new();
+    let (before, after) = rope.split_at(3);
+
+    rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+    wait_for_cells(&before, vec![1, 2, 3]);
+    wait_for_cells(&after, vec![4, 5, 6]);
+
+    // Inserting before the split point moves cells into the second rope
+    rope.replace(1..1, vec![7]);
+    wait_for_cells(&before, vec![1, 7, 2]);
+    wait_for_cells(&after, vec![3, 4, 5, 6]);
+
+    // Edits after the split point only affect the second rope
+    rope.replace(5..6, vec![8, 9]);
+    wait_for_cells(&before, vec![1, 7, 2]);
+    wait_for_cells(&after, vec![3, 4, 8, 9, 6]);
+
+    // Edits that span the split point are divided between the two ropes
+    rope.replace(2..5, vec![10]);
+    wait_for_cells(&before, vec![1, 7, 10]);
+    wait_for_cells(&after, vec![8, 9, 6]);
+}
+
+#[test]
+fn split_rope_with_existing_contents() {
+    let rope            = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+    rope.set_attributes(2..4, 1);
+
+    let (before, after) = rope.split_at(3);
+    wait_for_cells(&before, vec![1, 2, 3]);
+    wait_for_cells(&after, vec![4, 5, 6]);
+    assert!(before.read_attributes(2) == (1, 2..3));
+    assert!(after.read_attributes(0) == (1, 0..1));
+
+    rope.replace(1..1, vec![7]);
+    wait_for_cells(&before, vec![1, 7, 2]);
+    wait_for_cells(&after, vec![3, 4, 5, 6]);
+}
+
+#[test]
+fn split_rope_attributes() {
+    let rope            = RopeBindingMut::<usize, usize>::new();
+    let (before, after) = rope.split_at(2);
+
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    rope.set_attributes(1..3, 1);
+    wait_for_cells(&before, vec![1, 2]);
+    wait_for_cells(&after, vec![3, 4]);
+
+    // Attributes are split between the two ropes
+    wait_for_rope(&before, |before| before.read_attributes(1).0 == 1);
+    wait_for_rope(&after, |after| after.read_attributes(0).0 == 1);
+
+    assert!(before.read_attributes(0) == (0, 0..1));
+    assert!(before.read_attributes(1) == (1, 1..2));
+    assert!(after.read_attributes(0) == (1, 0..1));
+    assert!(after.read_attributes(1) == (0, 1..2));
+
+    // Cells moved between ropes keep their attributes
+    rope.replace(0..1, vec![]);
+    wait_for_cells(&before, vec![2, 3]);
+    wait_for_cells(&after, vec![4]);
+
+    assert!(before.read_attributes(0) == (1, 0..2));
+    assert!(after.read_attributes(0) == (0, 0..1));
+}
+
+#[test]
+fn stream_returns_batched_actions_in_order() {
+    let rope            = RopeBindingMut::<usize, ()>::new();
+    let mut stream      = rope.follow_changes();
+
+    rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+
+    executor::block_on(async {
+        assert!(stream.next().await == Some(RopeAction::Replace(0..0, vec![1, 2, 3, 4, 5, 6])));
+
+        // Make several edits that will be pulled from the rope at the same time: these should be returned in the order they were made
+        rope.with_rope(|rope| {
+            rope.replace(4..5, vec![7, 8]);
+            rope.replace(1..2, vec![]);
+        });
+
+        assert!(stream.next().await == Some(RopeAction::Replace(4..5, vec![7, 8])));
+        assert!(stream.next().await == Some(RopeAction::Replace(1..2, vec![])));
+    });
+}
+
+#[test]
+fn batched_changes_are_applied_in_order() {
+    let mutable_rope    = RopeBindingMut::<usize, ()>::new();
+    let rope_copy       = RopeBinding::from_mutable(&mutable_rope);
+
+    mutable_rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+    wait_for_cells(&rope_copy, vec![1, 2, 3, 4, 5, 6]);
+
+    // Make several edits that will be pulled from the rope at the same time
+    mutable_rope.with_rope(|rope| {
+        rope.replace(4..5, vec![7, 8]);
+        rope.replace(1..2, vec![]);
+    });
+
+    wait_for_cells(&rope_copy, vec![1, 3, 4, 7, 8, 6]);
+}
+
+#[test]
+fn computed_rope_sections() {
+    let header          = bind(vec![1, 2]);
+    let body            = bind(vec![3, 4, 5]);
+    let footer          = bind(vec![6]);
+
+    let header_copy     = header.clone();
+    let body_copy       = body.clone();
+    let footer_copy     = footer.clone();
+    let sections: Vec<Box<dyn Fn() -> Vec<usize>+Send>> = vec![
+        Box::new(move || header_copy.get()),
+        Box::new(move || body_copy.get()),
+        Box::new(move || footer_copy.get()),
+    ];
+    let rope            = RopeBinding::<_, ()>::computed_sections(sections);
+    let mut follow_rope = rope.follow_changes();
+
+    wait_for_cells(&rope, vec![1, 2, 3, 4, 5, 6]);
+
+    // Only the section that changed should be replaced
+    executor::block_on(async { while follow_rope.next().now_or_never().is_some() { } });
+    body.set(vec![7]);
+
+    let change = executor::block_on(async { follow_rope.next().await });
+    assert!(change == Some(RopeAction::Replace(2..5, vec![7])));
+    wait_for_cells(&rope, vec![1, 2, 7, 6]);
+
+    header.set(vec![]);
+    wait_for_cells(&rope, vec![7, 6]);
+
+    footer.set(vec![8, 9]);
+    wait_for_cells(&rope, vec![7, 8, 9]);
+}
+
+#[test]
+fn follow_rope_snapshots() {
+    let rope            = RopeBindingMut::<usize, ()>::new();
+    rope.replace(0..0, vec![1, 2, 3]);
+
+    let mut snapshots   = rope.follow_snapshots();
+    assert!(executor::block_on(snapshots.next()) == Some(vec![1, 2, 3]));
+
+    // Edits made between reads are combined into a single snapshot
+    rope.replace(1..2, vec![4, 5]);
+    rope.replace(0..0, vec![6]);
+    assert!(executor::block_on(snapshots.next()) == Some(vec![6, 1, 4, 5, 3]));
+
+    rope.replace(0..5, vec![]);
+    assert!(executor::block_on(snapshots.next()) == Some(vec![]));
+}
+
+#[test]
+fn set_attributes_for_matching_cells() {
+    let rope = RopeBindingMut::<usize, usize>::new();
+
+    rope.replace(0..0, vec![1, 2, 4, 5, 6, 7, 8]);
+    rope.set_attributes_matching(|cell| cell % 2 == 0, 1);
+
+    assert!(rope.read_attributes(0) == (0, 0..1));
+    assert!(rope.read_attributes(1) == (1, 1..3));
+    assert!(rope.read_attributes(3) == (0, 3..4));
+    assert!(rope.read_attributes(4) == (1, 4..5));
+    assert!(rope.read_attributes(5) == (0, 5..6));
+    assert!(rope.read_attributes(6) == (1, 6..7));
+}
+
+#[test]
+fn pull_stream_combines_changes() {
+    let rope            = RopeBindingMut::<usize, usize>::new();
+    let mut pull_stream = rope.follow_changes_pull();
+
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    rope.replace(1..2, vec![5, 6]);
+    rope.replace(0..0, vec![7]);
+
+    // All of the changes are read from the rope at once
+    executor::block_on(async {
+        assert!(pull_stream.next().await == Some(RopeAction::ReplaceAttributes(0..0, vec![7, 1, 5, 6, 3, 4], 0)));
+    });
+
+    // Changes are returned as the cells to remove followed by the cells to insert
+    rope.set_attributes(2..4, 1);
+    rope.replace(5..6, vec![]);
+
+    executor::block_on(async {
+        assert!(pull_stream.next().await == Some(RopeAction::Replace(2..6, vec![])));
+        assert!(pull_stream.next().await == Some(RopeAction::ReplaceAttributes(2..2, vec![5, 6], 1)));
+        assert!(pull_stream.next().await == Some(RopeAction::ReplaceAttributes(4..4, vec![3], 0)));
+    });
+}
+
+#[test]
+fn mirror_rope_from_pull_stream() {
+    let rope    = RopeBindingMut::<usize, usize>::new();
+    let mirror  = RopeBinding::from_stream(rope.follow_changes_pull());
+
+    rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+    wait_for_cells(&mirror, vec![1, 2, 3, 4, 5, 6]);
+
+    rope.replace(4..5, vec![7, 8]);
+    rope.replace(1..2, vec![]);
+    rope.set_attributes(0..2, 2);
+    wait_for_cells(&mirror, vec![1, 3, 4, 7, 8, 6]);
+    assert!(mirror.read_attributes(0) == (2, 0..2));
+}
+
+#[test]
+fn fixed_length_rope() {
+    let rope    = RopeBindingMut::<usize, usize>::new();
+    let fixed   = rope.fixed_length(4, 0);
+
+    wait_for_cells(&fixed, vec![0, 0, 0, 0]);
+
+    rope.replace(0..0, vec![1, 2]);
+    wait_for_cells(&fixed, vec![1, 2, 0, 0]);
+
+    // Edit that crosses the truncation boundary
+    rope.replace(2..2, vec![3, 4, 5]);
+    wait_for_cells(&fixed, vec![1, 2, 3, 4]);
+
+    rope.replace(0..1, vec![]);
+    wait_for_cells(&fixed, vec![2, 3, 4, 5]);
+
+    // Back to needing padding
+    rope.replace(0..3, vec![]);
+    wait_for_cells(&fixed, vec![5, 0, 0, 0]);
+
+    rope.replace(1..1, vec![6]);
+    wait_for_cells(&fixed, vec![5, 6, 0, 0]);
+
+    // Padding always has the default attribute
+    rope.set_attributes(0..2, 1);
+    wait_for_rope(&fixed, |fixed| fixed.read_attributes(0) == (1, 0..2));
+
+    assert!(fixed.read_attributes(0) == (1, 0..2));
+    assert!(fixed.read_attributes(2) == (0, 2..4));
+}
+
+#[test]
+fn fixed_length_rope_with_existing_contents() {
+    let rope    = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+
+    let fixed   = rope.fixed_length(4, 0);
+    wait_for_cells(&fixed, vec![1, 2, 3, 4]);
+
+    rope.replace(0..4, vec![]);
+    wait_for_cells(&fixed, vec![5, 6, 0, 0]);
+}
+
+#[test]
+fn follow_rope_change_stats() {
+    let rope        = RopeBindingMut::<usize, usize>::new();
+    let mut stats   = rope.follow_change_stats();
+
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    executor::block_on(async {
+        assert!(stats.next().await == Some(RopeChangeStats { inserted: 4, deleted: 0, range: 0..0 }));
+    });
+
+    rope.replace(1..3, vec![5]);
+    executor::block_on(async {
+        assert!(stats.next().await == Some(RopeChangeStats { inserted: 1, deleted: 2, range: 1..3 }));
+    });
+
+    // Setting attributes doesn't insert or delete anything
+    assert!(RopeAction::<usize, usize>::SetAttributes(0..2, 1).change_stats() == RopeChangeStats { inserted: 0, deleted: 0, range: 0..2 });
+}
+
+#[test]
+fn insert_rope_with_attributes() {
+    let clipboard = RopeBindingMut::<usize, usize>::new();
+    clipboard.replace(0..0, vec![5, 6, 7]);
+    clipboard.set_attributes(1..3, 2);
+
+    let rope    = RopeBindingMut::<usize, usize>::new();
+    let mirror  = RopeBinding::from_stream(rope.follow_changes());
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+
+    rope.insert_rope(2, &clipboard);
+
+    assert!(rope.read_cells(0..7).collect::<Vec<_>>() == vec![1, 2, 5, 6, 7, 3, 4]);
+    assert!(rope.read_attributes(0) == (0, 0..3));
+    assert!(rope.read_attributes(3) == (2, 3..5));
+    assert!(rope.read_attributes(5) == (0, 5..7));
+
+    wait_for_cells(&mirror, vec![1, 2, 5, 6, 7, 3, 4]);
+    assert!(mirror.read_attributes(3) == (2, 3..5));
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn bind_attribute_runs() {
+    let rope        = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    rope.set_attributes(1..3, 1);
+
+    let rope        = RopeBinding::from_mutable(&rope);
+    let runs        = rope.attribute_runs_binding();
+
+    assert!(runs.get() == vec![(0, 0..1), (1, 1..3), (0, 3..4)]);
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn attribute_runs_only_notify_for_attribute_changes() {
+    let rope        = RopeBindingMut::<usize, usize>::new();
+    let mirror      = RopeBinding::from_mutable(&rope);
+    let runs        = mirror.attribute_runs_binding();
+
+    // Count the notifications (this is notified before the stream that follows the runs)
+    let num_changes = bind(0);
+    let notify_num_changes = num_changes.clone();
+    runs.when_changed(notify(move || notify_num_changes.set(notify_num_changes.get() + 1))).keep_alive();
+
+    let mut runs_stream = follow(runs.clone());
+    executor::block_on(async {
+        assert!(runs_stream.next().await == Some(vec![]));
+
+        // Inserting cells changes the runs
+        rope.replace(0..0, vec![1, 2, 3, 4]);
+        assert!(runs_stream.next().await == Some(vec![(0, 0..4)]));
+        assert!(num_changes.get() == 1);
+
+        // Replacing a cell with a different value leaves the runs the same, so the next notification is for the inserted cell
+        rope.replace(1..2, vec![5]);
+        rope.replace(4..4, vec![6]);
+        assert!(runs_stream.next().await == Some(vec![(0, 0..5)]));
+        assert!(num_changes.get() == 2);
+
+        // Setting the attributes changes the runs
+        rope.set_attributes(0..2, 1);
+        assert!(runs_stream.next().await == Some(vec![(1, 0..2), (0, 2..5)]));
+        assert!(num_changes.get() == 3);
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn attribute_runs_follow_replacements_of_the_same_length() {
+    let rope        = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    rope.set_attributes(1..3, 1);
+
+    let mirror      = RopeBinding::from_mutable(&rope);
+    wait_for_cells(&mirror, vec![1, 2, 3, 4]);
+    let runs        = mirror.attribute_runs_binding();
+    assert!(runs.get() == vec![(0, 0..1), (1, 1..3), (0, 3..4)]);
+
+    let mut runs_stream = follow(runs);
+    executor::block_on(async {
+        assert!(runs_stream.next().await == Some(vec![(0, 0..1), (1, 1..3), (0, 3..4)]));
+
+        // The replacement cells are at the start of the rope, so they take on the attribute of the first cell
+        rope.replace(0..2, vec![5, 6]);
+        assert!(runs_stream.next().await == Some(vec![(0, 0..2), (1, 2..3), (0, 3..4)]));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn match_ranges_follow_edits() {
+    let rope        = RopeBindingMut::<char, ()>::new();
+    rope.replace(0..0, "one two one".chars());
+
+    let mirror      = RopeBinding::from_mutable(&rope);
+    let matches     = mirror.match_ranges(|cells| {
+        // Find every occurrence of 'one'
+        let pattern = ['o', 'n', 'e'];
+        (0..cells.len()).filter(|pos| cells[*pos..].starts_with(&pattern)).map(|pos| pos..(pos+pattern.len())).collect()
+    });
+
+    let mut follow_matches = follow(matches);
+    executor::block_on(async {
+        assert!(follow_matches.next().await == Some(vec![0..3, 8..11]));
+
+        // Editing the rope finds the matches again
+        rope.replace(4..7, "one".chars());
+        assert!(follow_matches.next().await == Some(vec![0..3, 4..7, 8..11]));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn match_ranges_search_next_to_edits() {
+    let rope        = RopeBindingMut::<char, ()>::new();
+    rope.replace(0..0, "xne two one one".chars());
+
+    let mirror      = RopeBinding::from_mutable(&rope);
+    let matches     = mirror.match_ranges(|cells| {
+        let pattern = ['o', 'n', 'e'];
+        (0..cells.len()).filter(|pos| cells[*pos..].starts_with(&pattern)).map(|pos| pos..(pos+pattern.len())).collect()
+    });
+
+    let mut follow_matches = follow(matches);
+    executor::block_on(async {
+        assert!(follow_matches.next().await == Some(vec![8..11, 12..15]));
+
+        // Edits can create matches that extend past the edited range
+        rope.replace(0..1, vec!['o']);
+        assert!(follow_matches.next().await == Some(vec![0..3, 8..11, 12..15]));
+
+        // ...or remove them
+        rope.replace(9..10, vec![]);
+        assert!(follow_matches.next().await == Some(vec![0..3, 11..14]));
+
+        // Matches after an edit are moved
+        rope.replace(4..4, "one ".chars());
+        assert!(follow_matches.next().await == Some(vec![0..3, 4..7, 15..18]));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn cursor_follows_edits() {
+    let rope            = RopeBindingMut::<usize, ()>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+
+    let cursor          = rope.cursor(3);
+    let mut positions   = follow(cursor.clone());
+
+    executor::block_on(async {
+        assert!(positions.next().await == Some(3));
+
+        // Inserting before the cursor moves it right
+        rope.replace(1..1, vec![7, 8]);
+        assert!(positions.next().await == Some(5));
+
+        // Deleting before the cursor moves it left
+        rope.replace(0..1, vec![]);
+        assert!(positions.next().await == Some(4));
+
+        // Editing after the cursor leaves it where it is (edits are applied in order, so the next position is from the insertion at the start)
+        rope.replace(4..6, vec![9]);
+        rope.replace(4..4, vec![10]);
+        rope.replace(0..0, vec![11]);
+        assert!(positions.next().await == Some(5));
+
+        // Deleting a range that contains the cursor clamps it to the start of the range
+        rope.replace(3..7, vec![]);
+        assert!(positions.next().await == Some(3));
+
+        // The cursor can be moved by setting it
+        cursor.set(1);
+        assert!(positions.next().await == Some(1));
+
+        rope.replace(0..0, vec![12]);
+        assert!(positions.next().await == Some(2));
+    });
+}
+
+#[test]
+fn insert_with_policy() {
+    let rope = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    rope.set_attributes(0..2, 1);
+    rope.set_attributes(2..4, 2);
+
+    // Insert at the boundary between the two attribute ranges
+    rope.edit_with_policy(2..2, vec![5], InsertPolicy::InheritFromLeft);
+    assert!(rope.read_attributes(2) == (1, 0..3));
+
+    rope.edit_with_policy(3..3, vec![6], InsertPolicy::InheritFromRight);
+    assert!(rope.read_attributes(3) == (2, 3..6));
+
+    rope.edit_with_policy(3..3, vec![7], InsertPolicy::UseDefault);
+    assert!(rope.read_attributes(3) == (0, 3..4));
+
+    // At the ends of the rope, the default attributes are used when there's nothing to inherit from
+    rope.edit_with_policy(0..0, vec![8], InsertPolicy::InheritFromLeft);
+    assert!(rope.read_attributes(0) == (0, 0..1));
+
+    rope.edit_with_policy(8..8, vec![9], InsertPolicy::InheritFromRight);
+    assert!(rope.read_attributes(8) == (0, 8..9));
+
+    assert!(rope.read_cells(0..9).collect::<Vec<_>>() == vec![8, 1, 2, 5, 7, 6, 3, 4, 9]);
+}
+
+#[test]
+fn map_cells_in_range() {
+    let rope    = RopeBindingMut::<char, usize>::new();
+    let mirror  = RopeBinding::from_mutable(&rope);
+
+    rope.replace(0..0, "hello world".chars().collect::<Vec<_>>());
+    rope.set_attributes(3..8, 1);
+    wait_for_cells(&mirror, "hello world".chars().collect());
+
+    rope.map_cells_in_range(2..9, |c| c.to_ascii_uppercase());
+
+    assert!(rope.read_cells(0..11).collect::<String>() == "heLLO WORld");
+    assert!(rope.read_attributes(0) == (0, 0..3));
+    assert!(rope.read_attributes(3) == (1, 3..8));
+    assert!(rope.read_attributes(8) == (0, 8..11));
+
+    wait_for_cells(&mirror, "heLLO WORld".chars().collect());
+}
+
+
+#[test]
+fn follow_changes_grouped_by_edit() {
+    let rope        = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4, 5, 6]);
+
+    let mut grouped = rope.follow_changes_grouped();
+
+    // Setting the attributes of the even cells is one edit that generates several actions
+    rope.set_attributes_matching(|cell| cell % 2 == 0, 1);
+    rope.replace(0..0, vec![0]);
+
+    executor::block_on(async {
+        let first_edit  = grouped.next().await.unwrap();
+        let second_edit = grouped.next().await.unwrap();
+
+        assert!(first_edit.len() == 3);
+        assert!(second_edit == vec![RopeAction::Replace(0..0, vec![0])]);
+    });
+}
+
+#[test]
+fn with_cells_reads_without_copying() {
+    let rope    = RopeBindingMut::<usize, ()>::new();
+    let follow  = RopeBinding::from_mutable(&rope);
+    rope.replace(0..0, vec![1, 2, 3, 4, 5]);
+
+    let total   = rope.with_cells(1..4, |cells| cells.sum::<usize>());
+    assert!(total == 9);
+
+    wait_for_cells(&follow, vec![1, 2, 3, 4, 5]);
+
+    let total   = follow.with_cells(0..5, |cells| cells.sum::<usize>());
+    assert!(total == 15);
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn interleave_sorted_ropes() {
+    let left            = RopeBindingMut::<usize, ()>::new();
+    let right           = RopeBindingMut::<usize, ()>::new();
+    let left_follow     = RopeBinding::from_mutable(&left);
+    let right_follow    = RopeBinding::from_mutable(&right);
+
+    // Merge the two ropes in order
+    let merged          = left_follow.interleave(&right_follow, |left, right| if left <= right { InterleaveSide::Left } else { InterleaveSide::Right });
+
+    left.replace(0..0, vec![1, 4, 6]);
+    right.replace(0..0, vec![2, 3, 7]);
+    wait_for_cells(&merged, vec![1, 2, 3, 4, 6, 7]);
+
+    // Editing either rope updates the interleaved rope
+    right.replace(3..3, vec![8]);
+    wait_for_cells(&merged, vec![1, 2, 3, 4, 6, 7, 8]);
+
+    left.replace(1..2, vec![5]);
+    wait_for_cells(&merged, vec![1, 2, 3, 5, 6, 7, 8]);
+}
+
+#[test]
+fn interleave_only_replaces_edited_cells() {
+    let sorted          = |left: &usize, right: &usize| if left <= right { InterleaveSide::Left } else { InterleaveSide::Right };
+    let mut interleaved = InterleavedCells::new(vec![10, 30, 50, 70, 90], vec![20, 40, 60, 80, 100], sorted);
+
+    let action          = interleaved.edit(InterleaveSide::Left, RopeAction::<usize, ()>::Replace(2..3, vec![55]));
+    assert!(action == Some(RopeAction::Replace(4..5, vec![55])));
+
+    let action          = interleaved.edit(InterleaveSide::Right, RopeAction::<usize, ()>::Replace(1..1, vec![25, 35]));
+    assert!(action == Some(RopeAction::Replace(2..3, vec![25, 30, 35])));
+    assert!(interleaved.cells() == vec![10, 20, 25, 30, 35, 40, 55, 60, 70, 80, 90, 100]);
+}
+
+#[test]
+fn interleave_edits_match_interleaving_again() {
+    let sorted          = |left: &usize, right: &usize| if left <= right { InterleaveSide::Left } else { InterleaveSide::Right };
+    let mut left        = (0..20).map(|pos| pos * 3).collect::<Vec<_>>();
+    let mut right       = (0..20).map(|pos| pos * 5).collect::<Vec<_>>();
+    let mut interleaved = InterleavedCells::new(left.clone(), right.clone(), sorted);
+    let mut cells       = interleaved.cells();
+
+    // Make a series of pseudo-random edits to both sides
+    let mut seed        = 12345usize;
+    let mut next_random = move |max: usize| { seed = (seed * 1103515245 + 12345) % 2147483648; seed % (max+1) };
+
+    for edit_num in 0..200 {
+        let (side, edited) = if edit_num % 2 == 0 { (InterleaveSide::Left, &mut left) } else { (InterleaveSide::Right, &mut right) };
+
+        let start       = next_random(edited.len());
+        let end         = start + next_random((edited.len() - start).min(3));
+        let new_cells   = (0..next_random(3)).map(|_| next_random(100)).collect::<Vec<_>>();
+
+        edited.splice(start..end, new_cells.clone());
+        if let Some(action) = interleaved.edit(side, RopeAction::<usize, ()>::Replace(start..end, new_cells)) {
+            action.apply_to_vec(&mut cells);
+        }
+
+        let expected    = InterleavedCells::new(left.clone(), right.clone(), sorted).cells();
+        assert!(cells == expected, "{:?} != {:?}", cells, expected);
+        assert!(interleaved.cells() == expected);
+    }
+}
+
+#[test]
+fn truncate_rope() {
+    let rope        = RopeBindingMut::<usize, ()>::new();
+    let mut changes = rope.follow_changes();
+    rope.replace(0..0, vec![1, 2, 3, 4, 5]);
+
+    executor::block_on(async { changes.next().await });
+
+    rope.truncate(2);
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![1, 2]);
+    executor::block_on(async { assert!(changes.next().await == Some(RopeAction::Replace(2..5, vec![]))) });
+
+    // Truncating to a longer length does nothing
+    rope.truncate(10);
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![1, 2]);
+}
+
+#[test]
+fn resize_rope() {
+    let rope = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2]);
+    rope.set_attributes(0..2, 3);
+
+    // Growing the rope pads it with the filler, using the attributes of the last cell
+    rope.resize(5, 0);
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![1, 2, 0, 0, 0]);
+    assert!(rope.read_attributes(4) == (3, 0..5));
+
+    rope.resize(1, 0);
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![1]);
+}
+
+#[test]
+fn from_stream_with_initial_cells() {
+    let rope    = RopeBindingMut::<usize, ()>::new();
+    let follow  = RopeBinding::from_stream_with_initial(rope.follow_changes(), vec![1, 2, 3]);
+
+    // The initial cells are available immediately
+    assert!(follow.read_cells(0..follow.len()).collect::<Vec<_>>() == vec![1, 2, 3]);
+
+    // Changes from the stream are applied on top of the initial cells
+    rope.replace(0..0, vec![4]);
+    wait_for_cells(&follow, vec![4, 1, 2, 3]);
+}
+
+#[test]
+fn map_windowed_follows_edits() {
+    let rope        = RopeBindingMut::<usize, ()>::new();
+
+    // Each cell is the sum of itself and its neighbours
+    let windowed    = rope.map_windowed(1, |window, _idx| window.iter().sum::<usize>());
+
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    wait_for_cells(&windowed, vec![3, 6, 9, 7]);
+
+    // Changing a cell changes the cells whose windows include it
+    rope.replace(1..2, vec![10]);
+    wait_for_cells(&windowed, vec![11, 14, 17, 7]);
+
+    // Inserting and removing cells updates the cells either side
+    rope.replace(4..4, vec![5]);
+    wait_for_cells(&windowed, vec![11, 14, 17, 12, 9]);
+
+    rope.replace(0..2, vec![]);
+    wait_for_cells(&windowed, vec![7, 12, 9]);
+}
+
+#[test]
+fn map_windowed_starts_with_existing_contents() {
+    let rope        = RopeBindingMut::<usize, usize>::new();
+    rope.replace(0..0, vec![1, 2, 3, 4]);
+    rope.set_attributes(2..4, 1);
+
+    let windowed    = rope.map_windowed(1, |window, _idx| window.iter().sum::<usize>());
+    wait_for_cells(&windowed, vec![3, 6, 9, 7]);
+    assert!(windowed.read_attributes(2) == (1, 2..4));
+
+    rope.replace(1..2, vec![10]);
+    wait_for_cells(&windowed, vec![11, 14, 17, 7]);
+}
+
+#[test]
+fn map_windowed_keeps_attributes_next_to_edits() {
+    let rope        = RopeBindingMut::<usize, usize>::new();
+    rope.replace_attributes(0..0, vec![1, 2, 3], 0);
+    rope.replace_attributes(3..3, vec![4, 5, 6], 7);
+
+    let windowed    = rope.map_windowed(1, |window, _idx| window.iter().sum::<usize>());
+    wait_for_cells(&windowed, vec![3, 6, 9, 12, 15, 11]);
+
+    // The cell at the start of the second run is generated again, but stays in that run
+    rope.replace(4..5, vec![50]);
+    wait_for_cells(&windowed, vec![3, 6, 9, 57, 60, 56]);
+    assert!(windowed.read_attributes(0) == (0, 0..3));
+    assert!(windowed.read_attributes(3) == (7, 3..6));
+}
+
+#[test]
+fn enumerate_renumbers_after_edits() {
+    let rope        = RopeBindingMut::<char, ()>::new();
+    let enumerated  = rope.enumerate();
+
+    rope.replace(0..0, vec!['a', 'b', 'c']);
+    wait_for_cells(&enumerated, vec![(0, 'a'), (1, 'b'), (2, 'c')]);
+
+    // Replacing a cell without changing the length keeps the numbering
+    rope.replace(1..2, vec!['x']);
+    wait_for_cells(&enumerated, vec![(0, 'a'), (1, 'x'), (2, 'c')]);
+
+    // Inserting and removing cells renumbers the cells that come after the edit
+    rope.replace(0..0, vec!['y', 'z']);
+    wait_for_cells(&enumerated, vec![(0, 'y'), (1, 'z'), (2, 'a'), (3, 'x'), (4, 'c')]);
+
+    rope.replace(1..3, vec![]);
+    wait_for_cells(&enumerated, vec![(0, 'y'), (1, 'x'), (2, 'c')]);
+}
+
+#[test]
+fn map_cells_with_attribute_transforms_matching_cells() {
+    let rope        = RopeBindingMut::<char, bool>::new();
+    let redacted    = rope.map_cells_with_attribute(|secret| *secret, |_| '*');
+
+    rope.replace_attributes(0..0, vec!['a', 'b'], false);
+    rope.replace_attributes(2..2, vec!['c', 'd'], true);
+    wait_for_cells(&redacted, vec!['a', 'b', '*', '*']);
+
+    // Cells inserted into a secret region take on its attributes, so are also transformed
+    rope.replace(3..3, vec!['e']);
+    wait_for_cells(&redacted, vec!['a', 'b', '*', '*', '*']);
+
+    rope.replace(0..1, vec!['f']);
+    wait_for_cells(&redacted, vec!['f', 'b', '*', '*', '*']);
+}
+
+#[test]
+fn map_cells_with_attribute_follows_attribute_changes() {
+    let rope        = RopeBindingMut::<char, bool>::new();
+    let redacted    = rope.map_cells_with_attribute(|secret| *secret, |_| '*');
+
+    rope.replace_attributes(0..0, vec!['a', 'b', 'c', 'd'], false);
+    wait_for_cells(&redacted, vec!['a', 'b', 'c', 'd']);
+
+    // Marking cells as secret transforms them, and clearing the attribute restores the original cells
+    rope.set_attributes(1..3, true);
+    wait_for_cells(&redacted, vec!['a', '*', '*', 'd']);
+    assert!(redacted.read_attributes(1) == (true, 1..3));
+
+    rope.set_attributes(0..2, false);
+    wait_for_cells(&redacted, vec!['a', 'b', '*', 'd']);
+}
+
+#[test]
+fn map_cells_with_attribute_starts_with_existing_contents() {
+    let rope        = RopeBindingMut::<char, bool>::new();
+    rope.replace_attributes(0..0, vec!['a', 'b'], false);
+    rope.replace_attributes(2..2, vec!['c', 'd'], true);
+
+    let redacted    = rope.map_cells_with_attribute(|secret| *secret, |_| '*');
+    wait_for_cells(&redacted, vec!['a', 'b', '*', '*']);
+
+    rope.replace(4..4, vec!['e']);
+    wait_for_cells(&redacted, vec!['a', 'b', '*', '*', '*']);
+}
+
+#[test]
+fn enumerate_starts_with_existing_contents() {
+    let rope        = RopeBindingMut::<char, usize>::new();
+    rope.replace(0..0, vec!['a', 'b', 'c', 'd']);
+    rope.set_attributes(2..4, 1);
+
+    let enumerated  = rope.enumerate();
+    wait_for_cells(&enumerated, vec![(0, 'a'), (1, 'b'), (2, 'c'), (3, 'd')]);
+
+    // The renumbered cells keep their attributes
+    rope.replace(0..1, vec![]);
+    wait_for_cells(&enumerated, vec![(0, 'b'), (1, 'c'), (2, 'd')]);
+    assert!(enumerated.read_attributes(0) == (0, 0..1));
+    assert!(enumerated.read_attributes(1) == (1, 1..3));
+}
+
+#[test]
+fn try_replace_reports_changes() {
+    let rope = RopeBindingMut::<char, ()>::new();
+
+    assert!(rope.try_replace(0..0, vec!['a', 'b', 'c']));
+    assert!(!rope.try_replace(0..2, vec!['a', 'b']));
+    assert!(!rope.try_replace(1..1, vec![]));
+    assert!(rope.try_replace(0..2, vec!['a', 'x']));
+
+    assert!(rope.read_cells(0..3).collect::<Vec<_>>() == vec!['a', 'x', 'c']);
+}
+
+#[test]
+fn retain_cells_removes_unmatched_cells() {
+    let rope    = RopeBindingMut::<usize, ()>::new();
+    let copy    = RopeBinding::from_stream(rope.follow_changes());
+
+    rope.replace(0..0, vec![1, 2, 4, 5, 6, 7, 8]);
+
+    // Retaining every cell leaves the rope as it is
+    assert!(!rope.retain_cells(|_| true));
+
+    assert!(rope.retain_cells(|cell| cell % 2 == 0));
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![2, 4, 6, 8]);
+    wait_for_cells(&copy, vec![2, 4, 6, 8]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn copy_rope_through_serialized_actions() {
+    fn assert_serializable<T: serde::Serialize+serde::de::DeserializeOwned>() { }
+    assert_serializable::<SerializedRopeAction<char, u8>>();
+
+    let rope        = RopeBindingMut::<char, u8>::new();
+    let serialized  = rope.follow_changes().map(SerializedRopeAction::from);
+    let copy        = RopeBinding::from_serialized_stream(serialized);
+
+    rope.replace(0..0, vec!['a', 'b', 'c']);
+    rope.set_attributes(1..2, 1);
+    rope.replace_attributes(3..3, vec!['d'], 2);
+
+    wait_for_cells(&copy, vec!['a', 'b', 'c', 'd']);
+    assert!(copy.read_attributes(1) == (1, 1..2));
+    assert!(copy.read_attributes(3) == (2, 3..4));
+}
+
+#[test]
+fn chain_with_separator_only_separates_non_empty_ropes() {
+    let left    = RopeBindingMut::<char, ()>::new();
+    let right   = RopeBindingMut::<char, ()>::new();
+    let chained = left.chain_with_separator(&right, vec![',', ' ']);
+
+    left.replace(0..0, vec!['a']);
+    wait_for_cells(&chained, vec!['a']);
+
+    right.replace(0..0, vec!['b']);
+    wait_for_cells(&chained, vec!['a', ',', ' ', 'b']);
+
+    right.replace(1..1, vec!['c']);
+    left.replace(0..0, vec!['x']);
+    wait_for_cells(&chained, vec!['x', 'a', ',', ' ', 'b', 'c']);
+
+    // The separator is removed when either side becomes empty
+    left.replace(0..2, vec![]);
+    wait_for_cells(&chained, vec!['b', 'c']);
+
+    left.replace(0..0, vec!['d']);
+    wait_for_cells(&chained, vec!['d', ',', ' ', 'b', 'c']);
+
+    right.replace(0..2, vec![]);
+    wait_for_cells(&chained, vec!['d']);
+}
+
+#[test]
+fn convert_between_offsets_and_lines() {
+    let rope = RopeBindingMut::<char, ()>::new();
+    let text = RopeBinding::from_mutable(&rope);
+
+    rope.replace(0..0, "ab\ncde\n\nf".chars().collect::<Vec<_>>());
+    wait_for_cells(&text, "ab\ncde\n\nf".chars().collect());
+
+    assert!(text.line_column(0) == (0, 0));
+    assert!(text.line_column(2) == (0, 2));
+    assert!(text.line_column(3) == (1, 0));
+    assert!(text.line_column(5) == (1, 2));
+    assert!(text.line_column(8) == (3, 0));
+    assert!(text.line_column(100) == (3, 1));
+
+    assert!(text.offset_for_line_column(0, 1) == 1);
+    assert!(text.offset_for_line_column(1, 2) == 5);
+    assert!(text.offset_for_line_column(1, 10) == 6);
+    assert!(text.offset_for_line_column(2, 0) == 7);
+    assert!(text.offset_for_line_column(3, 0) == 8);
+    assert!(text.offset_for_line_column(3, 1) == 9);
+    assert!(text.offset_for_line_column(10, 0) == 9);
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn line_count_follows_edits() {
+    let rope                = RopeBindingMut::<char, ()>::new();
+    let text                = RopeBinding::from_mutable(&rope);
+    let line_count          = text.line_count_binding();
+    let mut follow_count    = follow(line_count);
+
+    executor::block_on(async {
+        assert!(follow_count.next().await == Some(1));
+
+        rope.replace(0..0, "a\nb\nc".chars().collect::<Vec<_>>());
+        assert!(follow_count.next().await == Some(3));
+
+        rope.replace(1..3, vec![]);
+        assert!(follow_count.next().await == Some(2));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn line_count_starts_from_existing_lines() {
+    let rope                = RopeBindingMut::<char, ()>::new();
+    rope.replace(0..0, "a\nb\nc".chars().collect::<Vec<_>>());
+
+    let text                = RopeBinding::from_mutable(&rope);
+    wait_for_cells(&text, "a\nb\nc".chars().collect());
+
+    let line_count          = text.line_count_binding();
+    assert!(line_count.get() == 3);
+
+    let mut follow_count    = follow(line_count);
+
+    executor::block_on(async {
+        assert!(follow_count.next().await == Some(3));
+
+        rope.replace(5..5, vec!['\n', 'd']);
+        assert!(follow_count.next().await == Some(4));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn total_len_of_several_ropes() {
+    let first           = RopeBindingMut::<char, ()>::new();
+    let second          = RopeBindingMut::<char, ()>::new();
+    first.replace(0..0, vec!['a', 'b']);
+
+    let total_len       = total_len_binding(vec![&first, &second]);
+    let mut follow_len  = follow(total_len);
+
+    executor::block_on(async {
+        assert!(follow_len.next().await == Some(2));
+
+        second.replace(0..0, vec!['c', 'd', 'e']);
+        assert!(follow_len.next().await == Some(5));
+
+        first.replace(0..1, vec![]);
+        assert!(follow_len.next().await == Some(4));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn total_len_ignores_edits_that_keep_the_length() {
+    let first           = RopeBindingMut::<char, ()>::new();
+    let second          = RopeBindingMut::<char, ()>::new();
+    let total_len       = total_len_binding(vec![&first, &second]);
+    let mut follow_len  = follow(total_len);
+
+    executor::block_on(async {
+        assert!(follow_len.next().await == Some(0));
+
+        first.replace(0..0, vec!['a', 'b']);
+        assert!(follow_len.next().await == Some(2));
+
+        // Replacing a cell doesn't change the length, so the next value comes from the edit to the second rope
+        first.replace(0..1, vec!['c']);
+        second.replace(0..0, vec!['d']);
+        assert!(follow_len.next().await == Some(3));
+    });
+}
+
+#[test]
+fn merge_two_streams_of_concurrent_edits() {
+    // Each stream edits its own copy of the rope: the first makes 'abc' then 'axyc', the second makes 'd' then 'de'
+    let first   = stream::iter(vec![RopeAction::Replace(0..0, vec!['a', 'b', 'c']), RopeAction::Replace(1..2, vec!['x', 'y'])]);
+    let second  = stream::iter(vec![RopeAction::Replace(0..0, vec!['d']), RopeAction::Replace(1..1, vec!['e'])]);
+    let merged  = RopeBinding::<char, ()>::from_two_streams(first, second, transform_rope_action);
+
+    // Both insertions were at the start, so the cells from the first stream go first
+    wait_for_cells(&merged, vec!['a', 'x', 'y', 'c', 'd', 'e']);
+}
+
+#[test]
+fn merge_two_streams_transforms_edits_that_arrive_later() {
+    let (first_sender, first)       = futures::channel::mpsc::unbounded();
+    let (second_sender, second)     = futures::channel::mpsc::unbounded();
+    let merged                      = RopeBinding::<char, ()>::from_two_streams(first, second, transform_rope_action);
+
+    // The first edit is applied without waiting for the second stream
+    first_sender.unbounded_send(RopeAction::Replace(0..0, vec!['a', 'b'])).unwrap();
+    wait_for_cells(&merged, vec!['a', 'b']);
+
+    // The second edit is transformed against the first one however late it arrives (it would be at the start if it was applied as it is)
+    second_sender.unbounded_send(RopeAction::Replace(0..0, vec!['c'])).unwrap();
+    wait_for_cells(&merged, vec!['a', 'b', 'c']);
+}
+
+#[test]
+fn merge_two_streams_when_only_one_stream_is_edited() {
+    let (first_sender, first)       = futures::channel::mpsc::unbounded();
+    let (_second_sender, second)    = futures::channel::mpsc::unbounded();
+    let merged                      = RopeBinding::<char, ()>::from_two_streams(first, second, transform_rope_action);
+
+    // The second stream stays open but never sends anything
+    first_sender.unbounded_send(RopeAction::Replace(0..0, vec!['a', 'b'])).unwrap();
+    wait_for_cells(&merged, vec!['a', 'b']);
+
+    first_sender.unbounded_send(RopeAction::Replace(1..1, vec!['c'])).unwrap();
+    wait_for_cells(&merged, vec!['a', 'c', 'b']);
+}
+
+#[test]
+fn merge_two_streams_applies_edits_after_a_stream_finishes() {
+    let (first_sender, first)       = futures::channel::mpsc::unbounded();
+    let (second_sender, second)     = futures::channel::mpsc::unbounded();
+    let merged                      = RopeBinding::<char, ()>::from_two_streams(first, second, transform_rope_action);
+
+    first_sender.unbounded_send(RopeAction::Replace(0..0, vec!['a', 'b'])).unwrap();
+    second_sender.unbounded_send(RopeAction::Replace(0..0, vec!['c'])).unwrap();
+    second_sender.close_channel();
+    wait_for_cells(&merged, vec!['a', 'b', 'c']);
+
+    // The first stream still hasn't seen the 'c' from the second stream
+    first_sender.unbounded_send(RopeAction::Replace(1..1, vec!['d'])).unwrap();
+    wait_for_cells(&merged, vec!['a', 'd', 'b', 'c']);
+}
+
+#[test]
+fn transform_rope_action_before_and_after() {
+    let against = RopeAction::<char, ()>::Replace(2..4, vec!['a', 'b', 'c']);
+
+    assert!(transform_rope_action(RopeAction::Replace(0..1, vec!['x']), &against, false) == RopeAction::Replace(0..1, vec!['x']));
+    assert!(transform_rope_action(RopeAction::Replace(5..6, vec!['x']), &against, false) == RopeAction::Replace(6..7, vec!['x']));
+    assert!(transform_rope_action(RopeAction::Replace(4..4, vec!['x']), &against, false) == RopeAction::Replace(5..5, vec!['x']));
+}
+
+#[test]
+fn transform_rope_action_overlapping() {
+    let against = RopeAction::<char, ()>::Replace(2..4, vec!['a', 'b', 'c']);
+
+    // Only the cells that weren't replaced are edited
+    assert!(transform_rope_action(RopeAction::Replace(1..3, vec!['x']), &against, false) == RopeAction::Replace(1..2, vec!['x']));
+    assert!(transform_rope_action(RopeAction::Replace(3..6, vec!['x']), &against, false) == RopeAction::Replace(5..7, vec!['x']));
+    assert!(transform_rope_action(RopeAction::Replace(2..4, vec!['x']), &against, false) == RopeAction::Replace(5..5, vec!['x']));
+    assert!(transform_rope_action(RopeAction::Replace(2..4, vec!['x']), &against, true) == RopeAction::Replace(2..2, vec!['x']));
+
+    // A range that contains the whole of the other range on both sides replaces its cells too
+    assert!(transform_rope_action(RopeAction::Replace(1..5, vec!['x']), &against, false) == RopeAction::Replace(1..6, vec!['x']));
+    assert!(transform_rope_action(RopeAction::Replace(3..3, vec!['x']), &against, false) == RopeAction::Replace(2..2, vec![]));
+
+    // Insertions at the same point are ordered by which stream they're from
+    let against = RopeAction::<char, ()>::Replace(2..2, vec!['a', 'b']);
+    assert!(transform_rope_action(RopeAction::Replace(2..2, vec!['x']), &against, false) == RopeAction::Replace(4..4, vec!['x']));
+    assert!(transform_rope_action(RopeAction::Replace(2..2, vec!['x']), &against, true) == RopeAction::Replace(2..2, vec!['x']));
+}
+
+#[test]
+fn transform_rope_action_gives_the_same_result_in_either_order() {
+    let initial = "0123456789".chars().collect::<Vec<_>>();
+    let ranges  = vec![0..0, 2..2, 2..4, 1..5, 3..3, 3..7, 4..4, 4..6, 6..9, 9..10, 10..10];
+
+    for first_range in ranges.iter() {
+        for second_range in ranges.iter() {
+            let first   = RopeAction::<char, ()>::Replace(first_range.clone(), vec!['a', 'b']);
+            let second  = RopeAction::<char, ()>::Replace(second_range.clone(), vec!['x']);
+
+            // Apply the first action then the second, and the second action then the first
+            let mut first_then_second = AttributedRope::<char, ()>::from(initial.clone());
+            first_then_second.edit(first.clone());
+            first_then_second.edit(transform_rope_action(second.clone(), &first, false));
+
+            let mut second_then_first = AttributedRope::<char, ()>::from(initial.clone());
+            second_then_first.edit(second.clone());
+            second_then_first.edit(transform_rope_action(first.clone(), &second, true));
+
+            assert!(first_then_second.read_cells(0..first_then_second.len()).cloned().collect::<Vec<_>>() == second_then_first.read_cells(0..second_then_first.len()).cloned().collect::<Vec<_>>(),
+                "{:?} {:?}", first_range, second_range);
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn bind_rope_stream_to_vec() {
+    let rope                = RopeBindingMut::<usize, ()>::new();
+    let cells               = bind_rope_stream(rope.follow_changes());
+    let mut follow_cells    = follow(cells);
+
+    executor::block_on(async {
+        assert!(follow_cells.next().await == Some(vec![]));
+
+        rope.replace(0..0, vec![1, 2, 3]);
+        assert!(follow_cells.next().await == Some(vec![1, 2, 3]));
+
+        rope.replace(1..2, vec![4, 5]);
+        assert!(follow_cells.next().await == Some(vec![1, 4, 5, 3]));
+    });
+}
+
+#[test]
+fn build_rope_with_initial_content() {
+    let rope = RopeBindingMut::<char, u8>::builder()
+        .push('a')
+        .extend(vec!['b', 'c'])
+        .push_with_attributes(vec!['d', 'e'], 1)
+        .push('f')
+        .build();
+
+    assert!(rope.len() == 6);
+    assert!(rope.read_cells(0..6).collect::<String>() == "abcdef");
+    assert!(rope.read_attributes(0) == (0, 0..3));
+    assert!(rope.read_attributes(3) == (1, 3..5));
+    assert!(rope.read_attributes(5) == (0, 5..6));
+
+    // Edits made after the rope is built are followed as normal
+    let mut changes = rope.follow_changes();
+    rope.replace(6..6, vec!['g']);
+
+    executor::block_on(async {
+        assert!(changes.next().await == Some(RopeAction::Replace(6..6, vec!['g'])));
+    });
+    assert!(rope.read_cells(0..7).collect::<String>() == "abcdefg");
+}
+
+#[test]
+fn buffered_rope_sends_edits_on_flush() {
+    let rope        = RopeBindingMut::<char, ()>::new();
+    let buffered    = BufferedRopeBindingMut::new(&rope);
+    let rope_copy   = RopeBinding::from_mutable(&rope);
+
+    buffered.replace(0..0, vec!['a', 'b', 'c']);
+    buffered.replace(3..3, vec!['d']);
+
+    // The buffered rope can be read before it's flushed, but the edits haven't been sent yet
+    assert!(buffered.read_cells(0..4).collect::<String>() == "abcd");
+    assert!(buffered.has_pending_edits());
+    assert!(rope.len() == 0);
+
+    buffered.flush();
+
+    assert!(!buffered.has_pending_edits());
+    assert!(rope.read_cells(0..4).collect::<String>() == "abcd");
+    wait_for_cells(&rope_copy, vec!['a', 'b', 'c', 'd']);
+}
+
+#[test]
+fn buffered_rope_notifies_on_flush() {
+    let rope            = RopeBindingMut::<char, ()>::new();
+    let buffered        = BufferedRopeBindingMut::new(&rope);
+    let changed         = bind(false);
+
+    let notify_changed  = changed.clone();
+    buffered.get();
+    buffered.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+    buffered.replace(0..0, vec!['a']);
+    rope.flush_pending();
+    assert!(!changed.get());
+
+    buffered.flush();
+    rope.flush_pending();
+    assert!(changed.get());
+}
+
+#[test]
+fn buffered_rope_can_be_read_when_notified_of_a_flush() {
+    let rope            = RopeBindingMut::<char, ()>::new();
+    let buffered        = BufferedRopeBindingMut::new(&rope);
+    let len             = bind(0);
+
+    let notify_len      = len.clone();
+    let read_buffered   = buffered.clone();
+    buffered.get();
+    buffered.when_changed(notify(move || notify_len.set(read_buffered.len()))).keep_alive();
+
+    buffered.replace(0..0, vec!['a', 'b']);
+    buffered.flush();
+    rope.flush_pending();
+    assert!(len.get() == 2);
+}
+
+#[test]
+fn buffered_rope_flushes_when_idle() {
+    let timer       = ManualTimer::new();
+    let rope        = RopeBindingMut::<char, ()>::new();
+    let buffered    = BufferedRopeBindingMut::new_with_timer(&rope, timer.clone());
+    buffered.flush_when_idle(Duration::from_millis(20));
+
+    buffered.replace(0..0, vec!['a', 'b']);
+    timer.advance(Duration::from_millis(15));
+    assert!(rope.len() == 0);
+
+    // Editing the rope again delays the flush
+    buffered.replace(2..2, vec!['c']);
+    timer.advance(Duration::from_millis(15));
+    assert!(rope.len() == 0);
+    assert!(buffered.has_pending_edits());
+
+    timer.advance(Duration::from_millis(5));
+    assert!(!buffered.has_pending_edits());
+    assert!(rope.read_cells(0..3).collect::<String>() == "abc");
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn bind_rope_cells_follows_edits() {
+    let rope                = RopeBindingMut::<char, ()>::new();
+    rope.replace(0..0, vec!['a', 'b']);
+
+    let cells               = bind_rope_cells(&rope);
+    let mut follow_cells    = follow(cells);
+
+    executor::block_on(async {
+        assert!(follow_cells.next().await == Some(vec!['a', 'b']));
+
+        rope.replace(1..1, vec!['c']);
+        assert!(follow_cells.next().await == Some(vec!['a', 'c', 'b']));
+
+        rope.replace(0..1, vec![]);
+        assert!(follow_cells.next().await == Some(vec!['c', 'b']));
+    });
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn rope_follows_vec_binding() {
+    let cells       = bind(vec![1, 2, 3]);
+    let rope        = RopeBindingMut::<i32, ()>::new();
+    let _following  = rope.follow_vec_binding(BindRef::from(cells.clone()));
+
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![1, 2, 3]);
+
+    // Only the cells that changed are edited
+    let mut changes = rope.follow_changes();
+    cells.set(vec![1, 4, 3]);
+
+    executor::block_on(async {
+        assert!(changes.next().await == Some(RopeAction::Replace(1..2, vec![4])));
+    });
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![1, 4, 3]);
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn rope_stops_following_vec_binding_when_released() {
+    let cells       = bind(vec![1, 2, 3]);
+    let rope        = RopeBindingMut::<i32, ()>::new();
+    let following   = rope.follow_vec_binding(BindRef::from(cells.clone()));
+
+    drop(following);
+    cells.set(vec![4]);
+
+    assert!(rope.read_cells(0..rope.len()).collect::<Vec<_>>() == vec![1, 2, 3]);
+}
+
+#[test]
+fn acked_stream_numbers_changes() {
+    let rope        = RopeBindingMut::<char, ()>::new();
+    let mut changes = rope.follow_changes_acked();
+
+    executor::block_on(async {
+        rope.replace(0..0, vec!['a']);
+        assert!(changes.next().await == Some((0, RopeAction::Replace(0..0, vec!['a']))));
+
+        rope.replace(1..1, vec!['b']);
+        assert!(changes.next().await == Some((1, RopeAction::Replace(1..1, vec!['b']))));
+    });
+}
+
+#[test]
+fn acked_stream_redelivers_unacknowledged_changes() {
+    let rope            = RopeBindingMut::<char, ()>::new();
+    let mut changes     = rope.follow_changes_acked();
+    let acknowledger    = changes.acknowledger();
+
+    executor::block_on(async {
+        rope.replace(0..0, vec!['a']);
+        assert!(changes.next().await == Some((0, RopeAction::Replace(0..0, vec!['a']))));
+        changes.ack(0);
+
+        rope.replace(1..1, vec!['b']);
+        assert!(changes.next().await == Some((1, RopeAction::Replace(1..1, vec!['b']))));
+
+        // Change 1 wasn't acknowledged, so the new stream delivers it again before any new changes
+        let mut resumed = acknowledger.resume();
+        assert!(resumed.next().await == Some((1, RopeAction::Replace(1..1, vec!['b']))));
+
+        rope.replace(2..2, vec!['c']);
+        assert!(resumed.next().await == Some((2, RopeAction::Replace(2..2, vec!['c']))));
+
+        // The original stream ends once it's been replaced
+        assert!(changes.next().await.is_none());
+    });
+}
+
+#[test]
+fn acked_stream_resumes_after_being_dropped() {
+    let rope            = RopeBindingMut::<char, ()>::new();
+    let mut changes     = rope.follow_changes_acked();
+    let acknowledger    = changes.acknowledger();
+
+    executor::block_on(async {
+        rope.replace(0..0, vec!['a']);
+        assert!(changes.next().await == Some((0, RopeAction::Replace(0..0, vec!['a']))));
+    });
+
+    // The core keeps the unacknowledged changes after the stream has gone, along with any changes made afterwards
+    drop(changes);
+    rope.replace(1..1, vec!['b']);
+
+    executor::block_on(async {
+        let mut resumed = acknowledger.resume();
+        assert!(resumed.next().await == Some((0, RopeAction::Replace(0..0, vec!['a']))));
+        assert!(resumed.next().await == Some((1, RopeAction::Replace(1..1, vec!['b']))));
+
+        resumed.ack(1);
+        let mut resumed = acknowledger.resume();
+        rope.replace(2..2, vec!['c']);
+        assert!(resumed.next().await == Some((2, RopeAction::Replace(2..2, vec!['c']))));
+    });
+}
+
+#[test]
+fn waiting_acked_stream_ends_when_resumed() {
+    let rope            = RopeBindingMut::<char, ()>::new();
+    let mut changes     = rope.follow_changes_acked();
+    let acknowledger    = changes.acknowledger();
+
+    // Start waiting for a change that never arrives, and wait for the stream to register that it's waiting with the core
+    executor::block_on(async { assert!(futures::poll!(changes.next()).is_pending()); });
+    rope.flush_pending();
+
+    // The waiting stream is woken up and finishes when it's replaced
+    let _resumed        = acknowledger.resume();
+    executor::block_on(async { assert!(changes.next().await.is_none()); });
+}
@@ -0,0 +1,70 @@
+use crate::rope_binding::rope_binding::*;
+#[cfg(feature = "stream")]
+use crate::rope_binding::rope_ext::*;
+#[cfg(feature = "stream")]
+use crate::bindref::*;
+
+impl<Attribute> RopeBinding<char, Attribute>
+where
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Returns the line and column of a position in this rope, counting from 0
+    ///
+    /// Lines are separated by `'\n'` cells, and a newline is considered to be the last cell of the line that it ends.
+    /// Positions after the end of the rope are treated as being at the end of the rope.
+    ///
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        // The length is read along with the cells, so the rope can't be edited in between
+        self.with_all_cells(move |_len, cells| {
+            cells.take(offset).fold((0, 0), |(line, column), cell| {
+                if *cell == '\n' {
+                    (line+1, 0)
+                } else {
+                    (line, column+1)
+                }
+            })
+        })
+    }
+
+    ///
+    /// Returns the position in this rope of a line and column, counting from 0
+    ///
+    /// This is the inverse of `line_column()`. Columns past the end of a line are treated as being at the end of that line
+    /// (just before its newline), and lines past the end of the rope are treated as being at the end of the rope.
+    ///
+    pub fn offset_for_line_column(&self, line: usize, column: usize) -> usize {
+        self.with_all_cells(move |len, cells| {
+            let mut current_line    = 0;
+            let mut line_start      = 0;
+
+            for (pos, cell) in cells.enumerate() {
+                if *cell == '\n' {
+                    if current_line == line {
+                        // The column is past the end of this line
+                        return pos;
+                    }
+
+                    current_line    += 1;
+                    line_start      = pos+1;
+                } else if current_line == line && pos - line_start == column {
+                    return pos;
+                }
+            }
+
+            len
+        })
+    }
+
+    ///
+    /// Returns a binding to the number of lines in this rope
+    ///
+    /// The count is updated incrementally by counting the newlines that are added and removed as the rope is edited. An
+    /// empty rope has one (empty) line.
+    ///
+    #[cfg(feature = "stream")]
+    pub fn line_count_binding(&self) -> BindRef<usize> {
+        self.fold_binding(1,
+            |count, cell| if *cell == '\n' { count + 1 } else { count },
+            |count, cell| if *cell == '\n' { count - 1 } else { count })
+    }
+}
@@ -0,0 +1,30 @@
+use super::bound_rope::*;
+use super::rope_action_ext::*;
+use crate::bindref::*;
+use crate::bind_stream::*;
+
+use futures::prelude::*;
+
+///
+/// Creates a binding to the total length of several ropes
+///
+/// This is useful when a document is made up of several ropes (for example, one for each section) and something needs to
+/// know how long the whole document is. The total is updated by adding the number of cells inserted and subtracting the
+/// number removed by each edit, so the ropes are only read once when the binding is created. The binding only notifies
+/// when the total length changes: edits that replace cells without changing the length of a rope are ignored.
+///
+pub fn total_len_binding<Cell, Attribute>(ropes: Vec<&dyn BoundRope<Cell, Attribute>>) -> BindRef<usize>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    // Start from the current lengths of the ropes, then follow the edits made after that (each length is read at the point its stream starts)
+    let mut initial_len = 0;
+    let changes         = stream::select_all(ropes.iter().map(|rope| rope.read_and_follow_changes(&mut |rope| initial_len += rope.len())).collect::<Vec<_>>());
+
+    let total_len   = bind_stream(changes, initial_len, |total_len, action| {
+        let stats = action.change_stats();
+        total_len + stats.inserted - stats.deleted
+    });
+
+    BindRef::from(total_len)
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "diff")]
+use crate::traits::*;
+use crate::bindref::*;
+#[cfg(feature = "diff")]
+use crate::source_monitor::*;
+#[cfg(feature = "stream")]
+use crate::bind_stream::*;
+#[cfg(feature = "stream")]
+use crate::rope_binding::bound_rope::*;
+#[cfg(feature = "stream")]
+use crate::rope_binding::rope_action_ext::*;
+#[cfg(feature = "diff")]
+use crate::rope_binding::rope_binding_mut::*;
+
+#[cfg(feature = "diff")]
+use flo_rope::*;
+#[cfg(feature = "diff")]
+use similar::*;
+
+#[cfg(feature = "diff")]
+use std::hash::{Hash};
+
+///
+/// Returns the actions that will change a rope containing `old_cells` into one containing `new_cells`
+///
+#[cfg(feature = "diff")]
+pub (super) fn diff_actions<Cell, Attribute>(old_cells: &[Cell], new_cells: &[Cell]) -> Vec<RopeAction<Cell, Attribute>>
+where
+Cell:       Clone+Hash+Eq+Ord,
+Attribute:  Clone+PartialEq+Default {
+    // Sorting by the new index means that each action can be applied to the rope after the actions before it
+    let mut differences = capture_diff_slices(Algorithm::Myers, old_cells, new_cells);
+    differences.sort_by_key(|diff| diff.new_range().start);
+
+    let mut actions     = vec![];
+    for diff in differences {
+        use self::DiffOp::*;
+        match diff {
+            Equal { old_index: _, new_index: _, len: _ }            => { /* No difference */ },
+            Delete { old_index: _, old_len, new_index }             => { actions.push(RopeAction::Replace(new_index..(new_index+old_len), vec![])) },
+            Insert { old_index: _, new_index, new_len }             => { actions.push(RopeAction::Replace(new_index..new_index, new_cells[new_index..(new_index+new_len)].to_vec())) },
+            Replace { old_index: _, old_len, new_index, new_len }   => { actions.push(RopeAction::Replace(new_index..(new_index+old_len), new_cells[new_index..(new_index+new_len)].to_vec())) }
+        }
+    }
+
+    actions
+}
+
+///
+/// Creates a binding to the cells in a rope
+///
+/// The binding starts with the current contents of the rope, and is updated by applying each change to the rope to its
+/// value, so the rope is only read in full once. Attributes are ignored. This is useful for consumers that find a plain
+/// vector simpler to work with than a rope.
+///
+#[cfg(feature = "stream")]
+pub fn bind_rope_cells<Cell, Attribute, TRope>(rope: &TRope) -> BindRef<Vec<Cell>>
+where
+Cell:       'static+Send+Unpin+Clone+PartialEq,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default,
+TRope:      BoundRope<Cell, Attribute> {
+    // Start from the current contents of the rope, then follow the edits made after that
+    let mut initial_cells   = vec![];
+    let changes             = rope.read_and_follow_changes(&mut |rope| initial_cells = rope.read_cells(0..rope.len()).cloned().collect());
+
+    let cells               = bind_stream(changes, initial_cells, |mut cells, action| {
+        action.apply_to_vec(&mut cells);
+        cells
+    });
+
+    BindRef::from(cells)
+}
+
+#[cfg(feature = "diff")]
+impl<Cell, Attribute> RopeBindingMut<Cell, Attribute>
+where
+Cell:       'static+Send+Sync+Unpin+Clone+PartialEq+Hash+Eq+Ord,
+Attribute:  'static+Send+Sync+Clone+Unpin+PartialEq+Default {
+    ///
+    /// Updates this rope to match the value of a binding to a vector of cells, now and whenever the binding changes
+    ///
+    /// The rope is edited using the differences between the old and the new values of the binding, so anything following
+    /// the rope only sees the cells that changed. The rope follows the binding until the returned releasable is dropped.
+    /// Edits made to the rope in any other way will be overwritten when the binding changes.
+    ///
+    pub fn follow_vec_binding(&self, source: BindRef<Vec<Cell>>) -> Box<dyn Releasable> {
+        // Replace the current contents of the rope with the value of the binding
+        let current_cells   = self.read_cells(0..self.len()).collect::<Vec<_>>();
+        let last_cells      = source.get();
+        self.apply_actions(diff_actions(&current_cells, &last_cells));
+
+        // Apply the differences whenever the binding changes
+        let mut last_cells  = last_cells;
+        let rope            = self.clone();
+
+        Box::new(watch_source(source, move |new_cells: Vec<Cell>| {
+            rope.apply_actions(diff_actions(&last_cells, &new_cells));
+            last_cells = new_cells;
+        }))
+    }
+
+    ///
+    /// Applies a set of actions to this rope as a single edit
+    ///
+    fn apply_actions(&self, actions: Vec<RopeAction<Cell, Attribute>>) {
+        if !actions.is_empty() {
+            self.with_rope(move |rope| {
+                for action in actions {
+                    rope.edit(action);
+                }
+            });
+        }
+    }
+}
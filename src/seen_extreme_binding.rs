@@ -0,0 +1,158 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+///
+/// A binding that remembers the most extreme value (for example, the minimum or maximum) that another binding has had
+///
+/// The value only changes when the source binding takes a new extreme value. Calling `reset()` forgets the values
+/// seen so far and restarts tracking from the current value of the source binding.
+///
+#[derive(Clone)]
+pub struct SeenExtremeBinding<Value> {
+    /// The binding that is being tracked
+    source: Arc<BindRef<Value>>,
+
+    /// The most extreme value seen so far
+    extreme: Binding<Value>,
+
+    /// Monitors the source binding for changes
+    _change_monitor: Arc<Mutex<Box<dyn Releasable>>>
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> SeenExtremeBinding<Value> {
+    ///
+    /// Creates a new binding that tracks the extreme values of a source binding. `is_new_extreme` returns true if
+    /// its first argument is more extreme than its second.
+    ///
+    fn new(source: BindRef<Value>, is_new_extreme: fn(&Value, &Value) -> bool) -> SeenExtremeBinding<Value> {
+        let extreme         = Binding::new(source.get());
+
+        // Check for a new extreme value whenever the source changes
+        let extreme_copy    = extreme.clone();
+        let change_monitor  = watch_source(source.clone(), move |new_value: Value| {
+            extreme_copy.with_mut(move |extreme| {
+                if is_new_extreme(&new_value, extreme) {
+                    *extreme = new_value;
+                    true
+                } else {
+                    false
+                }
+            });
+        });
+
+        SeenExtremeBinding {
+            source:             Arc::new(source),
+            extreme,
+            _change_monitor:    Arc::new(Mutex::new(Box::new(change_monitor)))
+        }
+    }
+
+    ///
+    /// Forgets the values seen so far, setting this binding to the current value of the source binding
+    ///
+    pub fn reset(&self) {
+        self.extreme.set(self.source.get());
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Changeable for SeenExtremeBinding<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.extreme.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.extreme.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.extreme.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.extreme.binding_id()
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Bound<Value> for SeenExtremeBinding<Value> {
+    fn get(&self) -> Value {
+        self.extreme.get()
+    }
+}
+
+///
+/// Creates a binding to the smallest value that another binding has had
+///
+pub fn min_seen_binding<Value>(binding: BindRef<Value>) -> SeenExtremeBinding<Value>
+where Value: 'static+Clone+Ord+Send {
+    SeenExtremeBinding::new(binding, |new_value, min_value| new_value < min_value)
+}
+
+///
+/// Creates a binding to the largest value that another binding has had
+///
+pub fn max_seen_binding<Value>(binding: BindRef<Value>) -> SeenExtremeBinding<Value>
+where Value: 'static+Clone+Ord+Send {
+    SeenExtremeBinding::new(binding, |new_value, max_value| new_value > max_value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn track_min_and_max() {
+        let reading = bind(5);
+        let min     = min_seen_binding(BindRef::new(&reading));
+        let max     = max_seen_binding(BindRef::new(&reading));
+
+        assert!(min.get() == 5);
+        assert!(max.get() == 5);
+
+        reading.set(3);
+        reading.set(8);
+        reading.set(6);
+
+        assert!(min.get() == 3);
+        assert!(max.get() == 8);
+    }
+
+    #[test]
+    fn only_notifies_on_new_extreme() {
+        let reading     = bind(5);
+        let max         = max_seen_binding(BindRef::new(&reading));
+        let changed     = bind(false);
+
+        let notify_changed = changed.clone();
+        max.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        reading.set(4);
+        assert!(!changed.get());
+
+        reading.set(7);
+        assert!(changed.get());
+    }
+
+    #[test]
+    fn reset_forgets_seen_values() {
+        let reading = bind(5);
+        let max     = max_seen_binding(BindRef::new(&reading));
+
+        reading.set(10);
+        reading.set(2);
+        assert!(max.get() == 10);
+
+        max.reset();
+        assert!(max.get() == 2);
+
+        reading.set(4);
+        assert!(max.get() == 4);
+    }
+}
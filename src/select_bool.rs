@@ -0,0 +1,80 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+use super::sync::*;
+
+///
+/// Creates a binding that has the value of `if_true` when `condition` is true, and the value of `if_false` otherwise
+///
+/// Only the condition and the binding that is currently selected are dependencies of the result, so changes to the other
+/// binding don't cause any notifications. When the condition changes, the result follows the newly selected binding.
+///
+pub fn select_bool<Value>(condition: BindRef<bool>, if_true: BindRef<Value>, if_false: BindRef<Value>) -> BindRef<Value>
+where
+Value: 'static+Clone+Send {
+    let selected = ComputedBinding::new(move || {
+        if condition.get() {
+            if_true.get()
+        } else {
+            if_false.get()
+        }
+    });
+
+    BindRef::from_arc(Arc::new(selected))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn follows_selected_binding() {
+        let condition   = bind(true);
+        let a           = bind(1);
+        let b           = bind(2);
+        let selected    = select_bool(BindRef::new(&condition), BindRef::new(&a), BindRef::new(&b));
+
+        assert!(selected.get() == 1);
+
+        condition.set(false);
+        assert!(selected.get() == 2);
+
+        b.set(3);
+        assert!(selected.get() == 3);
+    }
+
+    #[test]
+    fn inactive_binding_does_not_notify() {
+        let condition   = bind(true);
+        let a           = bind(1);
+        let b           = bind(2);
+        let selected    = select_bool(BindRef::new(&condition), BindRef::new(&a), BindRef::new(&b));
+        let changed     = bind(false);
+
+        let notify_changed = changed.clone();
+        selected.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+        assert!(selected.get() == 1);
+
+        b.set(3);
+        assert!(!changed.get());
+
+        a.set(4);
+        assert!(changed.get());
+        assert!(selected.get() == 4);
+
+        // After flipping the condition, the other binding drives the updates
+        changed.set(false);
+        condition.set(false);
+        assert!(changed.get());
+        assert!(selected.get() == 3);
+
+        changed.set(false);
+        a.set(5);
+        assert!(!changed.get());
+
+        b.set(6);
+        assert!(changed.get());
+        assert!(selected.get() == 6);
+    }
+}
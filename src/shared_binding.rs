@@ -0,0 +1,160 @@
+use super::traits::*;
+use super::binding::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use std::sync::*;
+
+///
+/// A binding whose value is stored in an `Arc<Mutex<Value>>` that can also be used by code that doesn't use bindings
+///
+/// Changes made through the binding notify anything that depends on it. The value can also be changed by locking the
+/// mutex directly, but the binding can't detect this, so `notify_changed()` must be called afterwards to announce the
+/// change.
+///
+#[derive(Clone)]
+pub struct SharedBinding<Value> {
+    /// The shared value
+    value: Arc<Mutex<Value>>,
+
+    /// Changed whenever the value changes, used to track dependencies and send notifications
+    changed: Binding<u64>,
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> SharedBinding<Value> {
+    ///
+    /// Creates a new binding backed by an existing shared value
+    ///
+    pub fn new(value: Arc<Mutex<Value>>) -> SharedBinding<Value> {
+        SharedBinding {
+            value,
+            changed:    Binding::new(0),
+        }
+    }
+
+    ///
+    /// Indicates that the shared value has been changed outside of this binding, notifying anything that depends on it
+    ///
+    pub fn notify_changed(&self) {
+        self.changed.with_mut(|generation| {
+            *generation += 1;
+            true
+        });
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Changeable for SharedBinding<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.changed.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.changed.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.changed.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.changed.binding_id()
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Bound<Value> for SharedBinding<Value> {
+    fn get(&self) -> Value {
+        // Reading the change counter adds this binding as a dependency of any computed binding that's being calculated
+        self.changed.get();
+
+        self.value.lock().unwrap().clone()
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> MutableBound<Value> for SharedBinding<Value> {
+    fn set(&self, new_value: Value) {
+        self.with_mut(move |value| {
+            if *value != new_value {
+                *value = new_value;
+                true
+            } else {
+                false
+            }
+        });
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> WithBound<Value> for SharedBinding<Value> {
+    fn with_ref<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce(&Value) -> T,
+    {
+        self.changed.get();
+
+        f(&*self.value.lock().unwrap())
+    }
+
+    fn with_mut<F>(&self, f: F)
+    where
+        F: FnOnce(&mut Value) -> bool,
+    {
+        // The value is unlocked before sending notifications, so they can read the new value
+        let changed = f(&mut *self.value.lock().unwrap());
+
+        if changed {
+            self.notify_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    use std::sync::*;
+
+    #[test]
+    fn reads_shared_value() {
+        let shared  = Arc::new(Mutex::new(1));
+        let bound   = bind_shared(Arc::clone(&shared));
+
+        assert!(bound.get() == 1);
+
+        bound.set(2);
+        assert!(*shared.lock().unwrap() == 2);
+    }
+
+    #[test]
+    fn computed_follows_external_changes() {
+        let shared  = Arc::new(Mutex::new(1));
+        let bound   = bind_shared(Arc::clone(&shared));
+
+        let computed_bound  = bound.clone();
+        let doubled         = computed(move || computed_bound.get() * 2);
+
+        assert!(doubled.get() == 2);
+
+        // Changes made directly to the shared value are picked up once they're announced
+        *shared.lock().unwrap() = 3;
+        bound.notify_changed();
+        assert!(doubled.get() == 6);
+
+        bound.set(4);
+        assert!(doubled.get() == 8);
+    }
+
+    #[test]
+    fn setting_same_value_does_not_notify() {
+        let bound   = bind_shared(Arc::new(Mutex::new(1)));
+        let changed = bind(false);
+
+        let notify_changed = changed.clone();
+        bound.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        bound.set(1);
+        assert!(!changed.get());
+
+        bound.set(2);
+        assert!(changed.get());
+    }
+}
@@ -0,0 +1,55 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+
+use alloc::vec::Vec;
+use core::cmp::{Ordering};
+
+///
+/// Creates a binding that contains the items from another binding sorted using a comparison function
+///
+/// The sort is stable, so items that compare as equal stay in the same order as they are in the source binding. The
+/// sorted list is recalculated whenever the source binding changes.
+///
+pub fn sorted_binding<Item, TCompareFn>(source: BindRef<Vec<Item>>, compare: TCompareFn) -> BindRef<Vec<Item>>
+where
+Item:       'static+Clone+Send+PartialEq,
+TCompareFn: 'static+Send+Sync+Fn(&Item, &Item) -> Ordering {
+    let sorted = ComputedBinding::new(move || {
+        let mut items = source.get();
+        items.sort_by(&compare);
+
+        items
+    });
+
+    BindRef::from(sorted)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn sorts_items() {
+        let scores  = bind(vec![3, 1, 2]);
+        let sorted  = sorted_binding(BindRef::new(&scores), |a: &i32, b: &i32| a.cmp(b));
+
+        assert!(sorted.get() == vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn follows_changes() {
+        let scores  = bind(vec![("a", 3), ("b", 1), ("c", 2)]);
+        let sorted  = sorted_binding(BindRef::new(&scores), |a: &(&str, i32), b: &(&str, i32)| b.1.cmp(&a.1));
+
+        assert!(sorted.get() == vec![("a", 3), ("c", 2), ("b", 1)]);
+
+        scores.set(vec![("a", 3), ("b", 5), ("c", 2), ("d", 3)]);
+
+        // Items with the same score stay in the order they're in the source
+        assert!(sorted.get() == vec![("b", 5), ("a", 3), ("d", 3), ("c", 2)]);
+    }
+}
@@ -0,0 +1,108 @@
+use super::traits::*;
+#[cfg(any(feature = "std", not(feature = "spin")))]
+use super::binding::*;
+use super::bindref::*;
+use super::computed::*;
+use super::notify_fn::*;
+use super::binding_context::*;
+use super::sync::*;
+
+use alloc::boxed::Box;
+use core::mem;
+
+///
+/// A notification attached to a source binding, which owns the source binding
+///
+/// Many of the binding combinators work by attaching a notification to a source binding that updates some state whenever
+/// the source changes. The source owns its notifications, so the notification itself only holds a weak reference to the
+/// source: otherwise the two would keep each other alive forever. Instead, this holds the strong reference to the source,
+/// and whatever owns the monitor decides how long the source is followed for. The source is read every time it changes,
+/// as computed bindings only notify about their next change once they've been read.
+///
+pub (crate) struct SourceMonitor<TSource> {
+    /// The notification attached to the source (declared first so it's released before the source)
+    releasable: Box<dyn Releasable>,
+
+    /// The binding that's being monitored
+    source: Arc<TSource>,
+}
+
+///
+/// Calls `on_change` with the new value of a source binding whenever it changes, until the returned monitor is released or dropped
+///
+pub (crate) fn watch_source<Value, TSource, TOnChange>(source: TSource, mut on_change: TOnChange) -> SourceMonitor<TSource>
+where
+TSource:    'static+Bound<Value>,
+TOnChange:  'static+Send+FnMut(Value) {
+    let source      = Arc::new(source);
+    let weak_source = Arc::downgrade(&source);
+
+    let releasable  = source.when_changed(notify(move || {
+        let source      = if let Some(source) = weak_source.upgrade() { source } else { return; };
+        let new_value   = BindingContext::out_of_context(|| source.get());
+
+        on_change(new_value);
+    }));
+
+    SourceMonitor { releasable, source }
+}
+
+///
+/// Creates a binding that's derived from a source binding by a function that's called whenever the source changes
+///
+/// `on_change` is called with each new value of the source, and usually updates a binding, which `read_fn` then reads. The
+/// source binding is followed for as long as the returned binding exists.
+///
+pub (crate) fn bind_to_source<Value, TSource, TOnChange, Result, TReadFn>(source: TSource, on_change: TOnChange, read_fn: TReadFn) -> BindRef<Result>
+where
+TSource:    'static+Bound<Value>,
+TOnChange:  'static+Send+FnMut(Value),
+Result:     'static+Clone+Send+PartialEq,
+TReadFn:    'static+Send+Sync+Fn() -> Result {
+    let monitor = Mutex::new(watch_source(source, on_change));
+
+    BindRef::from(ComputedBinding::new(move || {
+        // The source binding and its notification are released when the computed binding is dropped
+        let _monitor = &monitor;
+
+        read_fn()
+    }))
+}
+
+///
+/// Creates a binding that's updated from some state that follows the changes to a source binding
+///
+/// `update_fn` is called with the state locked whenever the source changes, and returns the new value for the binding, or
+/// `None` to leave it as it is. The binding is set once the state is unlocked, so anything it notifies can read it straight away.
+///
+#[cfg(any(feature = "std", not(feature = "spin")))]
+pub (crate) fn bind_to_source_with_state<Value, TSource, State, Result, TUpdateFn>(source: TSource, state: State, initial_value: Result, mut update_fn: TUpdateFn) -> BindRef<Result>
+where
+TSource:    'static+Bound<Value>,
+State:      'static+Send,
+Result:     'static+Clone+Send+PartialEq,
+TUpdateFn:  'static+Send+FnMut(&mut State, Value) -> Option<Result> {
+    let state       = Mutex::new(state);
+    let result      = Binding::new(initial_value);
+    let set_result  = result.clone();
+
+    bind_to_source(source, move |new_value: Value| {
+        let new_result = update_fn(&mut *state.lock().unwrap(), new_value);
+
+        if let Some(new_result) = new_result {
+            set_result.set(new_result);
+        }
+    }, move || result.get())
+}
+
+impl<TSource: 'static+Send+Sync> Releasable for SourceMonitor<TSource> {
+    fn keep_alive(&mut self) {
+        // The notification only has a weak reference to the source, so it needs to be kept alive too
+        self.releasable.keep_alive();
+        mem::forget(Arc::clone(&self.source));
+    }
+
+    fn done(&mut self) {
+        self.releasable.done();
+    }
+}
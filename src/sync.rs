@@ -0,0 +1,52 @@
+//!
+//! The synchronisation primitives used by the bindings
+//!
+//! Normally these are the types from `std::sync`. When the `spin` feature is on and the `std` feature is off, `Arc` and
+//! `Weak` come from `alloc` and the mutex is a spin lock from the `spin` crate, so the core bindings can be used on targets
+//! without the standard library.
+//!
+
+#[cfg(any(feature = "std", not(feature = "spin")))]
+pub (crate) use std::sync::{Arc, Weak, Mutex};
+
+#[cfg(all(feature = "spin", not(feature = "std")))]
+pub (crate) use alloc::sync::{Arc, Weak};
+
+#[cfg(all(feature = "spin", not(feature = "std")))]
+pub (crate) use self::spin_mutex::*;
+
+#[cfg(all(feature = "spin", not(feature = "std")))]
+mod spin_mutex {
+    use core::fmt;
+    use core::convert::{Infallible};
+
+    ///
+    /// A spin lock with the same interface as `std::sync::Mutex` (spin locks can't be poisoned, so locking never fails)
+    ///
+    #[derive(Default)]
+    pub struct Mutex<T: ?Sized>(spin::Mutex<T>);
+
+    impl<T> Mutex<T> {
+        ///
+        /// Creates a new mutex containing a value
+        ///
+        pub const fn new(value: T) -> Mutex<T> {
+            Mutex(spin::Mutex::new(value))
+        }
+    }
+
+    impl<T: ?Sized> Mutex<T> {
+        ///
+        /// Locks the mutex, waiting for it to become available
+        ///
+        pub fn lock(&self) -> Result<spin::MutexGuard<'_, T>, Infallible> {
+            Ok(self.0.lock())
+        }
+    }
+
+    impl<T: ?Sized+fmt::Debug> fmt::Debug for Mutex<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use super::traits::*;
+use super::binding::*;
+use super::bindref::*;
+use super::computed::*;
+use super::source_monitor::*;
+
+use super::timer::*;
+
+use std::sync::*;
+use std::time::{Duration, Instant};
+
+///
+/// Updates the value of a `time_since_change()` binding on a timer
+///
+struct ElapsedTicker {
+    /// When the trigger binding last changed
+    last_change: Mutex<Instant>,
+
+    /// The time since the last change, as of the most recent tick
+    elapsed: Binding<Duration>,
+
+    /// The time between ticks
+    tick_interval: Duration,
+
+    /// The timer that triggers the ticks
+    timer: Arc<dyn Timer>,
+}
+
+impl ElapsedTicker {
+    ///
+    /// Updates the elapsed time, then schedules the next tick, as long as the ticker is still in use
+    ///
+    fn tick(ticker: Weak<ElapsedTicker>) {
+        let ticker = if let Some(ticker) = ticker.upgrade() { ticker } else { return; };
+
+        let since = ticker.timer.now().duration_since(*ticker.last_change.lock().unwrap());
+        ticker.elapsed.set(since);
+
+        let next_tick   = ticker.timer.now() + ticker.tick_interval;
+        let weak        = Arc::downgrade(&ticker);
+        ticker.timer.schedule(next_tick, Box::new(move || Self::tick(weak)));
+    }
+}
+
+///
+/// Creates a binding that measures the time since another binding last changed
+///
+/// The value is reset to zero whenever the trigger binding changes, and is updated to the time elapsed since the
+/// most recent change every `tick_interval` (or every millisecond, if the interval is shorter than that). The updates are
+/// made by the timer that's shared with the other timed bindings, and stop once the returned binding is dropped.
+///
+pub fn time_since_change<Value>(trigger: BindRef<Value>, tick_interval: Duration) -> BindRef<Duration>
+where Value: 'static+Send {
+    time_since_change_with_timer(trigger, tick_interval, shared_timer())
+}
+
+///
+/// Creates a `time_since_change()` binding that is updated on the specified timer
+///
+fn time_since_change_with_timer<Value>(trigger: BindRef<Value>, tick_interval: Duration, timer: Arc<dyn Timer>) -> BindRef<Duration>
+where Value: 'static+Send {
+    let tick_interval   = tick_interval.max(MIN_TICK_INTERVAL);
+    let elapsed         = Binding::new(Duration::default());
+    let ticker          = Arc::new(ElapsedTicker {
+        last_change:    Mutex::new(timer.now()),
+        elapsed:        elapsed.clone(),
+        tick_interval,
+        timer,
+    });
+
+    // Reset the elapsed time whenever the trigger changes
+    let reset_ticker    = Arc::downgrade(&ticker);
+    let change_monitor  = watch_source(trigger, move |_: Value| {
+        if let Some(ticker) = reset_ticker.upgrade() {
+            *ticker.last_change.lock().unwrap() = ticker.timer.now();
+            ticker.elapsed.set(Duration::default());
+        }
+    });
+    let change_monitor  = Mutex::new(change_monitor);
+
+    // The first tick is after one interval, then the ticker keeps updating the elapsed time until it's dropped
+    let first_tick      = ticker.timer.now() + tick_interval;
+    let weak_ticker     = Arc::downgrade(&ticker);
+    ticker.timer.schedule(first_tick, Box::new(move || ElapsedTicker::tick(weak_ticker)));
+
+    let time_since      = ComputedBinding::new(move || {
+        // The trigger, its change monitor and the ticker are released when the binding is dropped
+        let _change_monitor = &change_monitor;
+        let _ticker         = &ticker;
+
+        elapsed.get()
+    });
+
+    BindRef::from(time_since)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn elapsed_time_increases_on_each_tick() {
+        let timer       = ManualTimer::new();
+        let trigger     = bind(0);
+        let time_since  = time_since_change_with_timer(BindRef::new(&trigger), Duration::from_millis(10), timer.clone());
+
+        assert!(time_since.get() == Duration::default());
+
+        timer.advance(Duration::from_millis(100));
+        assert!(time_since.get() == Duration::from_millis(100));
+
+        // The value only changes when the timer ticks
+        timer.advance(Duration::from_millis(5));
+        assert!(time_since.get() == Duration::from_millis(100));
+
+        timer.advance(Duration::from_millis(5));
+        assert!(time_since.get() == Duration::from_millis(110));
+    }
+
+    #[test]
+    fn elapsed_time_resets_when_trigger_changes() {
+        let timer       = ManualTimer::new();
+        let trigger     = bind(0);
+        let time_since  = time_since_change_with_timer(BindRef::new(&trigger), Duration::from_millis(10), timer.clone());
+
+        timer.advance(Duration::from_millis(200));
+        assert!(time_since.get() == Duration::from_millis(200));
+
+        trigger.set(1);
+        assert!(time_since.get() == Duration::default());
+
+        // The elapsed time is measured from the change
+        timer.advance(Duration::from_millis(25));
+        assert!(time_since.get() == Duration::from_millis(20));
+    }
+
+    #[test]
+    fn zero_interval_does_not_stop_the_timer() {
+        let timer       = ManualTimer::new();
+        let trigger     = bind(0);
+        let time_since  = time_since_change_with_timer(BindRef::new(&trigger), Duration::ZERO, timer.clone());
+
+        // Advancing the timer finishes, and the elapsed time is updated every millisecond
+        timer.advance(Duration::from_millis(10));
+        assert!(time_since.get() == Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,245 @@
+use std::thread;
+use std::sync::*;
+use std::panic::{self, AssertUnwindSafe};
+use std::collections::{BTreeMap};
+use std::time::{Duration, Instant};
+
+///
+/// The shortest time between the ticks of a binding that repeatedly updates on a timer (shorter intervals are
+/// lengthened to this, so that a zero interval doesn't keep the timer busy forever)
+///
+pub (crate) const MIN_TICK_INTERVAL: Duration = Duration::from_millis(1);
+
+///
+/// Runs actions at a later time on behalf of the bindings that need to update on a timer
+///
+pub (crate) trait Timer : Send+Sync {
+    ///
+    /// Returns the current time according to this timer
+    ///
+    fn now(&self) -> Instant;
+
+    ///
+    /// Runs an action once the specified time has been reached
+    ///
+    fn schedule(&self, when: Instant, action: Box<dyn Send+FnOnce()>);
+}
+
+///
+/// The actions waiting to be run by a timer, in the order that they should run
+///
+#[derive(Default)]
+struct TimerQueue {
+    /// The actions that are waiting to run, ordered by when they should run and then by when they were scheduled
+    actions: BTreeMap<(Instant, u64), Box<dyn Send+FnOnce()>>,
+
+    /// The identifier to assign to the next action
+    next_id: u64,
+}
+
+///
+/// A timer that runs its actions on a background thread
+///
+#[derive(Default)]
+struct ThreadTimer {
+    /// The actions waiting to run
+    queue: Mutex<TimerQueue>,
+
+    /// Signalled when a new action is scheduled
+    scheduled: Condvar,
+}
+
+impl TimerQueue {
+    ///
+    /// Adds an action to this queue
+    ///
+    fn push(&mut self, when: Instant, action: Box<dyn Send+FnOnce()>) {
+        let id          = self.next_id;
+        self.next_id    += 1;
+
+        self.actions.insert((when, id), action);
+    }
+
+    ///
+    /// Removes the first action that's due to run at or before the specified time
+    ///
+    fn pop_due(&mut self, now: Instant) -> Option<(Instant, Box<dyn Send+FnOnce()>)> {
+        let key = *self.actions.keys().next()?;
+
+        if key.0 <= now {
+            self.actions.remove(&key).map(|action| (key.0, action))
+        } else {
+            None
+        }
+    }
+}
+
+impl ThreadTimer {
+    ///
+    /// Runs the actions for this timer as they become due (never returns)
+    ///
+    fn run(&self) {
+        let mut queue = self.queue.lock().unwrap();
+
+        loop {
+            let now = Instant::now();
+
+            if let Some((_, action)) = queue.pop_due(now) {
+                // Actions can schedule further actions, so the queue is unlocked while they run
+                drop(queue);
+
+                // This thread runs the actions for every binding in the process, so a panicking action must not stop it
+                let _ = panic::catch_unwind(AssertUnwindSafe(action));
+
+                queue = self.queue.lock().unwrap();
+            } else if let Some((when, _)) = queue.actions.keys().next() {
+                let wait_time   = *when - now;
+                queue           = self.scheduled.wait_timeout(queue, wait_time).unwrap().0;
+            } else {
+                queue           = self.scheduled.wait(queue).unwrap();
+            }
+        }
+    }
+}
+
+impl Timer for ThreadTimer {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn schedule(&self, when: Instant, action: Box<dyn Send+FnOnce()>) {
+        self.queue.lock().unwrap().push(when, action);
+        self.scheduled.notify_one();
+    }
+}
+
+///
+/// Returns the timer shared by every binding, which runs its actions on a single background thread
+///
+pub (crate) fn shared_timer() -> Arc<dyn Timer> {
+    static SHARED_TIMER: Mutex<Option<Arc<ThreadTimer>>> = Mutex::new(None);
+
+    let mut shared_timer    = SHARED_TIMER.lock().unwrap();
+    let timer               = shared_timer.get_or_insert_with(|| {
+        let timer       = Arc::new(ThreadTimer::default());
+        let run_timer   = Arc::clone(&timer);
+
+        thread::Builder::new()
+            .name("flo_binding timer".to_string())
+            .spawn(move || run_timer.run())
+            .unwrap();
+
+        timer
+    });
+
+    Arc::clone(timer) as Arc<dyn Timer>
+}
+
+///
+/// A timer whose time only moves when `advance()` is called, for testing bindings that update on a timer
+///
+#[cfg(test)]
+pub (crate) struct ManualTimer {
+    /// The current time according to this timer
+    now: Mutex<Instant>,
+
+    /// The actions waiting to run
+    queue: Mutex<TimerQueue>,
+}
+
+#[cfg(test)]
+impl ManualTimer {
+    ///
+    /// Creates a new manual timer, starting at the current time
+    ///
+    pub (crate) fn new() -> Arc<ManualTimer> {
+        Arc::new(ManualTimer {
+            now:    Mutex::new(Instant::now()),
+            queue:  Mutex::new(TimerQueue::default()),
+        })
+    }
+
+    ///
+    /// Moves the time forward, running any actions that become due in the order they're scheduled for
+    ///
+    pub (crate) fn advance(&self, duration: Duration) {
+        let target = *self.now.lock().unwrap() + duration;
+
+        loop {
+            let next_action = self.queue.lock().unwrap().pop_due(target);
+            let (when, action) = if let Some(next_action) = next_action { next_action } else { break; };
+
+            {
+                let mut now = self.now.lock().unwrap();
+                *now        = (*now).max(when);
+            }
+
+            action();
+        }
+
+        *self.now.lock().unwrap() = target;
+    }
+}
+
+#[cfg(test)]
+impl Timer for ManualTimer {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+
+    fn schedule(&self, when: Instant, action: Box<dyn Send+FnOnce()>) {
+        self.queue.lock().unwrap().push(when, action);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shared_timer_runs_actions_in_order() {
+        let timer   = shared_timer();
+        let order   = Arc::new(Mutex::new(vec![]));
+        let (tx, rx) = mpsc::channel();
+
+        let now     = timer.now();
+        let second  = Arc::clone(&order);
+        let tx      = Mutex::new(tx);
+        timer.schedule(now + Duration::from_millis(20), Box::new(move || { second.lock().unwrap().push(2); tx.lock().unwrap().send(()).unwrap(); }));
+
+        let first   = Arc::clone(&order);
+        timer.schedule(now + Duration::from_millis(10), Box::new(move || first.lock().unwrap().push(1)));
+
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(*order.lock().unwrap() == vec![1, 2]);
+    }
+
+    #[test]
+    fn shared_timer_continues_after_panicking_action() {
+        let timer       = shared_timer();
+        let (tx, rx)    = mpsc::channel();
+
+        let now         = timer.now();
+        let tx          = Mutex::new(tx);
+        timer.schedule(now + Duration::from_millis(10), Box::new(|| panic!("Action panicked")));
+        timer.schedule(now + Duration::from_millis(20), Box::new(move || tx.lock().unwrap().send(()).unwrap()));
+
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn manual_timer_runs_due_actions() {
+        let timer   = ManualTimer::new();
+        let count   = Arc::new(Mutex::new(0));
+
+        let now     = timer.now();
+        let counter = Arc::clone(&count);
+        timer.schedule(now + Duration::from_millis(10), Box::new(move || *counter.lock().unwrap() += 1));
+
+        timer.advance(Duration::from_millis(5));
+        assert!(*count.lock().unwrap() == 0);
+
+        timer.advance(Duration::from_millis(5));
+        assert!(*count.lock().unwrap() == 1);
+    }
+}
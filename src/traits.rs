@@ -1,4 +1,8 @@
-use std::sync::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use alloc::boxed::Box;
 
 ///
 /// Trait implemented by items with dependencies that need to be notified when they have changed
@@ -43,6 +47,55 @@ pub trait Changeable {
     /// (if the event never seems to fire, this is likely to be the problem)
     ///
     fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable>;
+
+    ///
+    /// Removes any notifications that have been released from this item
+    ///
+    /// Released notifications are normally tidied up the next time a notification is added or
+    /// the item changes. This can be called to tidy them up immediately.
+    ///
+    fn release_unused_notifications(&self) { }
+
+    ///
+    /// Returns a value that identifies the shared state of this item, if it has one
+    ///
+    /// Clones of the same binding return the same identity, which is used to avoid subscribing to the same binding
+    /// more than once when it's read several times while calculating a computed value.
+    ///
+    fn identity(&self) -> Option<usize> { None }
+
+    ///
+    /// Returns the ID of this item in the binding graph, if it has one
+    ///
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> { None }
+}
+
+///
+/// References to changeable items can be subscribed to directly, so generic code that only needs to observe an
+/// item can borrow it instead of taking ownership or cloning it
+///
+impl<T: ?Sized+Changeable> Changeable for &T {
+    #[inline]
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        (**self).when_changed(what)
+    }
+
+    #[inline]
+    fn release_unused_notifications(&self) {
+        (**self).release_unused_notifications()
+    }
+
+    #[inline]
+    fn identity(&self) -> Option<usize> {
+        (**self).identity()
+    }
+
+    #[cfg(feature = "graph")]
+    #[inline]
+    fn binding_id(&self) -> Option<BindingId> {
+        (**self).binding_id()
+    }
 }
 
 ///
@@ -53,6 +106,15 @@ pub trait Bound<Value> : Changeable+Send+Sync {
     /// Retrieves the value stored by this binding
     ///
     fn get(&self) -> Value;
+
+    ///
+    /// Returns the name of the type of value stored by this binding
+    ///
+    /// This is intended for diagnostics, such as labelling the nodes in a visualisation of a dependency graph.
+    ///
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<Value>()
+    }
 }
 
 ///
@@ -0,0 +1,105 @@
+use super::traits::*;
+use super::bindref::*;
+use super::source_monitor::*;
+
+use std::mem;
+use std::sync::*;
+
+///
+/// Supplies a function to be notified when a binding changes from a value in `from_values` to a value in `to_values`
+///
+/// Unlike `when_changed()`, this is edge-triggered: the previous value of the binding is tracked so the notification
+/// only fires for a change that moves between the two sets of values. For example, this can be used to react to a
+/// state machine entering an error state from one of its working states.
+///
+/// As with `when_changed()`, the notification stops once the returned `Releasable` is released or dropped.
+///
+pub fn on_transition<Value, FromValues, ToValues>(binding: BindRef<Value>, from_values: FromValues, to_values: ToValues, what: Arc<dyn Notifiable>) -> Box<dyn Releasable>
+where
+Value:      'static+Clone+Send+PartialEq,
+FromValues: IntoIterator<Item=Value>,
+ToValues:   IntoIterator<Item=Value> {
+    let from_values = from_values.into_iter().collect::<Vec<_>>();
+    let to_values   = to_values.into_iter().collect::<Vec<_>>();
+    let last_value  = Mutex::new(binding.get());
+
+    Box::new(watch_source(binding, move |new_value: Value| {
+        let old_value   = mem::replace(&mut *last_value.lock().unwrap(), new_value.clone());
+
+        if old_value != new_value && from_values.contains(&old_value) && to_values.contains(&new_value) {
+            what.mark_as_changed();
+        }
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    enum State {
+        Idle,
+        Loading,
+        Ready,
+        Error,
+    }
+
+    #[test]
+    fn notify_on_transition() {
+        let state       = bind(State::Idle);
+        let errors      = bind(0);
+
+        let notify_errors   = errors.clone();
+        let _transition     = on_transition(BindRef::from(&state), vec![State::Loading, State::Ready], vec![State::Error], notify(move || notify_errors.set(notify_errors.get()+1)));
+
+        // Idle is not in the 'from' set
+        state.set(State::Error);
+        assert!(errors.get() == 0);
+
+        state.set(State::Loading);
+        state.set(State::Ready);
+        assert!(errors.get() == 0);
+
+        state.set(State::Error);
+        assert!(errors.get() == 1);
+
+        state.set(State::Loading);
+        state.set(State::Error);
+        assert!(errors.get() == 2);
+    }
+
+    #[test]
+    fn no_notifications_once_released() {
+        let state       = bind(State::Loading);
+        let errors      = bind(0);
+
+        let notify_errors   = errors.clone();
+        let mut transition  = on_transition(BindRef::from(&state), vec![State::Loading], vec![State::Error], notify(move || notify_errors.set(notify_errors.get()+1)));
+
+        transition.done();
+
+        state.set(State::Error);
+        assert!(errors.get() == 0);
+    }
+
+    #[test]
+    fn follow_transitions_from_computed_binding() {
+        let count       = bind(0);
+        let count_copy  = count.clone();
+        let is_large    = BindRef::from(computed(move || count_copy.get() > 10));
+        let crossings   = bind(0);
+
+        let notify_crossings    = crossings.clone();
+        let _transition         = on_transition(is_large, vec![false], vec![true], notify(move || notify_crossings.set(notify_crossings.get()+1)));
+
+        count.set(5);
+        count.set(20);
+        count.set(30);
+        assert!(crossings.get() == 1);
+
+        count.set(0);
+        count.set(11);
+        assert!(crossings.get() == 2);
+    }
+}
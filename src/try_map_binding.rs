@@ -0,0 +1,70 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+use super::sync::*;
+
+///
+/// Creates a binding that maps the value of another binding using a function that can fail
+///
+/// When the mapping function returns an error, the binding keeps the last value that was mapped successfully (or the
+/// initial value if there hasn't been one yet). This is useful for things like parsing, where an input that is briefly
+/// invalid while it's being edited shouldn't replace a value that's derived from it.
+///
+pub fn try_map_binding<Value, Mapped, Error, TFn>(binding: BindRef<Value>, initial_value: Mapped, map_fn: TFn) -> BindRef<Mapped>
+where
+Value:  'static+Clone+Send,
+Mapped: 'static+Clone+Send+PartialEq,
+TFn:    'static+Send+Sync+Fn(&Value) -> Result<Mapped, Error> {
+    // The last value that was successfully mapped
+    let last_mapped = Mutex::new(initial_value);
+
+    let mapped      = ComputedBinding::new(move || {
+        let value           = binding.get();
+        let mut last_mapped = last_mapped.lock().unwrap();
+
+        if let Ok(new_value) = map_fn(&value) {
+            *last_mapped = new_value;
+        }
+
+        last_mapped.clone()
+    });
+
+    BindRef::from(mapped)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use alloc::string::{String, ToString};
+
+    #[test]
+    fn maps_valid_values() {
+        let text    = bind("42".to_string());
+        let number  = try_map_binding(BindRef::new(&text), 0, |text: &String| text.parse::<i32>());
+
+        assert!(number.get() == 42);
+
+        text.set("43".to_string());
+        assert!(number.get() == 43);
+    }
+
+    #[test]
+    fn keeps_last_value_on_error() {
+        let text    = bind("not a number".to_string());
+        let number  = try_map_binding(BindRef::new(&text), 0, |text: &String| text.parse::<i32>());
+
+        // The initial value is used until a value is mapped successfully
+        assert!(number.get() == 0);
+
+        text.set("12".to_string());
+        assert!(number.get() == 12);
+
+        text.set("12a".to_string());
+        assert!(number.get() == 12);
+
+        text.set("123".to_string());
+        assert!(number.get() == 123);
+    }
+}
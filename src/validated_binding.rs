@@ -0,0 +1,116 @@
+use super::traits::*;
+use super::binding::*;
+use super::sync::*;
+#[cfg(feature = "graph")]
+use super::binding_graph::*;
+
+use alloc::boxed::Box;
+
+///
+/// A binding that passes any new value through a validation function before storing it
+///
+/// The validation function can transform the value (for example, by clamping it to a range), so anything reading
+/// from this binding will only ever see values that have been validated.
+///
+#[derive(Clone)]
+pub struct ValidatedBinding<Value> {
+    /// The binding where the validated value is stored
+    binding: Binding<Value>,
+
+    /// The function used to validate values before they're stored
+    validate: Arc<dyn Fn(Value) -> Value+Send+Sync>
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> ValidatedBinding<Value> {
+    ///
+    /// Creates a new validated binding. The initial value is also validated.
+    ///
+    pub fn new<TFn>(initial_value: Value, validate: TFn) -> ValidatedBinding<Value>
+    where TFn: 'static+Send+Sync+Fn(Value) -> Value {
+        let initial_value = validate(initial_value);
+
+        ValidatedBinding {
+            binding:    Binding::new(initial_value),
+            validate:   Arc::new(validate)
+        }
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Changeable for ValidatedBinding<Value> {
+    fn when_changed(&self, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+        self.binding.when_changed(what)
+    }
+
+    fn release_unused_notifications(&self) {
+        self.binding.release_unused_notifications()
+    }
+
+    fn identity(&self) -> Option<usize> {
+        self.binding.identity()
+    }
+
+    #[cfg(feature = "graph")]
+    fn binding_id(&self) -> Option<BindingId> {
+        self.binding.binding_id()
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> Bound<Value> for ValidatedBinding<Value> {
+    fn get(&self) -> Value {
+        self.binding.get()
+    }
+}
+
+impl<Value: 'static+Clone+PartialEq+Send> MutableBound<Value> for ValidatedBinding<Value> {
+    fn set(&self, new_value: Value) {
+        self.binding.set((self.validate)(new_value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn clamps_initial_value() {
+        let volume = bind_clamped(150, 0..=100);
+
+        assert!(volume.get() == 100);
+    }
+
+    #[test]
+    fn clamps_set_values() {
+        let volume = bind_clamped(50, 0..=100);
+
+        volume.set(-10);
+        assert!(volume.get() == 0);
+
+        volume.set(110);
+        assert!(volume.get() == 100);
+
+        volume.set(20);
+        assert!(volume.get() == 20);
+    }
+
+    #[test]
+    fn validates_set_values() {
+        let even = bind_validated(1, |value| value & !1);
+
+        assert!(even.get() == 0);
+
+        even.set(5);
+        assert!(even.get() == 4);
+    }
+
+    #[test]
+    fn no_notification_if_validated_value_is_unchanged() {
+        let volume          = bind_clamped(100, 0..=100);
+        let changed         = bind(false);
+
+        let notify_changed  = changed.clone();
+        volume.when_changed(notify(move || notify_changed.set(true))).keep_alive();
+
+        volume.set(120);
+        assert!(!changed.get());
+    }
+}
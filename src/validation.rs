@@ -0,0 +1,95 @@
+use super::traits::*;
+use super::bindref::*;
+use super::computed::*;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::string::String;
+
+///
+/// The result of validating a value, along with any error messages describing why it's invalid
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ValidationResult {
+    /// True if the value is valid
+    pub valid: bool,
+
+    /// Messages describing why the value is invalid (this can be non-empty for a valid value to report warnings)
+    pub errors: Vec<String>,
+}
+
+impl ValidationResult {
+    ///
+    /// Creates a result for a valid value
+    ///
+    pub fn valid() -> ValidationResult {
+        ValidationResult {
+            valid:  true,
+            errors: vec![]
+        }
+    }
+
+    ///
+    /// Creates a result for an invalid value, with a list of error messages
+    ///
+    pub fn invalid<TErrors: IntoIterator<Item=String>>(errors: TErrors) -> ValidationResult {
+        ValidationResult {
+            valid:  false,
+            errors: errors.into_iter().collect()
+        }
+    }
+}
+
+///
+/// Creates a binding that validates the value of another binding whenever it changes
+///
+/// This is intended for things like forms, where the result can be used to display error messages next to an input. Unlike
+/// `bind_validated()`, the input binding can contain invalid values: the validation function reports what is wrong with
+/// them rather than changing them.
+///
+pub fn validated_binding<Value, TFn>(input: BindRef<Value>, validate: TFn) -> BindRef<ValidationResult>
+where
+Value:  'static+Clone+Send,
+TFn:    'static+Send+Sync+Fn(&Value) -> ValidationResult {
+    let result = ComputedBinding::new(move || validate(&input.get()));
+
+    BindRef::from(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use alloc::string::ToString;
+
+    fn validate_name(name: &str) -> ValidationResult {
+        let mut errors = vec![];
+
+        if name.is_empty()                      { errors.push("Name is required".to_string()); }
+        if name.chars().any(|c| c.is_numeric()) { errors.push("Name cannot contain numbers".to_string()); }
+
+        if errors.is_empty() { ValidationResult::valid() } else { ValidationResult::invalid(errors) }
+    }
+
+    #[test]
+    fn reports_errors() {
+        let name        = bind("".to_string());
+        let validation  = validated_binding(BindRef::new(&name), |name: &String| validate_name(name));
+
+        assert!(validation.get() == ValidationResult::invalid(vec!["Name is required".to_string()]));
+    }
+
+    #[test]
+    fn follows_input_changes() {
+        let name        = bind("".to_string());
+        let validation  = validated_binding(BindRef::new(&name), |name: &String| validate_name(name));
+
+        name.set("Ada".to_string());
+        assert!(validation.get() == ValidationResult::valid());
+
+        name.set("Ada2".to_string());
+        assert!(!validation.get().valid);
+        assert!(validation.get().errors == vec!["Name cannot contain numbers".to_string()]);
+    }
+}
@@ -0,0 +1,104 @@
+use super::traits::*;
+use super::bindref::*;
+use super::binding_context::*;
+use super::notify_fn::*;
+use super::sync::*;
+
+use alloc::boxed::Box;
+
+///
+/// Supplies a function to be notified when a binding changes, but only while another binding is set to true
+///
+/// Changes made while `enable` is false are ignored rather than being delivered later, but the notification stays
+/// registered, so changes made after `enable` becomes true again will notify as normal. As with `when_changed()`, the
+/// notification stops once the returned `Releasable` is released or dropped.
+///
+pub fn when_changed_while<TValue, TBinding>(binding: &TBinding, enable: BindRef<bool>, what: Arc<dyn Notifiable>) -> Box<dyn Releasable>
+where
+TBinding: 'static+Clone+Bound<TValue> {
+    let source = binding.clone();
+
+    let notification = binding.when_changed(notify(move || {
+        // Computed bindings only notify again once they've been read, so the source is read even if the change is suppressed
+        BindingContext::out_of_context(|| source.get());
+
+        if enable.get() {
+            what.mark_as_changed();
+        }
+    }));
+
+    // Read the source once so that a computed binding will notify on its first change
+    BindingContext::out_of_context(|| binding.get());
+
+    notification
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    #[test]
+    fn only_notifies_while_enabled() {
+        let value           = bind(1);
+        let enabled         = bind(true);
+        let change_count    = bind(0);
+
+        let count_copy      = change_count.clone();
+        let _notification   = when_changed_while(&value, BindRef::new(&enabled), notify(move || count_copy.set(count_copy.get() + 1)));
+
+        value.set(2);
+        assert!(change_count.get() == 1);
+
+        // Changes are suppressed while disabled
+        enabled.set(false);
+        value.set(3);
+        value.set(4);
+        assert!(change_count.get() == 1);
+
+        // Re-enabling doesn't deliver the suppressed changes, but later changes notify again
+        enabled.set(true);
+        assert!(change_count.get() == 1);
+
+        value.set(5);
+        assert!(change_count.get() == 2);
+    }
+
+    #[test]
+    fn computed_source_notifies_after_suppressed_change() {
+        let value           = bind(1);
+        let enabled         = bind(true);
+        let change_count    = bind(0);
+
+        let value_copy      = value.clone();
+        let computed        = computed(move || value_copy.get() + 1);
+
+        let count_copy      = change_count.clone();
+        let _notification   = when_changed_while(&computed, BindRef::new(&enabled), notify(move || count_copy.set(count_copy.get() + 1)));
+
+        enabled.set(false);
+        value.set(2);
+        assert!(change_count.get() == 0);
+
+        enabled.set(true);
+        value.set(3);
+        assert!(change_count.get() == 1);
+
+        value.set(4);
+        assert!(change_count.get() == 2);
+    }
+
+    #[test]
+    fn stops_notifying_when_released() {
+        let value           = bind(1);
+        let enabled         = bind(true);
+        let change_count    = bind(0);
+
+        let count_copy      = change_count.clone();
+        let mut notification = when_changed_while(&value, BindRef::new(&enabled), notify(move || count_copy.set(count_copy.get() + 1)));
+
+        notification.done();
+        value.set(2);
+        assert!(change_count.get() == 0);
+    }
+}
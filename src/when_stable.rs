@@ -0,0 +1,182 @@
+use super::traits::*;
+use super::notify_fn::*;
+
+use futures::prelude::*;
+use futures::future;
+use futures::future::{BoxFuture};
+use futures::task::{Poll};
+use ::desync::*;
+
+use std::mem;
+use std::sync::*;
+
+///
+/// Tracks the changes to a binding that's being monitored by `when_stable()`
+///
+struct StableState {
+    /// The number of times the binding has changed
+    change_count: usize,
+
+    /// True if a notification has been scheduled
+    scheduled: bool,
+}
+
+///
+/// Supplies a function to be notified once a binding has stopped changing
+///
+/// Rather than firing immediately like `when_changed()`, the notification is scheduled to run in the background after the
+/// binding changes. The background task yields to the scheduler until it sees that no further changes were made while it
+/// was waiting, so a burst of changes to the binding only generates a single notification, delivered slightly later.
+///
+/// As with `when_changed()`, the notification stops once the returned `Releasable` is released or dropped: any notification
+/// that's still waiting to fire at this point is discarded.
+///
+pub fn when_stable<TBinding: Changeable>(binding: &TBinding, what: Arc<dyn Notifiable>) -> Box<dyn Releasable> {
+    let queue = Desync::new(());
+
+    when_stable_with_scheduler(binding, what, move |task| queue.future_desync(move |_| task).detach())
+}
+
+///
+/// Implements `when_stable()`, passing the background task that sends each notification to the `schedule` function to be run
+///
+fn when_stable_with_scheduler<TBinding, TScheduleFn>(binding: &TBinding, what: Arc<dyn Notifiable>, schedule: TScheduleFn) -> Box<dyn Releasable>
+where
+TBinding:       Changeable,
+TScheduleFn:    'static+Send+Fn(BoxFuture<'static, ()>) {
+    let state = Arc::new(Mutex::new(StableState { change_count: 0, scheduled: false }));
+
+    binding.when_changed(notify(move || {
+        // Count the change, and find out if a notification needs to be scheduled
+        let needs_schedule = {
+            let mut state = state.lock().unwrap();
+
+            state.change_count += 1;
+            !mem::replace(&mut state.scheduled, true)
+        };
+
+        if needs_schedule {
+            // The background task only holds a weak reference to the state so it can tell if this notification has been released
+            let state   = Arc::downgrade(&state);
+            let what    = Arc::clone(&what);
+
+            schedule(async move {
+                loop {
+                    let last_count = if let Some(state) = state.upgrade() { state.lock().unwrap().change_count } else { return; };
+
+                    // Yield to the scheduler so that any other pending changes can be made
+                    let mut yielded = false;
+                    future::poll_fn(move |ctxt| {
+                        if yielded {
+                            Poll::Ready(())
+                        } else {
+                            yielded = true;
+                            ctxt.waker().wake_by_ref();
+                            Poll::Pending
+                        }
+                    }).await;
+
+                    // Stop once the binding has not changed while the task was yielding
+                    let state       = if let Some(state) = state.upgrade() { state } else { return; };
+                    let mut state   = state.lock().unwrap();
+
+                    if state.change_count == last_count {
+                        state.scheduled = false;
+                        break;
+                    }
+                }
+
+                what.mark_as_changed();
+            }.boxed());
+        }
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+
+    use futures::executor;
+
+    type ScheduledTasks = Arc<Mutex<Vec<BoxFuture<'static, ()>>>>;
+
+    ///
+    /// Creates a scheduler that keeps the tasks it's given until `run_tasks()` is called
+    ///
+    fn manual_scheduler() -> (ScheduledTasks, impl 'static+Send+Fn(BoxFuture<'static, ()>)) {
+        let tasks       = Arc::new(Mutex::new(vec![]));
+        let add_tasks   = Arc::clone(&tasks);
+
+        (tasks, move |task| add_tasks.lock().unwrap().push(task))
+    }
+
+    ///
+    /// Runs the tasks that have been scheduled so far until they finish
+    ///
+    fn run_tasks(tasks: &Mutex<Vec<BoxFuture<'static, ()>>>) {
+        let scheduled = tasks.lock().unwrap().drain(..).collect::<Vec<_>>();
+
+        for task in scheduled {
+            executor::block_on(task);
+        }
+    }
+
+    #[test]
+    fn notifies_once_binding_is_stable() {
+        let binding             = bind(0);
+        let notify_count        = Arc::new(Mutex::new(0));
+        let (tasks, scheduler)  = manual_scheduler();
+
+        let count_copy          = Arc::clone(&notify_count);
+        let _lifetime           = when_stable_with_scheduler(&binding, notify(move || *count_copy.lock().unwrap() += 1), scheduler);
+
+        run_tasks(&tasks);
+        assert!(*notify_count.lock().unwrap() == 0);
+
+        // A burst of changes is coalesced into a single notification
+        for value in 1..=100 {
+            binding.set(value);
+        }
+
+        assert!(tasks.lock().unwrap().len() == 1);
+        assert!(*notify_count.lock().unwrap() == 0);
+
+        run_tasks(&tasks);
+        assert!(*notify_count.lock().unwrap() == 1);
+
+        // Changing the binding again generates a new notification
+        binding.set(101);
+
+        run_tasks(&tasks);
+        assert!(*notify_count.lock().unwrap() == 2);
+    }
+
+    #[test]
+    fn released_notification_does_not_fire() {
+        let binding             = bind(0);
+        let notify_count        = Arc::new(Mutex::new(0));
+        let (tasks, scheduler)  = manual_scheduler();
+
+        let count_copy          = Arc::clone(&notify_count);
+        let mut lifetime        = when_stable_with_scheduler(&binding, notify(move || *count_copy.lock().unwrap() += 1), scheduler);
+
+        binding.set(1);
+        lifetime.done();
+
+        run_tasks(&tasks);
+        assert!(*notify_count.lock().unwrap() == 0);
+    }
+
+    #[test]
+    fn notifies_on_background_queue() {
+        let binding             = bind(0);
+        let (sender, receiver)  = mpsc::channel();
+
+        let sender              = Mutex::new(sender);
+        let _lifetime           = when_stable(&binding, notify(move || sender.lock().unwrap().send(()).unwrap()));
+
+        binding.set(1);
+        receiver.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+    }
+}